@@ -7,13 +7,22 @@ use std::result;
 use std::sync::{Arc, Condvar};
 use adabraka_ui::components::input::{Input, InputEvent, InputVariant};
 use adabraka_ui::components::input_state::InputState;
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use futures::StreamExt;
 
-use crate::core::engine::{ActionDispatcher, QueryEngine};
-use crate::core::model::{Action, BuiltInIcon, ResultIcon, ResultItem};
+use crate::core::config::Config;
+use crate::core::engine::{ActionDispatcher, DispatchResult, QueryEngine};
+use crate::core::model::{Action, BuiltInIcon, PluginView, ResultIcon, ResultItem};
 use crate::core::plugin;
-use crate::core::plugin::{PluginContext, PluginRegistry};
+use crate::core::plugin::{PluginContext, PluginRegistry, PluginSettings};
+use crate::platform;
+use crate::services::icons::IconService;
 use crate::ui::components::result_list::ResultList;
 use crate::ui::components::search_bar::SearchBar;
+use crate::ui::navigation::{Route, Router, SavedState};
+use crate::ui::theme::{apply_theme, theme};
+use crate::ui::views::{AiChatView, LauncherView, PluginViewPanel, SaveSettings, SettingsEvent, SettingsView};
 
 actions!(Input_element, [HideApp, ExecuteSelected, NavigateDown, NavigateUp, DeleteWordBackward]);
 
@@ -29,10 +38,17 @@ pub struct app {
     selected_index: usize,
     is_searching: bool,
     scroll_handle: ScrollHandle,
+    router: Router,
+
+    config: Config,
+    plugin_settings: Vec<PluginSettings>,
+    settings_view: Option<Entity<SettingsView>>,
+    // id of the row whose action runs on the next Enter
+    pending_confirmation: Option<String>,
 }
 
 impl app {
-    pub fn new(window: &mut Window, cx: &mut Context<Self>, engine: Arc<QueryEngine>, dispatcher: Arc<ActionDispatcher>) -> Self {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>, engine: Arc<QueryEngine>, dispatcher: Arc<ActionDispatcher>, config: Config, plugin_settings: Vec<PluginSettings>) -> Self {
         let search_bar = cx.new(|_cx| SearchBar::new(_cx));
         search_bar.update(cx, |search_bar, cx| search_bar.input_state.focus_handle(cx).focus(window));
         
@@ -45,7 +61,8 @@ impl app {
             KeyBinding::new("up", NavigateUp, None),
             KeyBinding::new("tab", NavigateDown, None),
             KeyBinding::new("shift-tab", NavigateUp, None),
-            KeyBinding::new("ctrl-backspace", DeleteWordBackward, None)
+            KeyBinding::new("ctrl-backspace", DeleteWordBackward, None),
+            KeyBinding::new("ctrl-s", SaveSettings, Some("Settings"))
         ]);
         
         cx.observe_window_activation(window, |_this, window, cx| {
@@ -60,34 +77,34 @@ impl app {
             move |this, _, ev: &InputEvent, _window, cx| match ev {
                 InputEvent::Change => {
                     let value: String = input_state.read(cx).content().to_string();
+                    // the text was set by navigation, which restores its own selection
+                    if value == this.text.as_ref() {
+                        return;
+                    }
                     this.text = value.into();
                     this.selected_index = 0;
-                    let query = this.text.to_string();
-                    let engine = this.engine.clone();
-                    if query.is_empty() {
-                        this.results.clear();
-                        cx.notify();
-                    } else {
-                        this.is_searching = true;
+                    this.pending_confirmation = None;
+                    if !matches!(this.router.current(), Route::Launcher) {
+                        // plugin views filter their own items
                         cx.notify();
-
-                        cx.spawn(async move |this, cx| {
-                            let plugin_cx = PluginContext {config: serde_json::json!({})};
-                            if let Ok(new_results) = engine.search(&query, &plugin_cx).await {
-                                let _ = this.update(cx, |this, cx| {
-                                    this.results = new_results;
-                                    this.is_searching = false;
-                                    cx.notify();
-                                });
-                            }
-                        }).detach();
+                        return;
                     }
+                    this.run_search(cx);
                 }
                 _ => {}
             }
         })];
 
-        
+        // redraw once an app or file icon has been loaded in the background
+        let mut icon_events = IconService::global().subscribe();
+        cx.spawn(async move |this, cx| {
+            while icon_events.next().await.is_some() {
+                if this.update(cx, |_this, cx| cx.notify()).is_err() {
+                    break;
+                }
+            }
+        }).detach();
+
         Self {
             search_bar,
             text: SharedString::default(),
@@ -100,26 +117,199 @@ impl app {
             is_searching: false,
             dispatcher,
             scroll_handle: ScrollHandle::new(),
+            router: Router::new(),
+
+            config,
+            plugin_settings,
+            settings_view: None,
+            pending_confirmation: None,
+        }
+    }
+
+    fn run_search(&mut self, cx: &mut Context<Self>) {
+        let query = self.text.to_string();
+        let engine = self.engine.clone();
+        let plugin_cx = PluginContext::new(&self.config);
+        let max_results = self.config.general.max_results;
+        if query.is_empty() {
+            self.results.clear();
+            cx.notify();
+        } else {
+            self.is_searching = true;
+            cx.notify();
+
+            cx.spawn(async move |this, cx| {
+                if let Ok(new_results) = engine.search(&query, &plugin_cx).await {
+                    let _ = this.update(cx, |this, cx| {
+                        this.results = new_results.into_iter().take(max_results).collect();
+                        this.is_searching = false;
+                        cx.notify();
+                    });
+                }
+            }).detach();
+        }
+    }
+
+    // Rows of the current view that can be selected and executed
+    fn visible_items(&self) -> Vec<ResultItem> {
+        let mut items = match self.router.current() {
+            Route::Launcher => self.results.clone(),
+            Route::Plugin { view: PluginView::List { items, .. }, .. } if self.text.is_empty() => items.clone(),
+            Route::Plugin { view: PluginView::List { items, .. }, .. } => {
+                let matcher = SkimMatcherV2::default();
+                let mut scored: Vec<(i64, &ResultItem)> = items.iter()
+                    .filter_map(|item| matcher.fuzzy_match(&item.title, &self.text).map(|score| (score, item)))
+                    .collect();
+                scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+                scored.into_iter().map(|(_, item)| item.clone()).collect()
+            }
+            Route::Plugin { view: PluginView::Detail { actions, .. }, .. } => actions.clone(),
+            Route::Settings | Route::AiChat => Vec::new(),
+        };
+        // the row waiting for a second Enter says so instead of its usual subtitle
+        if let Some(pending) = &self.pending_confirmation {
+            for item in items.iter_mut().filter(|item| &item.id == pending) {
+                if let Action::Confirm { message, .. } = &item.action {
+                    item.subtitle = Some(format!("{} Press Enter again to confirm", message).into());
+                }
+            }
+        }
+        items
+    }
+
+    fn push_route(&mut self, route: Route, window: &mut Window, cx: &mut Context<Self>) {
+        let saved = SavedState {
+            query: self.text.to_string(),
+            selected_index: self.selected_index,
+        };
+        self.router.push(route, saved);
+        self.set_query(String::new(), 0, window, cx);
+    }
+
+    fn navigate_back(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(saved) = self.router.pop() {
+            if !matches!(self.router.current(), Route::Settings) {
+                self.settings_view = None;
+            }
+            self.set_query(saved.query, saved.selected_index, window, cx);
         }
     }
-    
+
+    fn open_settings(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let config = self.config.clone();
+        let plugin_settings = self.plugin_settings.clone();
+        let settings_view = cx.new(|cx| SettingsView::new(config, plugin_settings, window, cx));
+        cx.subscribe(&settings_view, |this, _, event: &SettingsEvent, cx| match event {
+            SettingsEvent::Saved(config) => {
+                if config.appearance.theme != this.config.appearance.theme {
+                    apply_theme(&config.appearance.theme, cx);
+                }
+                if config.hotkeys.show_launcher != this.config.hotkeys.show_launcher {
+                    if let Err(e) = platform::hotkey::change_hotkey(&config.hotkeys.show_launcher) {
+                        log::warn!("Can't switch the launcher hotkey to {:?}: {}", config.hotkeys.show_launcher, e);
+                    }
+                }
+                this.config = config.clone();
+                cx.notify();
+            }
+        }).detach();
+        self.settings_view = Some(settings_view);
+        self.push_route(Route::Settings, window, cx);
+    }
+
+    // Updates the search bar without triggering a new search
+    fn set_query(&mut self, query: String, selected_index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.text = query.clone().into();
+        self.selected_index = selected_index;
+        self.pending_confirmation = None;
+        self.search_bar.update(cx, |search_bar, cx| {
+            search_bar.input_state.update(cx, |input_state, cx| input_state.set_value(query, window, cx));
+        });
+        self.scroll_handle.scroll_to_item(self.selected_index);
+        cx.notify();
+    }
+
     fn hide_app(&mut self, _: &HideApp, window: &mut Window, cx: &mut Context<Self>) {
+        if self.router.can_pop() {
+            self.navigate_back(window, cx);
+            return;
+        }
         println!("Hiding app!!");
         window.hide_window();
     }
     fn execute_selected(&mut self, _: &ExecuteSelected, window: &mut Window, cx: &mut Context<Self>) {
-        if let Some(result) = self.results.get(self.selected_index) {
+        if let Some(result) = self.visible_items().get(self.selected_index) {
             let action = result.action.clone();
             let plugin_id = result.plugin_id.to_string();
-            let context = PluginContext {config: serde_json::json!({})};
-            self.dispatcher.execute(plugin_id, action, context);
-            window.hide_window();
+            let item_id = result.id.clone();
+            let context = PluginContext::new(&self.config);
+            // a second Enter on the same row runs what the first one asked to confirm
+            let confirmed = action.requires_confirmation() && self.pending_confirmation.as_ref() == Some(&item_id);
+            self.pending_confirmation = None;
+            if confirmed && matches!(&action, Action::Confirm { action, .. } if action.is_immediate()) {
+                self.dispatcher.confirm(plugin_id, action, context);
+                self.router.reset();
+                window.hide_window();
+                return;
+            }
+            if let Action::OpenSettings = action {
+                self.open_settings(window, cx);
+                return;
+            }
+            if let Action::InsertText(text) = &action {
+                // back to the launcher with the text as the new query
+                self.router.reset();
+                self.settings_view = None;
+                self.set_query(text.clone(), 0, window, cx);
+                self.run_search(cx);
+                return;
+            }
+            if action.is_immediate() {
+                self.dispatcher.execute(plugin_id, action, context);
+                self.router.reset();
+                window.hide_window();
+                return;
+            }
+            let task = if confirmed {
+                self.dispatcher.confirm(plugin_id, action, context)
+            } else {
+                self.dispatcher.dispatch(plugin_id, action, context)
+            };
+            cx.spawn_in(window, async move |this, cx| {
+                match task.await {
+                    Ok(Ok(DispatchResult::ShowView { plugin_id, view })) => {
+                        let _ = this.update_in(cx, |this, window, cx| {
+                            this.push_route(Route::Plugin { plugin_id, view }, window, cx);
+                        });
+                    }
+                    Ok(Ok(DispatchResult::ConfirmationRequired { .. })) => {
+                        let _ = this.update(cx, |this, cx| {
+                            this.pending_confirmation = Some(item_id);
+                            cx.notify();
+                        });
+                    }
+                    Ok(Ok(DispatchResult::Executed)) => {}
+                    Ok(Err(e)) => log::error!("Failed to open plugin view: {}", e),
+                    Err(e) => log::error!("Plugin view task failed: {}", e),
+                }
+            }).detach();
+        }
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        // backspace on an empty search bar goes back to the previous view
+        let search_focused = self.search_bar.read(cx).input_state.focus_handle(cx).is_focused(window);
+        if event.keystroke.key == "backspace" && search_focused && self.text.is_empty() && self.router.can_pop() {
+            self.navigate_back(window, cx);
+            cx.stop_propagation();
         }
     }
 
     fn navigate_down(&mut self, _: &NavigateDown, window: &mut Window, cx: &mut Context<Self>) {
-        if !self.results.is_empty() {
-            self.selected_index = (self.selected_index + 1) % self.results.len();
+        self.pending_confirmation = None;
+        let len = self.visible_items().len();
+        if len > 0 {
+            self.selected_index = (self.selected_index + 1) % len;
             cx.notify();
             // scrolling to the item
             self.scroll_handle.scroll_to_item(self.selected_index);
@@ -127,11 +317,13 @@ impl app {
     }
 
     fn navigate_up(&mut self, _: &NavigateUp, window: &mut Window, cx: &mut Context<Self>) {
-        if !self.results.is_empty() {
+        self.pending_confirmation = None;
+        let len = self.visible_items().len();
+        if len > 0 {
             if self.selected_index == 0 {
-                self.selected_index = self.results.len() - 1;
+                self.selected_index = len - 1;
             } else {
-                self.selected_index = (self.selected_index - 1) % self.results.len();
+                self.selected_index = (self.selected_index - 1) % len;
             }
             cx.notify();
             // scrolling to the item
@@ -158,15 +350,26 @@ impl Render for app {
         let search_bar_height = 60.0_f32;  // search bar + margin
         let row_height = 60.0_f32;
         let padding = 16.0_f32;  // p_2 top + bottom
-        
-        let results_height = if self.results.is_empty() {
-            0.0
-        } else {
-            let raw = (self.results.len() as f32 * row_height) + padding;
-            raw.min(400.0 + padding)  // capped at max_h(400) + padding
+        let header_height = 34.0_f32;  // plugin header + gap
+        let detail_height = 244.0_f32;  // detail body + gap
+
+        let items = self.visible_items();
+        let rows_height = (items.len() as f32 * row_height).min(400.0);  // capped at max_h(400)
+        let results_height = match self.router.current() {
+            Route::Launcher if items.is_empty() => 0.0,
+            Route::Launcher => rows_height + padding,
+            Route::Plugin { view: PluginView::List { .. }, .. } => {
+                header_height + rows_height.max(row_height) + padding
+            }
+            Route::Plugin { view: PluginView::Detail { .. }, .. } => {
+                header_height + detail_height + rows_height + padding
+            }
+            Route::Settings => header_height + 400.0 + 34.0 + padding,  // list + footer
+            Route::AiChat => header_height + row_height + padding,
         };
-        
+
         let total_height = search_bar_height + results_height;
+        let theme = theme(cx).clone();
         
         // Resize the window dynamically
         window.resize(size(px(610.0), px(total_height)));
@@ -174,7 +377,7 @@ impl Render for app {
         div()
             .flex()
             .flex_col()
-            .bg(rgb(0x13144A))
+            .bg(theme.background)
             .w(px(610.0))
             .p_0()
             .m_0()
@@ -188,6 +391,7 @@ impl Render for app {
             .on_action(cx.listener(Self::navigate_down))
             .on_action(cx.listener(Self::navigate_up))
             .on_action(cx.listener(Self::delete_word_backward))
+            .capture_key_down(cx.listener(Self::on_key_down))
             .child(
                 Input::new(&self.search_bar.read(cx).input_state)
                     .placeholder("Type to search...")
@@ -197,19 +401,22 @@ impl Render for app {
                     .text_size(px(30.0))
                     .line_height(px(35.0))
                     .m_1()
-                    .bg(rgb(0x13144A))
+                    .bg(theme.background)
                     .variant(InputVariant::Ghost)
                     .justify_center()
             )
             .child(
-                div()
-                    .flex()
-                    .p_2()
-                    .flex_col()
-                    .gap_1()
-                    .child(
-                        ResultList::new(self.results.clone(), self.selected_index, self.is_searching, self.scroll_handle.clone())
-                    ) 
+                match self.router.current() {
+                    Route::Launcher => LauncherView::new(items, self.selected_index, self.is_searching, self.scroll_handle.clone())
+                        .into_any_element(),
+                    Route::Plugin { view, .. } => PluginViewPanel::new(view.clone(), items, self.selected_index, self.scroll_handle.clone())
+                        .into_any_element(),
+                    Route::Settings => match &self.settings_view {
+                        Some(settings_view) => settings_view.clone().into_any_element(),
+                        None => div().into_any_element(),
+                    },
+                    Route::AiChat => AiChatView::new().into_any_element(),
+                }
             )
     }
 }
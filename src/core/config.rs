@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use toml_edit::{DocumentMut, Item, Table};
+
+const DEFAULT_CONFIG_PATH: &str = "config/default.toml";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub general: GeneralConfig,
     pub appearance: AppearanceConfig,
@@ -10,12 +14,14 @@ pub struct Config {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GeneralConfig {
     pub max_results: usize,
-    telemetry: bool,
+    pub telemetry: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppearanceConfig {
     pub theme: String,
     pub window_width: u32,
@@ -23,20 +29,48 @@ pub struct AppearanceConfig {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct HotkeysConfig {
     pub show_launcher: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PluginsConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub calculator: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ai: Option<serde_json::Value>,
+    // Every other `[plugins.<id>]` table, keyed by plugin id
+    #[serde(flatten)]
     pub custom: std::collections::HashMap<String, serde_json::Value>,
 }
 
+// Directory holding the user's config.toml and other user editable files
+pub fn config_dir() -> PathBuf {
+    dirs::config_dir()
+        .map(|dir| dir.join("Catalyst"))
+        .unwrap_or_else(|| PathBuf::from("config"))
+}
+
+// Directory for state the launcher keeps between runs, like history and caches
+pub fn data_dir() -> PathBuf {
+    dirs::data_dir()
+        .map(|dir| dir.join("Catalyst"))
+        .unwrap_or_else(|| PathBuf::from("data"))
+}
+
 impl Config {
-    pub fn laod() -> anyhow::Result<Self> {
-        let config_path = PathBuf::from("config/default.toml");
+    pub fn user_path() -> PathBuf {
+        config_dir().join("config.toml")
+    }
+    pub fn load() -> anyhow::Result<Self> {
+        let user_path = Self::user_path();
+        let config_path = if user_path.exists() {
+            user_path
+        } else {
+            PathBuf::from(DEFAULT_CONFIG_PATH)
+        };
         if config_path.exists() {
             let content = std::fs::read_to_string(&config_path)?;
             let config: Config = toml::from_str(&content)?;
@@ -46,28 +80,118 @@ impl Config {
             Ok(Self::default())
         }
     }
+    // Writes to the user config, keeping the comments and key order of the file it was loaded from
+    pub fn save(&self) -> anyhow::Result<PathBuf> {
+        let user_path = Self::user_path();
+        let existing = std::fs::read_to_string(&user_path)
+            .or_else(|_| std::fs::read_to_string(DEFAULT_CONFIG_PATH))
+            .unwrap_or_default();
+        let content = self.merge_into(&existing)?;
+        if let Some(parent) = user_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&user_path, content)?;
+        log::info!("Config saved to {}", user_path.display());
+        Ok(user_path)
+    }
+    fn merge_into(&self, existing: &str) -> anyhow::Result<String> {
+        let mut document = existing.parse::<DocumentMut>().unwrap_or_default();
+        let updated = toml::to_string(self)?.parse::<DocumentMut>()?;
+        merge_table(document.as_table_mut(), updated.as_table());
+        Ok(document.to_string())
+    }
+}
+
+fn merge_table(existing: &mut Table, updated: &Table) {
+    for (key, item) in updated.iter() {
+        match existing.get_mut(key) {
+            Some(current) => merge_item(current, item),
+            None => {
+                existing.insert(key, item.clone());
+            }
+        }
+    }
+}
+
+fn merge_item(existing: &mut Item, updated: &Item) {
+    match (existing, updated) {
+        (Item::Table(current), Item::Table(updated)) => merge_table(current, updated),
+        (Item::Value(current), Item::Value(updated)) => {
+            // keep the comments and spacing around the old value
+            let decor = current.decor().clone();
+            *current = updated.clone();
+            *current.decor_mut() = decor;
+        }
+        (current, updated) => *current = updated.clone(),
+    }
+}
+
+impl Default for GeneralConfig {
+    fn default() -> Self {
+        Self {
+            max_results: 10,
+            telemetry: false,
+        }
+    }
+}
+
+impl Default for AppearanceConfig {
+    fn default() -> Self {
+        Self {
+            theme: "dark".to_string(),
+            window_width: 600,
+            window_height: 500,
+        }
+    }
+}
+
+impl Default for HotkeysConfig {
+    fn default() -> Self {
+        Self {
+            show_launcher: "Alt+Space".to_string(),
+        }
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
-            general: GeneralConfig {
-                max_results: 10,
-                telemetry: false,
-            },
-            appearance: AppearanceConfig {
-                theme: "light".to_string(),
-                window_width: 600,
-                window_height: 500,
-            },
-            hotkeys: HotkeysConfig {
-                show_launcher: "Alt+Space".to_string(),
-            },
-            plugins: PluginsConfig {
-                calculator: None,
-                ai: None,
-                custom: std::collections::HashMap::new(),
-            },
+            general: GeneralConfig::default(),
+            appearance: AppearanceConfig::default(),
+            hotkeys: HotkeysConfig::default(),
+            plugins: PluginsConfig::default(),
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMENTED: &str = "# Launcher settings\n[general]\nmax_results = 10 # rows shown\ntelemetry = false\n\n[appearance]\ntheme = \"light\"\nwindow_width = 600\nwindow_height = 500\n\n[hotkeys]\nshow_launcher = \"Alt+Space\"\n\n[plugins]\n# Plugin-specific configs will go here\n";
+
+    #[test]
+    fn test_default_config_parses() {
+        let config: Config = toml::from_str(COMMENTED).unwrap();
+        assert_eq!(config.general.max_results, 10);
+        assert!(config.plugins.custom.is_empty());
+    }
+
+    #[test]
+    fn test_merge_keeps_comments_and_order() {
+        let mut config: Config = toml::from_str(COMMENTED).unwrap();
+        config.general.max_results = 25;
+        config.appearance.theme = "dark".to_string();
+        config.plugins.custom.insert("app_search".to_string(), serde_json::json!({"max_results": 5}));
+
+        let merged = config.merge_into(COMMENTED).unwrap();
+        assert!(merged.starts_with("# Launcher settings\n[general]\nmax_results = 25 # rows shown\n"));
+        assert!(merged.contains("theme = \"dark\""));
+        assert!(merged.contains("# Plugin-specific configs will go here"));
+        assert!(merged.find("[general]").unwrap() < merged.find("[appearance]").unwrap());
+
+        let reloaded: Config = toml::from_str(&merged).unwrap();
+        assert_eq!(reloaded.general.max_results, 25);
+        assert_eq!(reloaded.plugins.custom["app_search"]["max_results"], 5);
+    }
 }
\ No newline at end of file
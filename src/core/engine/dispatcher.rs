@@ -4,9 +4,24 @@ use std::sync::Arc;
 
 use crate::core::{
     plugin::{PluginRegistry, PluginContext},
-    model::Action,
+    model::{Action, PluginView},
 };
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum DispatchResult {
+    // The action ran to completion and the launcher can be hidden
+    Executed,
+    // The action asked for a view that the UI should push on the router
+    ShowView {
+        plugin_id: String,
+        view: PluginView,
+    },
+    // Nothing ran, the action has to be confirmed with `confirm` first
+    ConfirmationRequired {
+        message: String,
+    },
+}
+
 pub struct ActionDispatcher {
     registry: Arc<PluginRegistry>,
     runtime: Arc<tokio::runtime::Runtime>
@@ -27,6 +42,9 @@ impl ActionDispatcher {
         ) -> tokio::task::JoinHandle<anyhow::Result<()>> {
             let registry = self.registry.clone();
             self.runtime.spawn(async move {
+                if action.requires_confirmation() {
+                    anyhow::bail!("Action from plugin {} needs to be confirmed first.", plugin_id);
+                }
                 log::info!("Executing action from plugin: {}", plugin_id);
                 let plugin = registry.get(&plugin_id).await.ok_or_else(|| {
                     anyhow::anyhow!("Plugin {} not found.", plugin_id)
@@ -36,4 +54,52 @@ impl ActionDispatcher {
                 Ok(())
             })
     }
+    // Immediate actions are executed, anything else resolves to the view the owning plugin provides
+    pub fn dispatch(
+            &self,
+            plugin_id: String,
+            action: Action,
+            context: PluginContext,
+        ) -> tokio::task::JoinHandle<anyhow::Result<DispatchResult>> {
+            let registry = self.registry.clone();
+            self.runtime.spawn(async move {
+                if let Action::Confirm { message, .. } = &action {
+                    log::info!("Action from plugin {} is waiting for confirmation", plugin_id);
+                    return Ok(DispatchResult::ConfirmationRequired { message: message.clone() });
+                }
+                if action.is_immediate() {
+                    log::info!("Executing action from plugin: {}", plugin_id);
+                    let plugin = registry.get(&plugin_id).await.ok_or_else(|| {
+                        anyhow::anyhow!("Plugin {} not found.", plugin_id)
+                    })?;
+                    plugin.execute(&action, &context).await?;
+                    log::info!("Action executed successfully.");
+                    return Ok(DispatchResult::Executed);
+                }
+                let target = match &action {
+                    Action::ShowPluginView { plugin_id: target, .. } => target.clone(),
+                    _ => plugin_id.clone(),
+                };
+                log::info!("Opening view of plugin: {}", target);
+                let plugin = registry.get(&target).await.ok_or_else(|| {
+                    anyhow::anyhow!("Plugin {} not found.", target)
+                })?;
+                let view = plugin.view(&action, &context).await.ok_or_else(|| {
+                    anyhow::anyhow!("Plugin {} has no view for {:?}.", target, action)
+                })?;
+                Ok(DispatchResult::ShowView { plugin_id: target, view })
+            })
+    }
+    // Runs an action the user confirmed, unwrapping `Action::Confirm`
+    pub fn confirm(
+            &self,
+            plugin_id: String,
+            action: Action,
+            context: PluginContext,
+        ) -> tokio::task::JoinHandle<anyhow::Result<DispatchResult>> {
+            match action {
+                Action::Confirm { action, .. } => self.dispatch(plugin_id, *action, context),
+                action => self.dispatch(plugin_id, action, context),
+            }
+    }
 }
\ No newline at end of file
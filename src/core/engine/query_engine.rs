@@ -34,7 +34,7 @@ impl QueryEngine {
         
         let mut tasks = JoinSet::new();
         for plugin in plugins {
-            let query = query.to_lowercase();
+            let query = if plugin.keeps_case() { query.to_string() } else { query.to_lowercase() };
             let context_clone = context.clone();
             tasks.spawn_on(async move {
                 let plugin_query = plugin.trigger().matches(&query).unwrap_or_default();
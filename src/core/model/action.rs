@@ -16,7 +16,9 @@ pub enum Action {
     InsertText(String),
     // For complex plugins like AI
     ShowPluginView {
-        plugin_id: String
+        plugin_id: String,
+        view_id: String,
+        data: serde_json::Value
     },
     OpenSettings,
     // Custom action with json data
@@ -25,6 +27,11 @@ pub enum Action {
         data: serde_json::Value
     },
     SystemCommand(String),
+    // Runs the inner action only after the user confirmed it, for anything destructive
+    Confirm {
+        action: Box<Action>,
+        message: String
+    },
     Exit,
     // Do nothing
     None,
@@ -32,6 +39,15 @@ pub enum Action {
 
 impl Action {
     pub fn is_immediate(&self) -> bool {
-        !matches!(self, Action::ShowPluginView { .. })
+        !matches!(self, Action::ShowPluginView { .. } | Action::Confirm { .. })
+    }
+    pub fn confirm(self, message: impl Into<String>) -> Self {
+        Action::Confirm {
+            action: Box::new(self),
+            message: message.into(),
+        }
+    }
+    pub fn requires_confirmation(&self) -> bool {
+        matches!(self, Action::Confirm { .. })
     }
 }
\ No newline at end of file
@@ -1,5 +1,7 @@
 mod action;
 mod result_item;
+mod plugin_view;
 
 pub use action::*;
-pub use result_item::*;
\ No newline at end of file
+pub use result_item::*;
+pub use plugin_view::*;
\ No newline at end of file
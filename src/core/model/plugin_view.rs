@@ -0,0 +1,47 @@
+use gpui::SharedString;
+
+use super::ResultItem;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PluginView {
+    // A filterable list of rows, rendered like the launcher results
+    List {
+        title: SharedString,
+        items: Vec<ResultItem>,
+    },
+    // A block of text with the actions that can be run on it
+    Detail {
+        title: SharedString,
+        body: SharedString,
+        actions: Vec<ResultItem>,
+    },
+}
+
+impl PluginView {
+    pub fn list(title: impl Into<SharedString>, items: Vec<ResultItem>) -> Self {
+        PluginView::List {
+            title: title.into(),
+            items,
+        }
+    }
+    pub fn detail(title: impl Into<SharedString>, body: impl Into<SharedString>, actions: Vec<ResultItem>) -> Self {
+        PluginView::Detail {
+            title: title.into(),
+            body: body.into(),
+            actions,
+        }
+    }
+    pub fn title(&self) -> &SharedString {
+        match self {
+            PluginView::List { title, .. } => title,
+            PluginView::Detail { title, .. } => title,
+        }
+    }
+    // Rows that can be selected and executed in this view
+    pub fn items(&self) -> &[ResultItem] {
+        match self {
+            PluginView::List { items, .. } => items,
+            PluginView::Detail { actions, .. } => actions,
+        }
+    }
+}
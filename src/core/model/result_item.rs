@@ -27,6 +27,26 @@ pub enum BuiltInIcon {
     Exit
 }
 
+impl BuiltInIcon {
+    // Path of the icon inside the assets directory
+    pub fn asset_path(&self) -> &'static str {
+        match self {
+            BuiltInIcon::Calculator => "icons/calculator.svg",
+            BuiltInIcon::Search => "icons/search.svg",
+            BuiltInIcon::File => "icons/file.svg",
+            BuiltInIcon::Folder => "icons/folder.svg",
+            BuiltInIcon::Terminal => "icons/terminal.svg",
+            BuiltInIcon::Settings => "icons/settings.svg",
+            BuiltInIcon::AI => "icons/ai.svg",
+            BuiltInIcon::Web => "icons/web.svg",
+            BuiltInIcon::App => "icons/app.svg",
+            BuiltInIcon::Dictionary => "icons/dictionary.svg",
+            BuiltInIcon::IP => "icons/ip.svg",
+            BuiltInIcon::Exit => "icons/exit.svg",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ResultItem {
     pub id: String,
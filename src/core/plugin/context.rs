@@ -1,25 +1,42 @@
 use gpui:: Entity;
 use serde_json::Value;
 
+use crate::core::config::Config;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct PluginContext {
+    // The `[plugins]` table of the config, keyed by plugin id
     pub config: Value,
     // pub app: Entity<crate::app::LauncherApp>
 }
 
 impl PluginContext {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            config: serde_json::to_value(&config.plugins).unwrap_or(Value::Null),
+        }
+    }
     // pub fn new(config: Value, app: Entity<crate::app::LauncherApp>) -> Self {
     //     Self {
     //         config,
     //         app
     //     }
     // }
-    // pub fn get_config<T>(&self, key: &str) -> Option<T>
-    // where
-    //     T: serde::de::DeserializeOwned,
-    // {
-    //     self.config
-    //         .get(key)
-    //         .and_then(|v| serde_json::from_value(v.clone()).ok())
-    // }
+    pub fn get_config<T>(&self, key: &str) -> Option<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.config
+            .get(key)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+    pub fn get_setting<T>(&self, plugin_id: &str, key: &str) -> Option<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.config
+            .get(plugin_id)
+            .and_then(|v| v.get(key))
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
 }
\ No newline at end of file
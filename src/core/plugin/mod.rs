@@ -2,8 +2,10 @@ mod context;
 mod registry;
 mod r#trait;
 mod trigger;
+mod settings;
 
 pub use context::*;
 pub use registry::*;
 pub use r#trait::*;
-pub use trigger::*;
\ No newline at end of file
+pub use trigger::*;
+pub use settings::*;
\ No newline at end of file
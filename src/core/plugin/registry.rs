@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use super::{Plugin, Trigger};
+use super::{Plugin, PluginSettings, Trigger};
 
 pub struct PluginRegistry {
     plugins: Arc<RwLock<HashMap<String, Arc<dyn Plugin>>>>,
@@ -34,10 +34,11 @@ impl PluginRegistry {
         plugins.get(plugin_id).cloned()
     }
     pub async fn match_plugins(&self, query: &str) -> Vec<Arc<dyn Plugin>> {
+        let query = query.to_lowercase();
         let plugins = self.plugins.read().await;
         let mut matched = Vec::new();
         for plugin in plugins.values() {
-            if plugin.trigger().matches(query).is_some() {
+            if plugin.trigger().matches(&query).is_some() {
                 matched.push(plugin.clone());
             }
         }
@@ -52,6 +53,19 @@ impl PluginRegistry {
         let plugins = self.plugins.read().await;
         plugins.values().cloned().collect()
     }
+    pub async fn settings_schemas(&self) -> Vec<PluginSettings> {
+        let plugins = self.plugins.read().await;
+        let mut schemas: Vec<PluginSettings> = plugins.values()
+            .map(|p| PluginSettings {
+                plugin_id: p.id().to_string(),
+                name: p.name().to_string(),
+                fields: p.settings_schema(),
+            })
+            .filter(|s| !s.fields.is_empty())
+            .collect();
+        schemas.sort_by(|a, b| a.name.cmp(&b.name));
+        schemas
+    }
     pub async fn count(&self) -> usize {
         let plugins = self.plugins.read().await;
         plugins.len()
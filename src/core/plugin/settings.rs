@@ -0,0 +1,51 @@
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingKind {
+    Toggle,
+    Number,
+    Text,
+    Choice(Vec<String>),
+    Hotkey,
+}
+
+// A single editable value, stored under `[<section>] key = ...` in the config file
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingField {
+    pub key: String,
+    pub label: String,
+    pub kind: SettingKind,
+    pub default: Value,
+    // shown under the label, for values that don't take effect right away
+    pub note: Option<String>,
+}
+
+impl SettingField {
+    pub fn toggle(key: impl Into<String>, label: impl Into<String>, default: bool) -> Self {
+        Self { key: key.into(), label: label.into(), kind: SettingKind::Toggle, default: Value::from(default), note: None }
+    }
+    pub fn number(key: impl Into<String>, label: impl Into<String>, default: i64) -> Self {
+        Self { key: key.into(), label: label.into(), kind: SettingKind::Number, default: Value::from(default), note: None }
+    }
+    pub fn text(key: impl Into<String>, label: impl Into<String>, default: impl Into<String>) -> Self {
+        Self { key: key.into(), label: label.into(), kind: SettingKind::Text, default: Value::from(default.into()), note: None }
+    }
+    pub fn choice(key: impl Into<String>, label: impl Into<String>, options: Vec<String>, default: impl Into<String>) -> Self {
+        Self { key: key.into(), label: label.into(), kind: SettingKind::Choice(options), default: Value::from(default.into()), note: None }
+    }
+    pub fn hotkey(key: impl Into<String>, label: impl Into<String>, default: impl Into<String>) -> Self {
+        Self { key: key.into(), label: label.into(), kind: SettingKind::Hotkey, default: Value::from(default.into()), note: None }
+    }
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
+}
+
+// Settings a plugin exposes in the settings view, saved under `[plugins.<plugin_id>]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluginSettings {
+    pub plugin_id: String,
+    pub name: String,
+    pub fields: Vec<SettingField>,
+}
@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 
-use crate::core::model::{Action, ResultItem};
-use super::{PluginContext, Trigger};
+use crate::core::model::{Action, PluginView, ResultItem};
+use super::{PluginContext, SettingField, Trigger};
 
 #[async_trait]
 pub trait Plugin: Send + Sync + 'static {
@@ -11,8 +11,15 @@ pub trait Plugin: Send + Sync + 'static {
     fn trigger(&self) -> Trigger;
     async fn search(&self, query: &str, context: &PluginContext) -> Vec<ResultItem>;
     async fn execute(&self, action: &Action, context: &PluginContext) -> anyhow::Result<()>;
+    // Builds the view requested by an `Action::ShowPluginView` owned by this plugin
+    async fn view(&self, _action: &Action, _context: &PluginContext) -> Option<PluginView> {None}
     async fn initialize(&mut self, _context: &PluginContext) -> anyhow::Result<()> {Ok(())}
     async fn cleanup(&mut self) -> anyhow::Result<()> {Ok(())}
     fn requires_initialization(&self) -> bool {false}
     fn priority(&self) -> i32 {0}
+    // Searches get the query lowercased unless the plugin needs it as typed, like units ("MB"
+    // and "Mb") or shell commands
+    fn keeps_case(&self) -> bool {false}
+    // Fields shown for this plugin in the settings view
+    fn settings_schema(&self) -> Vec<SettingField> {Vec::new()}
 }
\ No newline at end of file
@@ -1,3 +1,7 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use regex::Regex;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Trigger {
     Implicit,
@@ -5,6 +9,23 @@ pub enum Trigger {
     Pattern(String),
 }
 
+// Patterns are compiled once, on the first query; an invalid one is kept as None so it only
+// warns once instead of on every keystroke
+fn compiled(pattern: &str) -> Option<Regex> {
+    static CACHE: OnceLock<Mutex<HashMap<String, Option<Regex>>>> = OnceLock::new();
+    let mut cache = CACHE.get_or_init(Default::default).lock().unwrap();
+    cache
+        .entry(pattern.to_string())
+        .or_insert_with(|| match Regex::new(pattern) {
+            Ok(regex) => Some(regex),
+            Err(e) => {
+                log::warn!("Invalid trigger pattern {}: {}", pattern, e);
+                None
+            }
+        })
+        .clone()
+}
+
 impl Trigger {
     pub fn matches(&self, query: &str) -> Option<String> {
         match self {
@@ -13,15 +34,18 @@ impl Trigger {
             }
             Trigger::Explicit {keyword} => {
                 let trimmed = query.trim();
-                if trimmed.starts_with(keyword) {
-                    let string = trimmed[keyword.len()..].trim_start();
-                    Some(string.to_string())
-                } else {
-                    None
-                }   
+                // "IP" and "Calc" work like "ip" and "calc"
+                match trimmed.get(..keyword.len()) {
+                    Some(start) if start.eq_ignore_ascii_case(keyword) => {
+                        Some(trimmed[keyword.len()..].trim_start().to_string())
+                    }
+                    _ => None,
+                }
             }
-            Trigger::Pattern(_pattern) => {
-                None
+            // a regex over the trimmed query, which is handed to the plugin whole
+            Trigger::Pattern(pattern) => {
+                let trimmed = query.trim();
+                compiled(pattern).filter(|regex| regex.is_match(trimmed)).map(|_| trimmed.to_string())
             }
         }
     }
@@ -55,5 +79,18 @@ mod tests {
             Some("".to_string())
         );
         assert_eq!(trigger.matches("hello"), None);
+        assert_eq!(trigger.matches("AI Hello"), Some("Hello".to_string()));
+        assert_eq!(trigger.matches("Ai"), Some("".to_string()));
+    }
+
+    #[test]
+    fn test_pattern_trigger() {
+        let trigger = Trigger::Pattern(r"^\d+\.\d+\.\d+\.\d+/\d+$".to_string());
+        assert_eq!(trigger.matches(" 10.0.0.0/22 "), Some("10.0.0.0/22".to_string()));
+        assert_eq!(trigger.matches("10.0.0.0"), None);
+        // the second query goes through the cached regex
+        assert_eq!(trigger.matches("192.168.1.0/24"), Some("192.168.1.0/24".to_string()));
+        assert_eq!(Trigger::Pattern("(".to_string()).matches("("), None);
+        assert_eq!(Trigger::Pattern("(".to_string()).matches("("), None);
     }
 }
\ No newline at end of file
@@ -24,12 +24,15 @@ use gpui::WindowKind;
 use gpui::WindowBackgroundAppearance::Transparent;
 
 use raw_window_handle::HasWindowHandle;
+#[cfg(target_os = "windows")]
 use windows::Win32::Foundation::HWND;
+#[cfg(target_os = "windows")]
 use windows::Win32::UI::WindowsAndMessaging::{
     SetWindowPos, HWND_TOPMOST, SWP_NOMOVE, SWP_NOSIZE, SWP_SHOWWINDOW,
 };
 
 
+use crate::core::config::Config;
 use crate::core::engine::ActionDispatcher;
 
 struct Assets {
@@ -57,16 +60,19 @@ impl AssetSource for Assets {
 }
 
 fn main() {
-    Application::new().run(|cx: &mut App| {
+    Application::new().with_assets(Assets { base: PathBuf::from("assets") }).run(|cx: &mut App| {
         adabraka_ui::init(cx);
         adabraka_ui::set_icon_base_path("assets/icons");
-        let mut theme = Theme::dark();
-        theme.tokens.ring = gpui::transparent_black();
-        install_theme(cx, theme);
-        
+
+        let config = Config::load().unwrap_or_else(|e| {
+            log::error!("Failed to load config, using defaults: {}", e);
+            Config::default()
+        });
+        crate::ui::theme::apply_theme(&config.appearance.theme, cx);
+
         let registry = std::sync::Arc::new(crate::core::plugin::PluginRegistry::new());
         let dispatcher = std::sync::Arc::new(ActionDispatcher::new(registry.clone()));
-        futures::executor::block_on(async {
+        let plugin_settings = futures::executor::block_on(async {
             let app_search_plugin: std::sync::Arc<dyn crate::core::plugin::Plugin> = std::sync::Arc::new(crate::plugins::implicit::app_search::AppSearchPlugin::new());
             let _ = registry.register(app_search_plugin).await;
             let testing_implicit_plugin: std::sync::Arc<dyn crate::core::plugin::Plugin> = std::sync::Arc::new(crate::plugins::implicit::testing_implicit::TestingImplicitPlugin::new());
@@ -75,7 +81,16 @@ fn main() {
             let _ = registry.register(testing_explicit_plugin).await;
             let ip_plugin: std::sync::Arc<dyn crate::core::plugin::Plugin> = std::sync::Arc::new(crate::plugins::explicit::ip::IpPlugin::new());
             let _ = registry.register(ip_plugin).await;
-            let calculator_plugin: std::sync::Arc<dyn crate::core::plugin::Plugin> = std::sync::Arc::new(crate::plugins::implicit::calculator::CalculatorPlugin::new());
+            let network_tools_plugin: std::sync::Arc<dyn crate::core::plugin::Plugin> = std::sync::Arc::new(crate::plugins::explicit::network_tools::NetworkToolsPlugin::new());
+            let _ = registry.register(network_tools_plugin).await;
+            let window_switcher_plugin: std::sync::Arc<dyn crate::core::plugin::Plugin> = std::sync::Arc::new(crate::plugins::explicit::window_switcher::WindowSwitcherPlugin::new());
+            let _ = registry.register(window_switcher_plugin).await;
+            let process_manager_plugin: std::sync::Arc<dyn crate::core::plugin::Plugin> = std::sync::Arc::new(crate::plugins::explicit::process_manager::ProcessManagerPlugin::new());
+            let _ = registry.register(process_manager_plugin).await;
+            let calculator = crate::plugins::implicit::calculator::CalculatorPlugin::new();
+            let calculator_history_plugin: std::sync::Arc<dyn crate::core::plugin::Plugin> = std::sync::Arc::new(calculator.history_plugin());
+            let _ = registry.register(calculator_history_plugin).await;
+            let calculator_plugin: std::sync::Arc<dyn crate::core::plugin::Plugin> = std::sync::Arc::new(calculator);
             let _ = registry.register(calculator_plugin).await;
             let web_search_plugin: std::sync::Arc<dyn crate::core::plugin::Plugin> = std::sync::Arc::new(crate::plugins::implicit::web_search::WebSearchPlugin::new());
             let _ = registry.register(web_search_plugin).await;
@@ -87,8 +102,14 @@ fn main() {
             let _ = registry.register(clipboard_plugin).await;
             let exit_plugin: std::sync::Arc<dyn crate::core::plugin::Plugin> = std::sync::Arc::new(crate::plugins::implicit::exit::ExitPlugin::new());
             let _ = registry.register(exit_plugin).await;
-            let terminal_plugin: std::sync::Arc<dyn crate::core::plugin::Plugin> = std::sync::Arc::new(crate::plugins::explicit::terminal::TerminalPlugin::new());
+            let terminal = crate::plugins::explicit::terminal::TerminalPlugin::new();
+            let terminal_capture_plugin: std::sync::Arc<dyn crate::core::plugin::Plugin> = std::sync::Arc::new(terminal.capture_plugin());
+            let _ = registry.register(terminal_capture_plugin).await;
+            let terminal_plugin: std::sync::Arc<dyn crate::core::plugin::Plugin> = std::sync::Arc::new(terminal);
             let _ = registry.register(terminal_plugin).await;
+            let settings_plugin: std::sync::Arc<dyn crate::core::plugin::Plugin> = std::sync::Arc::new(crate::plugins::explicit::settings::SettingsPlugin::new());
+            let _ = registry.register(settings_plugin).await;
+            registry.settings_schemas().await
         });
         let engine = std::sync::Arc::new(crate::core::engine::QueryEngine::new(registry.clone()));
         
@@ -125,12 +146,12 @@ fn main() {
                 ..Default::default()
             },
             |window, cx| {
-                cx.new(|cx| app::app::new(window, cx, engine.clone(), dispatcher.clone()))
+                cx.new(|cx| app::app::new(window, cx, engine.clone(), dispatcher.clone(), config.clone(), plugin_settings.clone()))
             },
         )
         .unwrap();
 
-        let mut rx = platform::windows::hotkey::register_hotkey();
+        let mut rx = platform::hotkey::register_hotkey(&config.hotkeys.show_launcher);
         let async_cx = cx.to_async();
         cx.foreground_executor().spawn(async move {
             while let Some(()) = rx.next().await {
@@ -0,0 +1,288 @@
+// Applications from the .desktop files in the XDG data directories and the ones Flatpak and Snap
+// export. A file in the user's directory hides any system file with the same desktop file id,
+// which is how entries get overridden or, with Hidden=true, removed.
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::platform::terminal::find_in_path;
+use crate::services::app_index::{split_arguments, AppEntry, Launch};
+
+// Exec field codes, replaced by files, URLs or the icon when a file manager launches the app
+const FIELD_CODES: &[&str] = &["%f", "%F", "%u", "%U", "%d", "%D", "%n", "%N", "%i", "%c", "%k", "%v", "%m"];
+// Around the field codes of Flatpak apps, for passing files into the sandbox
+const FLATPAK_FORWARDING: &[&str] = &["@@", "@@u"];
+
+// Where Flatpak and Snap export the .desktop files and icons of installed apps. Their setup adds
+// these to XDG_DATA_DIRS, but only for sessions started after it ran.
+pub fn package_data_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = dirs::data_dir().map(|dir| dir.join("flatpak/exports/share")).into_iter().collect();
+    dirs.push(PathBuf::from("/var/lib/flatpak/exports/share"));
+    dirs.push(PathBuf::from("/var/lib/snapd/desktop"));
+    dirs
+}
+
+// Most important first, the user's own entries before the system's
+pub fn application_dirs() -> Vec<PathBuf> {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(dirs::data_dir);
+    let data_dirs = std::env::var_os("XDG_DATA_DIRS")
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".into());
+    let mut dirs: Vec<PathBuf> = data_home
+        .into_iter()
+        .chain(std::env::split_paths(&data_dirs))
+        .map(|dir| dir.join("applications"))
+        .collect();
+    for dir in package_data_dirs() {
+        let dir = dir.join("applications");
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+// "de_DE.UTF-8@euro" gives de_DE@euro, de_DE, de@euro and de, most specific first
+fn locales() -> Vec<String> {
+    let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default();
+    let (locale, modifier) = match locale.split_once('@') {
+        Some((locale, modifier)) => (locale.to_string(), Some(modifier.to_string())),
+        None => (locale, None),
+    };
+    let locale = locale.split('.').next().unwrap_or_default();
+    let language = locale.split('_').next().unwrap_or_default();
+    let mut candidates = Vec::new();
+    for base in [locale, language] {
+        if base.is_empty() || base == "C" || base == "POSIX" {
+            continue;
+        }
+        if let Some(modifier) = &modifier {
+            candidates.push(format!("{}@{}", base, modifier));
+        }
+        candidates.push(base.to_string());
+    }
+    candidates.dedup();
+    candidates
+}
+
+fn unescape(value: &str) -> String {
+    let mut result = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => result.push(' '),
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some(other) => result.push(other),
+            None => {}
+        }
+    }
+    result
+}
+
+// The keys of the [Desktop Entry] group
+fn desktop_entry(content: &str) -> HashMap<&str, &str> {
+    let mut keys = HashMap::new();
+    let mut in_entry = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_entry = line == "[Desktop Entry]";
+        } else if in_entry && !line.starts_with('#') {
+            if let Some((key, value)) = line.split_once('=') {
+                keys.insert(key.trim(), value.trim());
+            }
+        }
+    }
+    keys
+}
+
+fn localized<'a>(keys: &HashMap<&str, &'a str>, key: &str, locales: &[String]) -> Option<&'a str> {
+    locales
+        .iter()
+        .find_map(|locale| keys.get(format!("{}[{}]", key, locale).as_str()))
+        .or_else(|| keys.get(key))
+        .copied()
+        .filter(|value| !value.is_empty())
+}
+
+// The program and its arguments, without the field codes
+fn parse_exec(exec: &str) -> Option<(String, Vec<String>)> {
+    let mut arguments = split_arguments(exec).into_iter().filter_map(|argument| {
+        if FIELD_CODES.contains(&argument.as_str()) || FLATPAK_FORWARDING.contains(&argument.as_str()) {
+            return None;
+        }
+        let mut argument = argument.replace("%%", "\u{0}");
+        for code in FIELD_CODES {
+            argument = argument.replace(code, "");
+        }
+        Some(argument.replace('\u{0}', "%"))
+    });
+    let program = arguments.next().filter(|program| !program.is_empty())?;
+    Some((program, arguments.collect()))
+}
+
+// What the app is called on the command line, looking past the wrappers that start sandboxed apps:
+// `flatpak run --command=code com.visualstudio.code` and `env VAR=value /snap/bin/firefox`
+fn exec_name(program: &str, args: &[String]) -> Option<String> {
+    let file_name = |path: &str| Path::new(path).file_name().map(|name| name.to_string_lossy().to_string());
+    let operands = || args.iter().filter(|arg| !arg.starts_with('-'));
+    match file_name(program)?.as_str() {
+        "flatpak" => args
+            .iter()
+            .find_map(|arg| arg.strip_prefix("--command=").and_then(file_name))
+            .or_else(|| {
+                let app_id = operands().nth(1)?;
+                app_id.rsplit('.').next().map(str::to_lowercase)
+            }),
+        "env" => operands().find(|arg| !arg.contains('=')).and_then(|arg| file_name(arg)),
+        name => Some(name.to_string()),
+    }
+}
+
+fn list(value: &str) -> Vec<String> {
+    value.split(';').map(unescape).filter(|item| !item.trim().is_empty()).collect()
+}
+
+// An app for the launcher, or None for entries that aren't shown in menus on this desktop.
+// Terminal apps are left out, the terminal plugin runs those.
+fn parse_desktop_file(id: &str, content: &str, desktops: &[String], locales: &[String]) -> Option<AppEntry> {
+    let keys = desktop_entry(content);
+    let is = |key: &str| keys.get(key).is_some_and(|value| *value == "true");
+    if keys.get("Type") != Some(&"Application") || is("NoDisplay") || is("Hidden") || is("Terminal") {
+        return None;
+    }
+    let shown_here = |key: &str| keys.get(key).map(|value| list(value).iter().any(|d| desktops.contains(d)));
+    if shown_here("OnlyShowIn") == Some(false) || shown_here("NotShowIn") == Some(true) {
+        return None;
+    }
+    if let Some(try_exec) = keys.get("TryExec").filter(|t| !t.is_empty()) {
+        find_in_path(&unescape(try_exec))?;
+    }
+    let (program, args) = parse_exec(&unescape(keys.get("Exec")?))?;
+    let path = find_in_path(&program)?;
+    let generic_name = localized(&keys, "GenericName", locales).map(unescape);
+    let mut keywords = localized(&keys, "Keywords", locales).map(list).unwrap_or_default();
+    keywords.extend(generic_name.clone());
+    Some(AppEntry {
+        id: id.to_string(),
+        name: unescape(localized(&keys, "Name", locales)?),
+        exec: exec_name(&program, &args)?,
+        path,
+        args,
+        icon: localized(&keys, "Icon", locales).map(unescape),
+        description: localized(&keys, "Comment", locales).map(unescape).or(generic_name),
+        keywords,
+        launch: Launch::Run,
+    })
+}
+
+fn current_desktops() -> Vec<String> {
+    std::env::var("XDG_CURRENT_DESKTOP")
+        .unwrap_or_default()
+        .split(':')
+        .filter(|d| !d.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+// Desktop file ids are the path below applications/ with slashes turned into dashes
+fn desktop_file_id(relative: &Path) -> String {
+    relative.to_string_lossy().replace('/', "-")
+}
+
+fn scan_dir(root: &Path, dir: &Path, found: &mut Vec<(String, PathBuf)>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            scan_dir(root, &path, found);
+        } else if path.extension().is_some_and(|e| e == "desktop") {
+            if let Ok(relative) = path.strip_prefix(root) {
+                found.push((desktop_file_id(relative), path));
+            }
+        }
+    }
+}
+
+fn read_app(id: &str, path: &Path, desktops: &[String], locales: &[String]) -> Option<AppEntry> {
+    let content = std::fs::read_to_string(path).ok()?;
+    parse_desktop_file(id, &content, desktops, locales)
+}
+
+pub fn scan() -> Vec<AppEntry> {
+    let (desktops, locales) = (current_desktops(), locales());
+    let mut seen = HashSet::new();
+    let mut apps = Vec::new();
+    for dir in application_dirs() {
+        let mut files = Vec::new();
+        scan_dir(&dir, &dir, &mut files);
+        for (id, path) in files {
+            if seen.insert(id.clone()) {
+                apps.extend(read_app(&id, &path, &desktops, &locales));
+            }
+        }
+    }
+    apps
+}
+
+// The desktop file id a changed file belongs to and the app that id stands for now, which comes
+// from whichever directory has the most important file for it; None for files that aren't entries
+pub fn load(path: &Path) -> Option<(String, Option<AppEntry>)> {
+    if path.extension().is_none_or(|e| e != "desktop") {
+        return None;
+    }
+    let dirs = application_dirs();
+    let relative = dirs.iter().find_map(|dir| path.strip_prefix(dir).ok())?;
+    let id = desktop_file_id(relative);
+    let current = dirs
+        .iter()
+        .flat_map(|dir| [dir.join(relative), dir.join(&id)])
+        .find(|file| file.is_file());
+    let app = current.and_then(|file| read_app(&id, &file, &current_desktops(), &locales()));
+    Some((id, app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_desktop_file() {
+        let content = "[Desktop Entry]\nType=Application\nName=Files\nName[de]=Dateien\n\
+            GenericName=File Manager\nComment=Access and organize files\nKeywords=folder;explorer;\n\
+            Exec=sh -c \"echo %%s\" --new-window %U\nIcon=org.gnome.Nautilus\nOnlyShowIn=GNOME;\n\
+            \n[Desktop Action new-window]\nName=New Window\nExec=sh --new-window\n";
+        let gnome = ["GNOME".to_string()];
+        let app = parse_desktop_file("org.gnome.Nautilus.desktop", content, &gnome, &["de_DE".to_string(), "de".to_string()]).unwrap();
+        assert_eq!(app.name, "Dateien");
+        assert_eq!(app.exec, "sh");
+        assert_eq!(app.args, ["-c", "echo %s", "--new-window"]);
+        assert_eq!(app.keywords, ["folder", "explorer", "File Manager"]);
+        assert_eq!(app.description.as_deref(), Some("Access and organize files"));
+        assert_eq!(app.icon.as_deref(), Some("org.gnome.Nautilus"));
+
+        assert!(parse_desktop_file("", content, &["KDE".to_string()], &[]).is_none());
+        let hidden = content.replace("Type=Application", "Type=Application\nNoDisplay=true");
+        assert!(parse_desktop_file("", &hidden, &gnome, &[]).is_none());
+
+        let flatpak = "flatpak run --branch=stable --arch=x86_64 --file-forwarding com.visualstudio.code @@ %F @@";
+        let (program, args) = parse_exec(flatpak).unwrap();
+        assert_eq!(args, ["run", "--branch=stable", "--arch=x86_64", "--file-forwarding", "com.visualstudio.code"]);
+        assert_eq!(exec_name(&program, &args).as_deref(), Some("code"));
+        let (program, args) = parse_exec("env BAMF_DESKTOP_FILE_HINT=/x.desktop /snap/bin/firefox %u").unwrap();
+        assert_eq!(exec_name(&program, &args).as_deref(), Some("firefox"));
+    }
+}
@@ -0,0 +1,60 @@
+use global_hotkey::{GlobalHotKeyManager, GlobalHotKeyEvent, hotkey::{HotKey, Modifiers, Code}};
+use futures::channel::mpsc;
+use std::cell::RefCell;
+
+thread_local! {
+    // kept for the life of the app, the registration ends when the manager is dropped
+    static REGISTERED: RefCell<Option<(GlobalHotKeyManager, HotKey)>> = const { RefCell::new(None) };
+}
+
+// `shortcut` uses the config format, e.g. "Alt+Space". Global shortcuts go through X11, so under
+// Wayland without XWayland there is no hotkey and the launcher stays on whatever opened it.
+pub fn register_hotkey(shortcut: &str) -> mpsc::UnboundedReceiver<()> {
+    let (tx, rx) = mpsc::unbounded::<()>();
+    let manager = match GlobalHotKeyManager::new() {
+        Ok(manager) => manager,
+        Err(e) => {
+            log::warn!("Can't register the launcher hotkey: {}", e);
+            return rx;
+        }
+    };
+    let hotkey = shortcut.parse::<HotKey>().unwrap_or_else(|e| {
+        log::warn!("Invalid launcher hotkey {:?} ({}), using Ctrl+Space.", shortcut, e);
+        HotKey::new(Some(Modifiers::CONTROL), Code::Space)
+    });
+    if let Err(e) = manager.register(hotkey) {
+        log::warn!("Can't register the launcher hotkey {:?}: {}", shortcut, e);
+    }
+    REGISTERED.set(Some((manager, hotkey)));
+
+    std::thread::spawn(move || {
+        let receiver = GlobalHotKeyEvent::receiver();
+        while receiver.recv().is_ok() {
+            if tx.unbounded_send(()).is_err() {
+                break;
+            }
+        }
+    });
+
+    rx
+}
+
+// Swaps the launcher hotkey for `shortcut` after it's changed in the settings. The old one stays
+// registered if the new one can't be parsed or is taken by another program.
+pub fn change_hotkey(shortcut: &str) -> anyhow::Result<()> {
+    let hotkey = shortcut.parse::<HotKey>()?;
+    REGISTERED.with_borrow_mut(|registered| {
+        let Some((manager, current)) = registered else {
+            anyhow::bail!("The hotkey manager didn't start");
+        };
+        if *current == hotkey {
+            return Ok(());
+        }
+        manager.register(hotkey)?;
+        if let Err(e) = manager.unregister(*current) {
+            log::warn!("Can't unregister the old launcher hotkey: {}", e);
+        }
+        *current = hotkey;
+        Ok(())
+    })
+}
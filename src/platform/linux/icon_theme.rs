@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+// Freedesktop icon theme lookup, see
+// https://specifications.freedesktop.org/icon-theme-spec/latest/
+
+const EXTENSIONS: [&str; 2] = ["png", "svg"];
+
+#[derive(Debug, Clone)]
+struct ThemeDir {
+    path: String,
+    size: u32,
+    scale: u32,
+    min_size: u32,
+    max_size: u32,
+    threshold: u32,
+    kind: String,
+}
+
+impl ThemeDir {
+    // The pixel sizes the directory is meant for; a Scale=2 directory of Size=24 holds 48px images
+    fn pixel_range(&self) -> (u32, u32) {
+        let (min, max) = match self.kind.as_str() {
+            "Fixed" => (self.size, self.size),
+            "Scalable" => (self.min_size, self.max_size),
+            _ => (self.size.saturating_sub(self.threshold), self.size + self.threshold),
+        };
+        (min * self.scale, max * self.scale)
+    }
+    fn matches(&self, size: u32) -> bool {
+        let (min, max) = self.pixel_range();
+        min <= size && size <= max
+    }
+    fn distance(&self, size: u32) -> u32 {
+        let (min, max) = self.pixel_range();
+        min.saturating_sub(size).max(size.saturating_sub(max))
+    }
+}
+
+struct IconTheme {
+    dirs: Vec<ThemeDir>,
+    inherits: Vec<String>,
+}
+
+fn home() -> Option<PathBuf> {
+    dirs::home_dir()
+}
+
+// ~/.icons, $XDG_DATA_HOME/icons, $XDG_DATA_DIRS/icons and the icons Flatpak and Snap export
+fn base_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(home) = home() {
+        dirs.push(home.join(".icons"));
+    }
+    if let Some(data_home) = dirs::data_dir() {
+        dirs.push(data_home.join("icons"));
+    }
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|d| !d.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+    for dir in data_dirs.split(':').filter(|d| !d.is_empty()) {
+        dirs.push(Path::new(dir).join("icons"));
+    }
+    for dir in super::app_index::package_data_dirs() {
+        let dir = dir.join("icons");
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+fn parse_ini(content: &str) -> HashMap<String, HashMap<String, String>> {
+    let mut sections: HashMap<String, HashMap<String, String>> = HashMap::new();
+    let mut current = String::new();
+    for line in content.lines().map(|l| l.trim()) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            current = line[1..line.len() - 1].to_string();
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            sections
+                .entry(current.clone())
+                .or_default()
+                .insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+    sections
+}
+
+fn load_theme(name: &str, base_dirs: &[PathBuf]) -> Option<IconTheme> {
+    let index = base_dirs
+        .iter()
+        .map(|dir| dir.join(name).join("index.theme"))
+        .find(|path| path.exists())?;
+    let sections = parse_ini(&std::fs::read_to_string(index).ok()?);
+    let theme = sections.get("Icon Theme")?;
+    let list = |key: &str| -> Vec<String> {
+        theme
+            .get(key)
+            .map(|v| v.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default()
+    };
+    let mut directories = list("Directories");
+    directories.extend(list("ScaledDirectories"));
+    let dirs = directories
+        .into_iter()
+        .filter_map(|path| {
+            let section = sections.get(&path)?;
+            let number = |key: &str| section.get(key).and_then(|v| v.parse::<u32>().ok());
+            let size = number("Size")?;
+            Some(ThemeDir {
+                size,
+                scale: number("Scale").unwrap_or(1),
+                min_size: number("MinSize").unwrap_or(size),
+                max_size: number("MaxSize").unwrap_or(size),
+                threshold: number("Threshold").unwrap_or(2),
+                kind: section.get("Type").cloned().unwrap_or_else(|| "Threshold".to_string()),
+                path,
+            })
+        })
+        .collect();
+    Some(IconTheme { dirs, inherits: list("Inherits") })
+}
+
+// The GTK icon theme of the current user, hicolor when none is set
+pub fn current_theme() -> String {
+    let Some(config) = dirs::config_dir() else {
+        return "hicolor".to_string();
+    };
+    for settings in ["gtk-4.0/settings.ini", "gtk-3.0/settings.ini"] {
+        if let Ok(content) = std::fs::read_to_string(config.join(settings)) {
+            let sections = parse_ini(&content);
+            if let Some(name) = sections.get("Settings").and_then(|s| s.get("gtk-icon-theme-name")) {
+                return name.trim_matches('"').to_string();
+            }
+        }
+    }
+    "hicolor".to_string()
+}
+
+fn find_in_theme(theme: &IconTheme, theme_name: &str, icon: &str, size: u32, base_dirs: &[PathBuf]) -> Option<PathBuf> {
+    let mut closest: Option<(u32, PathBuf)> = None;
+    for dir in &theme.dirs {
+        for base in base_dirs {
+            for extension in EXTENSIONS {
+                let path = base.join(theme_name).join(&dir.path).join(format!("{}.{}", icon, extension));
+                if !path.exists() {
+                    continue;
+                }
+                if dir.matches(size) {
+                    return Some(path);
+                }
+                let distance = dir.distance(size);
+                if closest.as_ref().is_none_or(|(best, _)| distance < *best) {
+                    closest = Some((distance, path));
+                }
+            }
+        }
+    }
+    closest.map(|(_, path)| path)
+}
+
+// Resolves an icon name from a .desktop file to an image on disk
+pub fn lookup_icon(icon: &str, size: u32) -> Option<PathBuf> {
+    let path = Path::new(icon);
+    if path.is_absolute() {
+        return path.exists().then(|| path.to_path_buf());
+    }
+    let base_dirs = base_dirs();
+    let mut queue = vec![current_theme()];
+    let mut visited = Vec::new();
+    while let Some(name) = queue.pop() {
+        if visited.contains(&name) {
+            continue;
+        }
+        if let Some(theme) = load_theme(&name, &base_dirs) {
+            if let Some(found) = find_in_theme(&theme, &name, icon, size, &base_dirs) {
+                return Some(found);
+            }
+            // parents are searched in the order they are listed
+            queue.extend(theme.inherits.iter().rev().cloned());
+        }
+        visited.push(name);
+        if queue.is_empty() && !visited.iter().any(|v| v == "hicolor") {
+            queue.push("hicolor".to_string());
+        }
+    }
+    EXTENSIONS
+        .iter()
+        .map(|extension| Path::new("/usr/share/pixmaps").join(format!("{}.{}", icon, extension)))
+        .find(|path| path.exists())
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dir(kind: &str, size: u32, scale: u32) -> ThemeDir {
+        ThemeDir {
+            path: format!("{}x{}@{}", size, size, scale),
+            size,
+            scale,
+            min_size: 8,
+            max_size: 512,
+            threshold: 2,
+            kind: kind.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_dir_sizes() {
+        assert!(dir("Fixed", 48, 1).matches(48) && !dir("Fixed", 48, 1).matches(47));
+        assert!(dir("Threshold", 48, 1).matches(50) && !dir("Threshold", 48, 1).matches(51));
+        assert!(dir("Scalable", 48, 1).matches(256));
+        // a 24@2 directory holds 48px images, matching and distance agree on that
+        assert!(dir("Fixed", 24, 2).matches(48) && !dir("Fixed", 24, 2).matches(24));
+        assert_eq!(dir("Fixed", 24, 2).distance(48), 0);
+        assert_eq!(dir("Fixed", 24, 2).distance(32), 16);
+        assert_eq!(dir("Threshold", 32, 1).distance(48), 14);
+        assert_eq!(dir("Scalable", 48, 1).distance(4), 4);
+    }
+
+    #[test]
+    fn test_theme_lookup() {
+        let base = std::env::temp_dir().join(format!("catalyst-icon-theme-{}", std::process::id()));
+        let theme_dir = base.join("Test");
+        std::fs::create_dir_all(theme_dir.join("16x16/apps")).unwrap();
+        std::fs::create_dir_all(theme_dir.join("48x48/apps")).unwrap();
+        std::fs::create_dir_all(theme_dir.join("scalable/apps")).unwrap();
+        std::fs::write(
+            theme_dir.join("index.theme"),
+            "[Icon Theme]\nName=Test\nInherits=hicolor\nDirectories=16x16/apps,48x48/apps,scalable/apps\n\n\
+             [16x16/apps]\nSize=16\nType=Fixed\n\n[48x48/apps]\nSize=48\nType=Fixed\n\n\
+             [scalable/apps]\nSize=64\nMinSize=64\nMaxSize=256\nType=Scalable\n",
+        )
+        .unwrap();
+        std::fs::write(theme_dir.join("16x16/apps/editor.png"), "").unwrap();
+        std::fs::write(theme_dir.join("48x48/apps/editor.png"), "").unwrap();
+        std::fs::write(theme_dir.join("scalable/apps/editor.svg"), "").unwrap();
+
+        let bases = vec![base.clone()];
+        let theme = load_theme("Test", &bases).unwrap();
+        assert_eq!(theme.inherits, ["hicolor"]);
+        assert_eq!(theme.dirs.len(), 3);
+        let find = |size| find_in_theme(&theme, "Test", "editor", size, &bases).unwrap();
+        assert_eq!(find(48), theme_dir.join("48x48/apps/editor.png"));
+        assert_eq!(find(128), theme_dir.join("scalable/apps/editor.svg"));
+        // nothing for 20px, the 16px image is the closest
+        assert_eq!(find(20), theme_dir.join("16x16/apps/editor.png"));
+        assert_eq!(find_in_theme(&theme, "Test", "missing", 48, &bases), None);
+        assert!(load_theme("Missing", &bases).is_none());
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+}
@@ -0,0 +1,12 @@
+pub mod app_index;
+pub mod hotkey;
+pub mod icon_theme;
+pub mod network;
+pub mod process;
+pub mod shell;
+pub mod system;
+pub mod terminal;
+pub mod window;
+
+pub use icon_theme::*;
+pub use shell::*;
\ No newline at end of file
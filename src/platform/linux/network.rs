@@ -0,0 +1,219 @@
+// Network interfaces from getifaddrs, with MAC addresses from sysfs, and default routes and
+// listening sockets from procfs
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use crate::platform::{Listener, NetworkInterface};
+
+// Socket states in /proc/net/tcp and udp
+const TCP_LISTEN: &str = "0A";
+const UDP_UNCONNECTED: &str = "07";
+
+// Default routes in /proc/net/route: "eth0 00000000 0102A8C0 0003 ...", addresses in native byte order hex
+fn parse_ipv4_routes(content: &str) -> Vec<(String, IpAddr)> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 3 || fields[1] != "00000000" {
+                return None;
+            }
+            let gateway = u32::from_str_radix(fields[2], 16).ok().filter(|g| *g != 0)?;
+            Some((fields[0].to_string(), IpAddr::V4(Ipv4Addr::from(gateway.to_ne_bytes()))))
+        })
+        .collect()
+}
+
+fn parse_hex_ipv6(hex: &str) -> Option<Ipv6Addr> {
+    if hex.len() != 32 {
+        return None;
+    }
+    let mut bytes = [0u8; 16];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(Ipv6Addr::from(bytes))
+}
+
+// Default routes in /proc/net/ipv6_route: destination ::/0 with a next hop, interface in the last column
+fn parse_ipv6_routes(content: &str) -> Vec<(String, IpAddr)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 || fields[1] != "00" || parse_hex_ipv6(fields[0]) != Some(Ipv6Addr::UNSPECIFIED) {
+                return None;
+            }
+            let next_hop = parse_hex_ipv6(fields[4]).filter(|hop| !hop.is_unspecified())?;
+            Some((fields[9].to_string(), IpAddr::V6(next_hop)))
+        })
+        .collect()
+}
+
+fn mac_address(interface: &str) -> Option<String> {
+    let mac = std::fs::read_to_string(format!("/sys/class/net/{}/address", interface)).ok()?;
+    let mac = mac.trim();
+    (!mac.is_empty() && mac.chars().any(|c| c != '0' && c != ':')).then(|| mac.to_string())
+}
+
+// Interfaces with at least one address, in the order the system lists them
+pub fn interfaces() -> anyhow::Result<Vec<NetworkInterface>> {
+    let mut interfaces: Vec<NetworkInterface> = Vec::new();
+    for (name, address) in local_ip_address::list_afinet_netifas()? {
+        match interfaces.iter_mut().find(|i| i.name == name) {
+            Some(interface) => interface.addresses.push(address),
+            None => interfaces.push(NetworkInterface {
+                mac: mac_address(&name),
+                name,
+                addresses: vec![address],
+                ..Default::default()
+            }),
+        }
+    }
+    let ipv4 = std::fs::read_to_string("/proc/net/route").unwrap_or_default();
+    let ipv6 = std::fs::read_to_string("/proc/net/ipv6_route").unwrap_or_default();
+    for (name, gateway) in parse_ipv4_routes(&ipv4).into_iter().chain(parse_ipv6_routes(&ipv6)) {
+        if let Some(interface) = interfaces.iter_mut().find(|i| i.name == name) {
+            if !interface.gateways.contains(&gateway) {
+                interface.gateways.push(gateway);
+            }
+        }
+    }
+    Ok(interfaces)
+}
+
+// "0100007F:1F90" in /proc/net/tcp, IPv6 addresses being four native order words
+fn parse_socket_address(text: &str) -> Option<SocketAddr> {
+    let (address, port) = text.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let address = match address.len() {
+        8 => IpAddr::V4(Ipv4Addr::from(u32::from_str_radix(address, 16).ok()?.to_ne_bytes())),
+        32 => {
+            let mut bytes = [0u8; 16];
+            for (i, chunk) in bytes.chunks_mut(4).enumerate() {
+                let word = u32::from_str_radix(address.get(i * 8..i * 8 + 8)?, 16).ok()?;
+                chunk.copy_from_slice(&word.to_ne_bytes());
+            }
+            IpAddr::V6(Ipv6Addr::from(bytes))
+        }
+        _ => return None,
+    };
+    Some(SocketAddr::new(address, port))
+}
+
+// (local address, socket inode) of the sockets in `state`
+fn parse_sockets(content: &str, state: &str) -> Vec<(SocketAddr, u64)> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 || fields[3] != state {
+                return None;
+            }
+            Some((parse_socket_address(fields[1])?, fields[9].parse().ok()?))
+        })
+        .collect()
+}
+
+// Socket inodes to the processes holding them; other users' processes can't be looked into
+fn socket_owners() -> HashMap<u64, u32> {
+    let mut owners = HashMap::new();
+    let Ok(processes) = std::fs::read_dir("/proc") else {
+        return owners;
+    };
+    for process in processes.flatten() {
+        let Some(pid) = process.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+            continue;
+        };
+        let Ok(fds) = std::fs::read_dir(process.path().join("fd")) else {
+            continue;
+        };
+        for fd in fds.flatten() {
+            let Ok(target) = std::fs::read_link(fd.path()) else {
+                continue;
+            };
+            let inode = target.to_str().and_then(|t| t.strip_prefix("socket:[")).and_then(|t| t.strip_suffix(']'));
+            if let Some(inode) = inode.and_then(|i| i.parse().ok()) {
+                owners.insert(inode, pid);
+            }
+        }
+    }
+    owners
+}
+
+// TCP sockets listening on `port` and UDP sockets bound to it
+pub fn listeners(port: u16) -> anyhow::Result<Vec<Listener>> {
+    let tables = [
+        ("tcp", "/proc/net/tcp", TCP_LISTEN),
+        ("tcp", "/proc/net/tcp6", TCP_LISTEN),
+        ("udp", "/proc/net/udp", UDP_UNCONNECTED),
+        ("udp", "/proc/net/udp6", UDP_UNCONNECTED),
+    ];
+    let mut sockets = Vec::new();
+    for (protocol, path, state) in tables {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        for (address, inode) in parse_sockets(&content, state) {
+            if address.port() == port {
+                sockets.push((protocol, address, inode));
+            }
+        }
+    }
+    if sockets.is_empty() {
+        return Ok(Vec::new());
+    }
+    let owners = socket_owners();
+    Ok(sockets
+        .into_iter()
+        .map(|(protocol, address, inode)| Listener { protocol, address, pid: owners.get(&inode).copied() })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // procfs prints addresses in the machine's byte order, these samples come from a little-endian one
+
+    #[test]
+    fn test_parse_ipv4_routes() {
+        let content = "\
+Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+wlan0\t00000000\t0102A8C0\t0003\t0\t0\t600\t00000000\t0\t0\t0
+wlan0\t0002A8C0\t00000000\t0001\t0\t0\t600\t00FFFFFF\t0\t0\t0
+tun0\t00000000\t00000000\t0001\t0\t0\t50\t00000000\t0\t0\t0
+";
+        assert_eq!(parse_ipv4_routes(content), [("wlan0".to_string(), "192.168.2.1".parse().unwrap())]);
+        assert!(parse_ipv4_routes("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_ipv6_routes() {
+        let content = "\
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 fe800000000000000000000000000001 00000400 00000001 00000000 00000003 eth0
+fe800000000000000000000000000000 40 00000000000000000000000000000000 00 00000000000000000000000000000000 00000100 00000001 00000000 00000001 eth0
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 00000000000000000000000000000000 ffffffff 00000001 00000000 00200200 lo
+";
+        assert_eq!(parse_ipv6_routes(content), [("eth0".to_string(), "fe80::1".parse().unwrap())]);
+        assert_eq!(parse_hex_ipv6("fe80"), None);
+    }
+
+    #[test]
+    fn test_parse_socket_address() {
+        assert_eq!(parse_socket_address("0100007F:1F90"), Some("127.0.0.1:8080".parse().unwrap()));
+        assert_eq!(parse_socket_address("00000000:0016"), Some("0.0.0.0:22".parse().unwrap()));
+        assert_eq!(parse_socket_address("00000000000000000000000001000000:0035"), Some("[::1]:53".parse().unwrap()));
+        assert_eq!(parse_socket_address("0100007F"), None);
+        assert_eq!(parse_socket_address("7F:1F90"), None);
+
+        let tcp = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 41234 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:A1B2 0100007F:1F90 01 00000000:00000000 00:00000000 00000000  1000        0 41240 1 0000000000000000 20 4 30 10 -1
+";
+        assert_eq!(parse_sockets(tcp, TCP_LISTEN), [("127.0.0.1:8080".parse().unwrap(), 41234)]);
+    }
+}
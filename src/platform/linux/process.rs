@@ -0,0 +1,107 @@
+// Processes from procfs and termination through signals
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use crate::platform::ProcessInfo;
+
+// procfs counts CPU time in clock ticks, USER_HZ is 100 on every architecture
+const TICKS_PER_SECOND: u64 = 100;
+// Stopping these takes the session or the whole system down with them
+const CRITICAL: &[&str] = &[
+    "systemd", "init", "systemd-logind", "dbus-daemon", "dbus-broker", "Xorg", "Xwayland", "gnome-shell",
+    "kwin_x11", "kwin_wayland", "plasmashell", "gdm", "sddm", "lightdm", "NetworkManager", "pipewire",
+    "wireplumber", "pulseaudio", "polkitd", "sshd",
+];
+
+pub fn name(pid: u32) -> Option<String> {
+    let comm = std::fs::read_to_string(format!("/proc/{}/comm", pid)).ok()?;
+    Some(comm.trim().to_string()).filter(|name| !name.is_empty())
+}
+
+// The name, parent and CPU time from /proc/<pid>/stat. Names can hold spaces and parentheses,
+// so the other fields are counted from the last ')'.
+fn parse_stat(stat: &str) -> Option<(String, u32, Duration)> {
+    let (start, end) = (stat.find('(')?, stat.rfind(')')?);
+    let name = stat.get(start + 1..end)?.to_string();
+    // the state, field 3, comes first
+    let fields: Vec<&str> = stat[end + 1..].split_whitespace().collect();
+    let parent = fields.get(1)?.parse().ok()?;
+    let user: u64 = fields.get(11)?.parse().ok()?;
+    let system: u64 = fields.get(12)?.parse().ok()?;
+    Some((name, parent, Duration::from_millis((user + system) * 1000 / TICKS_PER_SECOND)))
+}
+
+fn resident_memory(status: &str) -> Option<u64> {
+    let kilobytes = status.lines().find_map(|line| line.strip_prefix("VmRSS:"))?;
+    Some(kilobytes.trim().trim_end_matches("kB").trim().parse::<u64>().ok()? * 1024)
+}
+
+fn process(pid: u32) -> Option<ProcessInfo> {
+    let (mut name, parent, cpu_time) = parse_stat(&std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?)?;
+    let cmdline = std::fs::read(format!("/proc/{}/cmdline", pid)).ok()?;
+    let arguments: Vec<String> = cmdline
+        .split(|b| *b == 0)
+        .filter(|argument| !argument.is_empty())
+        .map(|argument| String::from_utf8_lossy(argument).to_string())
+        .collect();
+    // kernel threads and zombies have no command line
+    let program = arguments.first()?;
+    // the kernel cuts names off at 15 bytes, "gnome-terminal-" is gnome-terminal-server
+    let program_name = Path::new(program).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    if name.len() == 15 && program_name.starts_with(&name) {
+        name = program_name;
+    }
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).unwrap_or_default();
+    Some(ProcessInfo {
+        pid,
+        parent: Some(parent).filter(|parent| *parent != 0),
+        name,
+        command: arguments.join(" "),
+        cpu_time,
+        memory: resident_memory(&status).unwrap_or(0),
+    })
+}
+
+pub fn list() -> anyhow::Result<Vec<ProcessInfo>> {
+    let mut processes = Vec::new();
+    for entry in std::fs::read_dir("/proc")?.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|name| name.parse().ok()) else {
+            continue;
+        };
+        // processes can exit while they are read, those are left out
+        processes.extend(process(pid));
+    }
+    Ok(processes)
+}
+
+pub fn is_critical(process: &ProcessInfo) -> bool {
+    process.pid == 1 || CRITICAL.contains(&process.name.as_str())
+}
+
+// SIGTERM asks the process to quit, SIGKILL doesn't give it a choice
+pub fn kill(pid: u32, force: bool) -> anyhow::Result<()> {
+    let signal = if force { "KILL" } else { "TERM" };
+    let output = Command::new("kill")
+        .args(["-s", signal, &pid.to_string()])
+        .stdin(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        anyhow::bail!("Failed to stop process {}: {}", pid, String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stat() {
+        let stat = "1234 (tmux: server) S 1 1234 1234 0 -1 4194560 1507 0 0 0 250 125 0 0 20 0 1 0 3721 9359360 1024";
+        let (name, parent, cpu_time) = parse_stat(stat).unwrap();
+        assert_eq!((name.as_str(), parent), ("tmux: server", 1));
+        assert_eq!(cpu_time, Duration::from_millis(3750));
+        assert_eq!(resident_memory("Name:\ttmux\nVmRSS:\t    4096 kB\n"), Some(4096 * 1024));
+    }
+}
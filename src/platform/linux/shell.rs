@@ -0,0 +1,10 @@
+// Opens a file, folder or URL with its default handler
+pub fn open(target: &str) -> anyhow::Result<()> {
+    std::process::Command::new("xdg-open")
+        .arg(target)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()?;
+    Ok(())
+}
@@ -0,0 +1,121 @@
+// Power, session, display and volume commands through systemd-logind and the desktop's tools
+use std::process::{Command, Stdio};
+
+use super::terminal::find_in_path;
+
+// Step used by the volume up and down commands
+const VOLUME_STEP: &str = "5%";
+
+fn run_program(program: &str, args: &[&str]) -> anyhow::Result<()> {
+    let status = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| anyhow::anyhow!("Failed to run {}: {}", program, e))?;
+    if !status.success() {
+        anyhow::bail!("{} {} failed with {}", program, args.join(" "), status);
+    }
+    Ok(())
+}
+
+// Tries each installed program in turn, for tools that differ between desktops and sound servers
+fn run_first(candidates: &[(&str, Vec<&str>)]) -> anyhow::Result<()> {
+    let mut last_error = None;
+    for (program, args) in candidates {
+        if find_in_path(program).is_none() {
+            continue;
+        }
+        match run_program(program, args) {
+            Ok(()) => return Ok(()),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| {
+        let names: Vec<&str> = candidates.iter().map(|(program, _)| *program).collect();
+        anyhow::anyhow!("None of {} is installed", names.join(", "))
+    }))
+}
+
+fn logout() -> anyhow::Result<()> {
+    match std::env::var("XDG_SESSION_ID") {
+        Ok(session) if !session.is_empty() => run_program("loginctl", &["terminate-session", &session]),
+        _ => run_program("loginctl", &["terminate-user", &std::env::var("USER").unwrap_or_default()]),
+    }
+}
+
+// Empties the XDG trash the file managers share, keeping the directories themselves
+fn empty_trash() -> anyhow::Result<()> {
+    let trash = dirs::data_dir()
+        .ok_or_else(|| anyhow::anyhow!("No data directory found"))?
+        .join("Trash");
+    for dir in ["files", "info", "expunged"] {
+        let Ok(entries) = std::fs::read_dir(trash.join(dir)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let result = if path.is_dir() && !path.is_symlink() {
+                std::fs::remove_dir_all(&path)
+            } else {
+                std::fs::remove_file(&path)
+            };
+            if let Err(e) = result {
+                log::warn!("Failed to remove {}: {}", path.display(), e);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn change_volume(direction: char) -> anyhow::Result<()> {
+    let wpctl = format!("{}{}", VOLUME_STEP, direction);
+    let pactl = format!("{}{}", direction, VOLUME_STEP);
+    run_first(&[
+        ("wpctl", vec!["set-volume", "-l", "1.0", "@DEFAULT_AUDIO_SINK@", &wpctl]),
+        ("pactl", vec!["set-sink-volume", "@DEFAULT_SINK@", &pactl]),
+        ("amixer", vec!["-q", "set", "Master", &wpctl]),
+    ])
+}
+
+// Starts a program without waiting for it or keeping its output
+pub fn spawn_detached(program: &str, args: &[String]) -> anyhow::Result<()> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to run {}: {}", program, e))?;
+    // reaped in the background so finished programs don't linger as zombies
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+pub fn run(command: &str) -> anyhow::Result<()> {
+    match command {
+        "shutdown" => run_program("systemctl", &["poweroff"]),
+        "restart" => run_program("systemctl", &["reboot"]),
+        "sleep" => run_program("systemctl", &["suspend"]),
+        "hibernate" => run_program("systemctl", &["hibernate"]),
+        "logout" => logout(),
+        "lock" => run_first(&[
+            ("loginctl", vec!["lock-session"]),
+            ("xdg-screensaver", vec!["lock"]),
+        ]),
+        "empty_trash" => empty_trash(),
+        "sleep_display" => run_first(&[
+            ("xset", vec!["dpms", "force", "off"]),
+            ("kscreen-doctor", vec!["--dpms", "off"]),
+        ]),
+        "toggle_mute" => run_first(&[
+            ("wpctl", vec!["set-mute", "@DEFAULT_AUDIO_SINK@", "toggle"]),
+            ("pactl", vec!["set-sink-mute", "@DEFAULT_SINK@", "toggle"]),
+            ("amixer", vec!["-q", "set", "Master", "toggle"]),
+        ]),
+        "volume_up" => change_volume('+'),
+        "volume_down" => change_volume('-'),
+        _ => anyhow::bail!("Unknown system command: {}", command),
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::BTreeSet;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+// Tried in order when neither the config nor $TERMINAL names one
+const TERMINALS: &[&str] = &[
+    "x-terminal-emulator", "gnome-terminal", "konsole", "kitty", "alacritty", "wezterm", "foot",
+    "xfce4-terminal", "tilix", "xterm",
+];
+
+pub fn find_in_path(name: &str) -> Option<PathBuf> {
+    if name.contains('/') {
+        return Path::new(name).is_file().then(|| PathBuf::from(name));
+    }
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+// Names of the executable files in the PATH directories, sorted
+pub fn path_executables() -> Vec<String> {
+    let Some(path) = std::env::var_os("PATH") else {
+        return Vec::new();
+    };
+    let mut names = BTreeSet::new();
+    for dir in std::env::split_paths(&path) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            // follows symlinks, most of /usr/bin is links
+            let executable = std::fs::metadata(entry.path()).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0);
+            if executable {
+                names.insert(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+    }
+    names.into_iter().collect()
+}
+
+// Arguments between the emulator and the program it should run
+fn exec_args(terminal: &str) -> &'static [&'static str] {
+    match Path::new(terminal).file_name().and_then(|n| n.to_str()).unwrap_or(terminal) {
+        "gnome-terminal" => &["--"],
+        "wezterm" => &["start", "--"],
+        "xfce4-terminal" | "tilix" => &["-x"],
+        "kitty" | "foot" => &[],
+        _ => &["-e"],
+    }
+}
+
+// Runs the program in a new window of the configured (or first installed) terminal emulator
+pub fn open_in_terminal(terminal: Option<&str>, program: &str, args: &[String], cwd: &Path) -> anyhow::Result<()> {
+    let terminal = terminal
+        .filter(|t| !t.trim().is_empty())
+        .map(str::to_string)
+        .or_else(|| std::env::var("TERMINAL").ok().filter(|t| !t.is_empty()))
+        .or_else(|| TERMINALS.iter().find(|t| find_in_path(t).is_some()).map(|t| t.to_string()))
+        .ok_or_else(|| anyhow::anyhow!("No terminal emulator found, set one in [plugins.terminal]"))?;
+    Command::new(&terminal)
+        .args(exec_args(&terminal))
+        .arg(program)
+        .args(args)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    Ok(())
+}
+
+// A command for capturing output without a window
+pub fn background_command(program: &str) -> tokio::process::Command {
+    tokio::process::Command::new(program)
+}
@@ -0,0 +1,153 @@
+// Top-level windows through the EWMH properties X11 window managers keep on the root window.
+// Wayland compositors don't share their window list, there only XWayland apps are found.
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{AtomEnum, ClientMessageEvent, ConnectionExt, EventMask, Window};
+use x11rb::rust_connection::RustConnection;
+
+use crate::platform::{WindowBackend, WindowInfo};
+
+// ICCCM state of minimized windows, asked for with WM_CHANGE_STATE
+const ICONIC_STATE: u32 = 3;
+// Marks requests as coming from a pager, window managers ignore focus stealing rules for those
+const SOURCE_PAGER: u32 = 2;
+
+x11rb::atom_manager! {
+    Atoms: AtomsCookie {
+        _NET_CLIENT_LIST,
+        _NET_CLIENT_LIST_STACKING,
+        _NET_ACTIVE_WINDOW,
+        _NET_CLOSE_WINDOW,
+        _NET_WM_NAME,
+        _NET_WM_PID,
+        _NET_WM_STATE,
+        _NET_WM_STATE_SKIP_TASKBAR,
+        _NET_WM_WINDOW_TYPE,
+        _NET_WM_WINDOW_TYPE_NORMAL,
+        _NET_WM_WINDOW_TYPE_DIALOG,
+        WM_CHANGE_STATE,
+        UTF8_STRING,
+    }
+}
+
+pub struct X11Windows {
+    connection: RustConnection,
+    root: Window,
+    atoms: Atoms,
+}
+
+pub fn backend() -> anyhow::Result<Box<dyn WindowBackend>> {
+    Ok(Box::new(X11Windows::connect()?))
+}
+
+// "Navigator\0firefox\0" is the instance and then the class, the class names the app
+fn class_name(wm_class: &[u8]) -> Option<String> {
+    let mut parts = wm_class.split(|b| *b == 0).filter(|part| !part.is_empty());
+    let instance = parts.next()?;
+    let class = parts.next().unwrap_or(instance);
+    Some(String::from_utf8_lossy(class).to_string())
+}
+
+impl X11Windows {
+    pub fn connect() -> anyhow::Result<Self> {
+        let (connection, screen) = x11rb::connect(None)?;
+        let root = connection.setup().roots[screen].root;
+        let atoms = Atoms::new(&connection)?.reply()?;
+        Ok(Self { connection, root, atoms })
+    }
+
+    fn property(&self, window: Window, property: impl Into<u32>, kind: impl Into<u32>) -> Option<Vec<u8>> {
+        let reply = self.connection.get_property(false, window, property, kind, 0, u32::MAX / 4).ok()?.reply().ok()?;
+        Some(reply.value).filter(|value| !value.is_empty())
+    }
+
+    fn cardinals(&self, window: Window, property: impl Into<u32>, kind: impl Into<u32>) -> Vec<u32> {
+        self.property(window, property, kind)
+            .map(|value| value.chunks_exact(4).map(|c| u32::from_ne_bytes([c[0], c[1], c[2], c[3]])).collect())
+            .unwrap_or_default()
+    }
+
+    fn title(&self, window: Window) -> Option<String> {
+        self.property(window, self.atoms._NET_WM_NAME, self.atoms.UTF8_STRING)
+            .or_else(|| self.property(window, AtomEnum::WM_NAME, AtomEnum::STRING))
+            .map(|title| String::from_utf8_lossy(&title).trim().to_string())
+            .filter(|title| !title.is_empty())
+    }
+
+    // Panels, docks and the desktop are client windows too, but not ones to switch to
+    fn is_switchable(&self, window: Window) -> bool {
+        let atoms = &self.atoms;
+        let state = self.cardinals(window, atoms._NET_WM_STATE, AtomEnum::ATOM);
+        let types = self.cardinals(window, atoms._NET_WM_WINDOW_TYPE, AtomEnum::ATOM);
+        !state.contains(&atoms._NET_WM_STATE_SKIP_TASKBAR)
+            && (types.is_empty() || types.iter().any(|t| *t == atoms._NET_WM_WINDOW_TYPE_NORMAL || *t == atoms._NET_WM_WINDOW_TYPE_DIALOG))
+    }
+
+    fn info(&self, window: Window) -> Option<WindowInfo> {
+        if !self.is_switchable(window) {
+            return None;
+        }
+        let app = self.property(window, AtomEnum::WM_CLASS, AtomEnum::STRING).and_then(|class| class_name(&class));
+        Some(WindowInfo {
+            id: window as u64,
+            title: self.title(window)?,
+            pid: self.cardinals(window, self.atoms._NET_WM_PID, AtomEnum::CARDINAL).first().copied(),
+            // most apps name their icon after their class, "firefox" or "org.gnome.Nautilus"
+            icon: app.as_ref().map(|app| app.to_lowercase()),
+            app: app.unwrap_or_default(),
+        })
+    }
+
+    // Window managers act on client messages sent to the root window
+    fn send(&self, id: u64, message_type: u32, data: [u32; 5]) -> anyhow::Result<()> {
+        let window = Window::try_from(id)?;
+        let event = ClientMessageEvent::new(32, window, message_type, data);
+        let mask = EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY;
+        self.connection.send_event(false, self.root, mask, event)?.check()?;
+        self.connection.flush()?;
+        Ok(())
+    }
+}
+
+impl WindowBackend for X11Windows {
+    fn windows(&self) -> anyhow::Result<Vec<WindowInfo>> {
+        // the stacking order has the most recently raised window last
+        let mut clients = self.cardinals(self.root, self.atoms._NET_CLIENT_LIST_STACKING, AtomEnum::WINDOW);
+        clients.reverse();
+        if clients.is_empty() {
+            clients = self.cardinals(self.root, self.atoms._NET_CLIENT_LIST, AtomEnum::WINDOW);
+        }
+        if clients.is_empty() {
+            anyhow::bail!("The window manager doesn't list its windows");
+        }
+        let own_pid = std::process::id();
+        Ok(clients
+            .into_iter()
+            .filter_map(|window| self.info(window))
+            .filter(|info| info.pid != Some(own_pid))
+            .collect())
+    }
+
+    fn focus(&self, id: u64) -> anyhow::Result<()> {
+        self.send(id, self.atoms._NET_ACTIVE_WINDOW, [SOURCE_PAGER, x11rb::CURRENT_TIME, 0, 0, 0])
+    }
+
+    fn close(&self, id: u64) -> anyhow::Result<()> {
+        self.send(id, self.atoms._NET_CLOSE_WINDOW, [x11rb::CURRENT_TIME, SOURCE_PAGER, 0, 0, 0])
+    }
+
+    fn minimize(&self, id: u64) -> anyhow::Result<()> {
+        self.send(id, self.atoms.WM_CHANGE_STATE, [ICONIC_STATE, 0, 0, 0, 0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_class_name() {
+        assert_eq!(class_name(b"Navigator\0firefox\0").as_deref(), Some("firefox"));
+        assert_eq!(class_name(b"xterm\0").as_deref(), Some("xterm"));
+        assert_eq!(class_name(b""), None);
+    }
+}
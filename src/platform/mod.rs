@@ -1,8 +1,69 @@
 #[cfg(target_os = "windows")]
 pub mod windows;
 
-// #[cfg(target_os = "linux")]
-// pub mod linux;
+#[cfg(target_os = "linux")]
+pub mod linux;
 
 // #[cfg(target_os = "macos")]
 // pub mod macos;
+
+#[cfg(target_os = "windows")]
+pub use windows::{app_index, hotkey, network, process, shell, system, terminal, window};
+
+#[cfg(target_os = "linux")]
+pub use linux::{app_index, hotkey, network, process, shell, system, terminal, window};
+
+// A network adapter and its addresses, as the platform's network module reports it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkInterface {
+    pub name: String,
+    pub description: Option<String>,
+    pub addresses: Vec<std::net::IpAddr>,
+    pub mac: Option<String>,
+    pub gateways: Vec<std::net::IpAddr>,
+}
+
+// A socket bound to a local port, with the process owning it when that can be found out
+#[derive(Debug, Clone, PartialEq)]
+pub struct Listener {
+    // "tcp" or "udp"
+    pub protocol: &'static str,
+    pub address: std::net::SocketAddr,
+    pub pid: Option<u32>,
+}
+
+// A running process as the platform's process module lists it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub parent: Option<u32>,
+    pub name: String,
+    // the full command line on Linux, the executable's path on Windows
+    pub command: String,
+    // CPU time used so far, two samples of it give the usage in between
+    pub cpu_time: std::time::Duration,
+    // resident memory in bytes
+    pub memory: u64,
+}
+
+// A top-level window as the platform's window module lists it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WindowInfo {
+    // the native handle, an X11 window id or an HWND
+    pub id: u64,
+    pub title: String,
+    // the owning application, like "firefox"
+    pub app: String,
+    pub pid: Option<u32>,
+    // icon location for the icon service
+    pub icon: Option<String>,
+}
+
+// What the window switcher needs from the windowing system
+pub trait WindowBackend {
+    // Most recently used first
+    fn windows(&self) -> anyhow::Result<Vec<WindowInfo>>;
+    fn focus(&self, id: u64) -> anyhow::Result<()>;
+    fn close(&self, id: u64) -> anyhow::Result<()>;
+    fn minimize(&self, id: u64) -> anyhow::Result<()>;
+}
@@ -1,93 +1,198 @@
 use std::path::{PathBuf, Path};
 use lnk::ShellLink;
-use dirs::data_dir;
 use lnk::encoding::WINDOWS_1252;
+use windows::core::HSTRING;
+use windows::Management::Deployment::PackageManager;
 
-#[derive(Debug, Clone)]
-pub struct AppEntry {
-    pub name: String,
-    pub path: PathBuf,
-    pub icon: Option<PathBuf>,
-}
-
-pub struct AppIndex {
-    apps: Vec<AppEntry>,
-}
+use crate::services::app_index::{split_arguments, AppEntry, Launch};
 
 fn parse_lnk(path: &Path) -> Option<AppEntry> {
     let link = ShellLink::open(path, WINDOWS_1252).ok()?;
+    let strings = link.string_data();
+    // shortcuts to anything but a program, like scripts, .msc consoles or shell folders, are opened
+    // as they are so the shell applies their arguments and working directory
+    let mut app = AppEntry {
+        id: path.to_string_lossy().to_string(),
+        name: path.file_stem()?.to_string_lossy().to_string(),
+        path: path.to_path_buf(),
+        icon: strings
+            .icon_location()
+            .as_deref()
+            .filter(|location| !location.is_empty())
+            .map(|location| format!("{},{}", location, link.header().icon_index())),
+        // the shortcut's comment, shown as its tooltip in the Start Menu
+        description: strings.name_string().clone().filter(|d| !d.is_empty()),
+        launch: Launch::Open,
+        ..Default::default()
+    };
     // we are using .as_ref() to convert the Option<ShellLink> to Option<&ShellLink>
     // this is because the link_info() method returns an Option<ShellLink>
-    let target = link.link_info().as_ref()?.local_base_path()?.to_string();
-    let target_path = PathBuf::from(&target);
-    if target_path.extension()?.to_str()? != "exe" {
-        return None;
+    let Some(target) = link.link_info().as_ref().and_then(|info| info.local_base_path()) else {
+        return Some(app);
+    };
+    let target_path = PathBuf::from(target);
+    app.exec = target_path.file_stem()?.to_string_lossy().to_string();
+    if target_path.extension().is_some_and(|e| e.eq_ignore_ascii_case("exe")) {
+        app.icon.get_or_insert_with(|| target.to_string());
+        app.args = strings.command_line_arguments().as_deref().map(split_arguments).unwrap_or_default();
+        app.path = target_path;
+        app.launch = Launch::Run;
+    }
+    Some(app)
+}
+
+// Internet shortcuts are small INI files with the address under [InternetShortcut]
+fn parse_url_file(path: &Path) -> Option<AppEntry> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let (mut url, mut icon_file, mut icon_index) = (None, None, 0);
+    for line in content.lines().map(str::trim) {
+        match line.split_once('=') {
+            Some(("URL", value)) if !value.is_empty() => url = Some(value.to_string()),
+            Some(("IconFile", value)) if !value.is_empty() => icon_file = Some(value.to_string()),
+            Some(("IconIndex", value)) => icon_index = value.parse().unwrap_or(0),
+            _ => {}
+        }
     }
-    let name = path.file_stem()?.to_string_lossy().to_string();
-    let icon = link.string_data().icon_location().as_ref().map(|s| PathBuf::from(s));
+    let url = url?;
     Some(AppEntry {
-        name,
-        path: target_path,
-        icon,
+        id: path.to_string_lossy().to_string(),
+        name: path.file_stem()?.to_string_lossy().to_string(),
+        path: path.to_path_buf(),
+        icon: icon_file.map(|file| format!("{},{}", file, icon_index)),
+        description: Some(url.clone()),
+        launch: Launch::Url(url),
+        ..Default::default()
     })
 }
 
-impl AppIndex {
-    pub fn build() -> Self {
-        let mut apps = Vec::new();
-        let dirs = vec![
-            // PathBuf since it is absolute
-            PathBuf::from(r"C:\ProgramData\Microsoft\Windows\Start Menu\Programs"),
-            // dirs::data_dir() since it is relative. An alternative is to use std::env::home_dir() inside of PathBuf::from()
-            dirs::data_dir().unwrap().join(r"Microsoft\Windows\Start Menu\Programs"),
-            // paths for desktop applications
-        ];
-        for dir in dirs {
-            Self::scan_dir(&dir, &mut apps);
-        }
+// Package manifests name their logo without the scale, "StoreLogo.png" is on disk as
+// StoreLogo.scale-200.png and the like; the largest one is kept
+fn logo_file(uri: &str) -> Option<String> {
+    let path = url::Url::parse(uri).ok()?.to_file_path().ok()?;
+    if path.exists() {
+        return Some(path.to_string_lossy().to_string());
+    }
+    let prefix = format!("{}.", path.file_stem()?.to_string_lossy().to_lowercase());
+    let mut variants: Vec<PathBuf> = std::fs::read_dir(path.parent()?)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|variant| {
+            let name = variant.file_name().unwrap_or_default().to_string_lossy().to_lowercase();
+            name.starts_with(&prefix) && name.ends_with(".png")
+        })
+        .collect();
+    variants.sort();
+    variants.pop().map(|variant| variant.to_string_lossy().to_string())
+}
 
-        let built_in_apps = vec![
-            ("File Explorer", r"C:\Windows\explorer.exe"),
-            ("Task Manager", r"C:\Windows\System32\Taskmgr.exe"),
-            ("Command Prompt", r"C:\Windows\System32\cmd.exe"),
-            ("Registry Editor", r"C:\Windows\regedit.exe"),
-            ("Calculator", r"C:\Windows\System32\calc.exe"),
-            ("Notepad", r"C:\Windows\System32\notepad.exe"),
-            ("Paint", r"C:\Windows\System32\mspaint.exe"),
-            ("Control Panel", r"C:\Windows\System32\control.exe"),
-        ]; 
-        for (name, path) in built_in_apps {
-            let path_buf = PathBuf::from(path);
-            if path_buf.exists() {
-                apps.push(AppEntry { name: name.to_string(), path: path_buf, icon: None });
+// Store and other packaged apps of the current user, started through their AppUserModelID. These
+// aren't in the Start Menu folders, so new ones show up with the next rebuild.
+fn packaged_apps() -> windows::core::Result<Vec<AppEntry>> {
+    let manager = PackageManager::new()?;
+    let mut apps = Vec::new();
+    for package in manager.FindPackagesByUserSecurityId(&HSTRING::new())? {
+        if package.IsFramework().unwrap_or(true) || package.IsResourcePackage().unwrap_or(true) {
+            continue;
+        }
+        let Ok(entries) = package.GetAppListEntries() else {
+            continue;
+        };
+        let logo = package.Logo().ok().and_then(|uri| logo_file(&uri.RawUri().ok()?.to_string()));
+        let package_name = package.Id().and_then(|id| id.Name()).map(|name| name.to_string()).unwrap_or_default();
+        for entry in entries {
+            let (Ok(aumid), Ok(info)) = (entry.AppUserModelId(), entry.DisplayInfo()) else {
+                continue;
+            };
+            let name = info.DisplayName().map(|name| name.to_string()).unwrap_or_default();
+            if name.is_empty() {
+                continue;
             }
+            apps.push(AppEntry {
+                id: aumid.to_string(),
+                name,
+                icon: logo.clone(),
+                description: info.Description().ok().map(|d| d.to_string()).filter(|d| !d.is_empty()),
+                keywords: vec![package_name.clone()],
+                launch: Launch::Url(format!(r"shell:AppsFolder\{}", aumid)),
+                ..Default::default()
+            });
+        }
+    }
+    Ok(apps)
+}
+
+pub fn application_dirs() -> Vec<PathBuf> {
+    vec![
+        // PathBuf since it is absolute
+        PathBuf::from(r"C:\ProgramData\Microsoft\Windows\Start Menu\Programs"),
+        // dirs::data_dir() since it is relative. An alternative is to use std::env::home_dir() inside of PathBuf::from()
+        dirs::data_dir().unwrap().join(r"Microsoft\Windows\Start Menu\Programs"),
+        // paths for desktop applications
+    ]
+}
+
+pub fn scan() -> Vec<AppEntry> {
+    let mut apps = Vec::new();
+    for dir in application_dirs() {
+        scan_dir(&dir, &mut apps);
+    }
+
+    let built_in_apps = vec![
+        ("File Explorer", r"C:\Windows\explorer.exe"),
+        ("Task Manager", r"C:\Windows\System32\Taskmgr.exe"),
+        ("Command Prompt", r"C:\Windows\System32\cmd.exe"),
+        ("Registry Editor", r"C:\Windows\regedit.exe"),
+        ("Calculator", r"C:\Windows\System32\calc.exe"),
+        ("Notepad", r"C:\Windows\System32\notepad.exe"),
+        ("Paint", r"C:\Windows\System32\mspaint.exe"),
+        ("Control Panel", r"C:\Windows\System32\control.exe"),
+    ];
+    for (name, path) in built_in_apps {
+        let path_buf = PathBuf::from(path);
+        if path_buf.exists() {
+            apps.push(AppEntry {
+                id: path.to_string(),
+                name: name.to_string(),
+                icon: Some(path.to_string()),
+                exec: path_buf.file_stem().unwrap_or_default().to_string_lossy().to_string(),
+                path: path_buf,
+                ..Default::default()
+            });
         }
+    }
 
-        apps.sort_by(|a, b| a.name.cmp(&b.name));
-        apps.dedup_by(|a, b| a.name == b.name);
-        Self { apps }
+    match packaged_apps() {
+        Ok(packaged) => apps.extend(packaged),
+        Err(e) => log::warn!("Failed to list Store apps: {}", e),
     }
+    apps
+}
+
+// Some(None) for a shortcut that couldn't be read, None for files that aren't shortcuts
+fn parse_shortcut(path: &Path) -> Option<Option<AppEntry>> {
+    match path.extension()?.to_string_lossy().to_lowercase().as_str() {
+        "lnk" => Some(parse_lnk(path)),
+        "url" => Some(parse_url_file(path)),
+        _ => None,
+    }
+}
 
-    fn scan_dir(dir: &Path, apps: &mut Vec<AppEntry>) {
-        if let Ok(entries) = std::fs::read_dir(dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() {
-                    Self::scan_dir(&path, apps);
-                } else if path.extension().map_or(false, |e| e == "lnk") {
-                    if let Some(app) = parse_lnk(&path) {
-                        apps.push(app);
-                    }
-                }
+fn scan_dir(dir: &Path, apps: &mut Vec<AppEntry>) {
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                scan_dir(&path, apps);
+            } else if let Some(Some(app)) = parse_shortcut(&path) {
+                apps.push(app);
             }
         }
     }
+}
 
-    pub fn search(&self, query: &str) -> Vec<AppEntry> {
-        let query_lower = query.to_lowercase();
-        self.apps.iter()
-            .filter(|app| app.name.to_lowercase().contains(&query_lower))
-            .cloned()
-            .collect()
-    }
+// The shortcut a changed file is and the app it points at now; None for files that aren't shortcuts
+pub fn load(path: &Path) -> Option<(String, Option<AppEntry>)> {
+    let app = parse_shortcut(path)?;
+    Some((path.to_string_lossy().to_string(), app))
 }
@@ -1,11 +1,21 @@
 use global_hotkey::{GlobalHotKeyManager, GlobalHotKeyEvent, hotkey::{HotKey, Modifiers, Code}};
 use futures::channel::mpsc;
+use std::cell::RefCell;
 
-pub fn register_hotkey() -> mpsc::UnboundedReceiver<()> {
+thread_local! {
+    // kept for the life of the app, the registration ends when the manager is dropped
+    static REGISTERED: RefCell<Option<(GlobalHotKeyManager, HotKey)>> = const { RefCell::new(None) };
+}
+
+// `shortcut` uses the config format, e.g. "Alt+Space"
+pub fn register_hotkey(shortcut: &str) -> mpsc::UnboundedReceiver<()> {
     let manager = GlobalHotKeyManager::new().unwrap();
-    let hotkey = HotKey::new(Some(Modifiers::CONTROL), Code::Space);
+    let hotkey = shortcut.parse::<HotKey>().unwrap_or_else(|e| {
+        log::warn!("Invalid launcher hotkey {:?} ({}), using Ctrl+Space.", shortcut, e);
+        HotKey::new(Some(Modifiers::CONTROL), Code::Space)
+    });
     let _ = manager.register(hotkey);
-    Box::leak(Box::new(manager));
+    REGISTERED.set(Some((manager, hotkey)));
 
     let (tx, rx) = mpsc::unbounded::<()>();
 
@@ -19,4 +29,24 @@ pub fn register_hotkey() -> mpsc::UnboundedReceiver<()> {
     });
 
     rx
-}
\ No newline at end of file
+}
+
+// Swaps the launcher hotkey for `shortcut` after it's changed in the settings. The old one stays
+// registered if the new one can't be parsed or is taken by another program.
+pub fn change_hotkey(shortcut: &str) -> anyhow::Result<()> {
+    let hotkey = shortcut.parse::<HotKey>()?;
+    REGISTERED.with_borrow_mut(|registered| {
+        let Some((manager, current)) = registered else {
+            anyhow::bail!("The hotkey manager didn't start");
+        };
+        if *current == hotkey {
+            return Ok(());
+        }
+        manager.register(hotkey)?;
+        if let Err(e) = manager.unregister(*current) {
+            log::warn!("Can't unregister the old launcher hotkey: {}", e);
+        }
+        *current = hotkey;
+        Ok(())
+    })
+}
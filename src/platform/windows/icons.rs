@@ -0,0 +1,85 @@
+use std::path::Path;
+
+use image::RgbaImage;
+use windows::core::PCWSTR;
+use windows::Win32::Graphics::Gdi::{
+    CreateCompatibleDC, DeleteDC, DeleteObject, GetDIBits, GetObjectW, BITMAP, BITMAPINFO,
+    BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS,
+};
+use windows::Win32::UI::Shell::ExtractIconExW;
+use windows::Win32::UI::WindowsAndMessaging::{DestroyIcon, GetIconInfo, HICON, ICONINFO};
+
+// Extracts the icon at `index` from an .exe, .dll or .ico file, as used by .lnk icon locations
+pub fn extract_icon(path: &Path, index: i32) -> Option<RgbaImage> {
+    let wide: Vec<u16> = path.as_os_str().to_string_lossy().encode_utf16().chain(Some(0)).collect();
+    let mut icon = HICON::default();
+    unsafe {
+        let extracted = ExtractIconExW(PCWSTR(wide.as_ptr()), index, Some(&mut icon), None, 1);
+        if extracted == 0 || icon.is_invalid() {
+            return None;
+        }
+        let image = icon_to_image(icon);
+        let _ = DestroyIcon(icon);
+        image
+    }
+}
+
+unsafe fn icon_to_image(icon: HICON) -> Option<RgbaImage> {
+    let mut info = ICONINFO::default();
+    GetIconInfo(icon, &mut info).ok()?;
+    let mut bitmap = BITMAP::default();
+    let read = GetObjectW(
+        info.hbmColor.into(),
+        std::mem::size_of::<BITMAP>() as i32,
+        Some(&mut bitmap as *mut _ as *mut _),
+    );
+    let image = if read == 0 {
+        None
+    } else {
+        let width = bitmap.bmWidth;
+        let height = bitmap.bmHeight;
+        let mut bitmap_info = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: width,
+                // negative height asks for top-down rows
+                biHeight: -height,
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut pixels = vec![0u8; (width * height * 4) as usize];
+        let dc = CreateCompatibleDC(None);
+        let lines = GetDIBits(
+            dc,
+            info.hbmColor,
+            0,
+            height as u32,
+            Some(pixels.as_mut_ptr() as *mut _),
+            &mut bitmap_info,
+            DIB_RGB_COLORS,
+        );
+        let _ = DeleteDC(dc);
+        if lines == 0 {
+            None
+        } else {
+            // BGRA -> RGBA
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+            // old icons carry no alpha channel and would render fully transparent
+            if pixels.chunks_exact(4).all(|pixel| pixel[3] == 0) {
+                for pixel in pixels.chunks_exact_mut(4) {
+                    pixel[3] = 255;
+                }
+            }
+            RgbaImage::from_raw(width as u32, height as u32, pixels)
+        }
+    };
+    let _ = DeleteObject(info.hbmColor.into());
+    let _ = DeleteObject(info.hbmMask.into());
+    image
+}
\ No newline at end of file
@@ -1,5 +1,13 @@
 pub mod hotkey;
 pub mod app_index;
+pub mod shell;
+pub mod icons;
+pub mod network;
+pub mod process;
+pub mod system;
+pub mod terminal;
+pub mod window;
 
 pub use hotkey::*;
 pub use app_index::*;
+pub use shell::*;
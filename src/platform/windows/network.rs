@@ -0,0 +1,154 @@
+// Network adapters from GetAdaptersAddresses, which also knows their MAC address and gateways,
+// and listening sockets from the extended TCP and UDP tables
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use windows::Win32::Foundation::{ERROR_BUFFER_OVERFLOW, ERROR_INSUFFICIENT_BUFFER, ERROR_SUCCESS};
+use windows::Win32::NetworkManagement::IpHelper::{
+    GetAdaptersAddresses, GetExtendedTcpTable, GetExtendedUdpTable, GAA_FLAG_INCLUDE_GATEWAYS, GAA_FLAG_SKIP_ANYCAST,
+    GAA_FLAG_SKIP_DNS_SERVER, GAA_FLAG_SKIP_MULTICAST, IP_ADAPTER_ADDRESSES_LH, MIB_TCP6ROW_OWNER_PID,
+    MIB_TCPROW_OWNER_PID, MIB_UDP6ROW_OWNER_PID, MIB_UDPROW_OWNER_PID, TCP_TABLE_OWNER_PID_LISTENER,
+    UDP_TABLE_OWNER_PID,
+};
+use windows::Win32::Networking::WinSock::{AF_INET, AF_INET6, AF_UNSPEC, SOCKET_ADDRESS};
+
+use crate::platform::{Listener, NetworkInterface};
+
+// sockaddr_in keeps the address at bytes 4..8, sockaddr_in6 at 8..24
+unsafe fn ip_address(address: &SOCKET_ADDRESS) -> Option<IpAddr> {
+    if address.lpSockaddr.is_null() || address.iSockaddrLength <= 0 {
+        return None;
+    }
+    let bytes = unsafe { std::slice::from_raw_parts(address.lpSockaddr as *const u8, address.iSockaddrLength as usize) };
+    let family = unsafe { (*address.lpSockaddr).sa_family };
+    if family == AF_INET && bytes.len() >= 8 {
+        let octets: [u8; 4] = bytes[4..8].try_into().ok()?;
+        Some(IpAddr::V4(Ipv4Addr::from(octets)))
+    } else if family == AF_INET6 && bytes.len() >= 24 {
+        let octets: [u8; 16] = bytes[8..24].try_into().ok()?;
+        Some(IpAddr::V6(Ipv6Addr::from(octets)))
+    } else {
+        None
+    }
+}
+
+fn format_mac(bytes: &[u8]) -> Option<String> {
+    if bytes.is_empty() || bytes.iter().all(|b| *b == 0) {
+        return None;
+    }
+    Some(bytes.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(":"))
+}
+
+// Adapters with at least one address, in the order the system lists them
+pub fn interfaces() -> anyhow::Result<Vec<NetworkInterface>> {
+    let flags = GAA_FLAG_INCLUDE_GATEWAYS | GAA_FLAG_SKIP_ANYCAST | GAA_FLAG_SKIP_MULTICAST | GAA_FLAG_SKIP_DNS_SERVER;
+    // u64s keep the buffer aligned for the structs written into it
+    let mut size: u32 = 16 * 1024;
+    let mut buffer: Vec<u64>;
+    loop {
+        buffer = vec![0u64; (size as usize).div_ceil(8)];
+        let result = unsafe {
+            GetAdaptersAddresses(
+                AF_UNSPEC.0 as u32,
+                flags,
+                None,
+                Some(buffer.as_mut_ptr() as *mut IP_ADAPTER_ADDRESSES_LH),
+                &mut size,
+            )
+        };
+        match result {
+            code if code == ERROR_SUCCESS.0 => break,
+            code if code == ERROR_BUFFER_OVERFLOW.0 => continue,
+            code => anyhow::bail!("GetAdaptersAddresses failed with error {}", code),
+        }
+    }
+
+    let mut interfaces = Vec::new();
+    let mut adapter = buffer.as_ptr() as *const IP_ADAPTER_ADDRESSES_LH;
+    while let Some(current) = unsafe { adapter.as_ref() } {
+        adapter = current.Next;
+        let mut addresses = Vec::new();
+        let mut unicast = current.FirstUnicastAddress;
+        while let Some(address) = unsafe { unicast.as_ref() } {
+            addresses.extend(unsafe { ip_address(&address.Address) });
+            unicast = address.Next;
+        }
+        if addresses.is_empty() {
+            continue;
+        }
+        let mut gateways = Vec::new();
+        let mut gateway = current.FirstGatewayAddress;
+        while let Some(address) = unsafe { gateway.as_ref() } {
+            gateways.extend(unsafe { ip_address(&address.Address) });
+            gateway = address.Next;
+        }
+        let length = (current.PhysicalAddressLength as usize).min(current.PhysicalAddress.len());
+        interfaces.push(NetworkInterface {
+            name: unsafe { current.FriendlyName.to_string() }.unwrap_or_default(),
+            description: unsafe { current.Description.to_string() }.ok().filter(|d| !d.is_empty()),
+            addresses,
+            mac: format_mac(&current.PhysicalAddress[..length]),
+            gateways,
+        });
+    }
+    Ok(interfaces)
+}
+
+// Calls a GetExtended*Table function until the buffer is big enough, then returns its rows
+fn read_table<Row: Copy>(fetch: impl Fn(*mut core::ffi::c_void, &mut u32) -> u32) -> anyhow::Result<Vec<Row>> {
+    let mut size: u32 = 0;
+    let mut buffer: Vec<u64> = Vec::new();
+    loop {
+        let result = fetch(buffer.as_mut_ptr() as *mut core::ffi::c_void, &mut size);
+        if result == ERROR_SUCCESS.0 && !buffer.is_empty() {
+            break;
+        }
+        if result != ERROR_SUCCESS.0 && result != ERROR_INSUFFICIENT_BUFFER.0 {
+            anyhow::bail!("Reading the socket table failed with error {}", result);
+        }
+        buffer = vec![0u64; (size.max(8) as usize).div_ceil(8)];
+    }
+    // a u32 entry count, then the rows aligned to their own alignment
+    let count = unsafe { *(buffer.as_ptr() as *const u32) } as usize;
+    let offset = std::mem::align_of::<Row>().max(4);
+    let rows = unsafe { (buffer.as_ptr() as *const u8).add(offset) as *const Row };
+    Ok((0..count).map(|i| unsafe { *rows.add(i) }).collect())
+}
+
+// The port is kept in network byte order in the low 16 bits
+fn port(raw: u32) -> u16 {
+    u16::from_be(raw as u16)
+}
+
+// TCP sockets listening on `port` and UDP sockets bound to it
+pub fn listeners(wanted: u16) -> anyhow::Result<Vec<Listener>> {
+    let mut listeners = Vec::new();
+    let tcp4: Vec<MIB_TCPROW_OWNER_PID> = read_table(|buffer, size| unsafe {
+        GetExtendedTcpTable(Some(buffer), size, false, AF_INET.0 as u32, TCP_TABLE_OWNER_PID_LISTENER, 0)
+    })?;
+    for row in tcp4.iter().filter(|row| port(row.dwLocalPort) == wanted) {
+        let address = Ipv4Addr::from(row.dwLocalAddr.to_ne_bytes());
+        listeners.push(Listener { protocol: "tcp", address: SocketAddr::new(address.into(), wanted), pid: Some(row.dwOwningPid) });
+    }
+    let tcp6: Vec<MIB_TCP6ROW_OWNER_PID> = read_table(|buffer, size| unsafe {
+        GetExtendedTcpTable(Some(buffer), size, false, AF_INET6.0 as u32, TCP_TABLE_OWNER_PID_LISTENER, 0)
+    })?;
+    for row in tcp6.iter().filter(|row| port(row.dwLocalPort) == wanted) {
+        let address = Ipv6Addr::from(row.ucLocalAddr);
+        listeners.push(Listener { protocol: "tcp", address: SocketAddr::new(address.into(), wanted), pid: Some(row.dwOwningPid) });
+    }
+    let udp4: Vec<MIB_UDPROW_OWNER_PID> = read_table(|buffer, size| unsafe {
+        GetExtendedUdpTable(Some(buffer), size, false, AF_INET.0 as u32, UDP_TABLE_OWNER_PID, 0)
+    })?;
+    for row in udp4.iter().filter(|row| port(row.dwLocalPort) == wanted) {
+        let address = Ipv4Addr::from(row.dwLocalAddr.to_ne_bytes());
+        listeners.push(Listener { protocol: "udp", address: SocketAddr::new(address.into(), wanted), pid: Some(row.dwOwningPid) });
+    }
+    let udp6: Vec<MIB_UDP6ROW_OWNER_PID> = read_table(|buffer, size| unsafe {
+        GetExtendedUdpTable(Some(buffer), size, false, AF_INET6.0 as u32, UDP_TABLE_OWNER_PID, 0)
+    })?;
+    for row in udp6.iter().filter(|row| port(row.dwLocalPort) == wanted) {
+        let address = Ipv6Addr::from(row.ucLocalAddr);
+        listeners.push(Listener { protocol: "udp", address: SocketAddr::new(address.into(), wanted), pid: Some(row.dwOwningPid) });
+    }
+    Ok(listeners)
+}
@@ -0,0 +1,118 @@
+// Processes and their termination through the Win32 API
+use std::os::windows::process::CommandExt;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+
+use windows::core::PWSTR;
+use windows::Win32::Foundation::{CloseHandle, FILETIME, HANDLE};
+use windows::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+};
+use windows::Win32::System::ProcessStatus::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
+use windows::Win32::System::Threading::{
+    GetProcessTimes, OpenProcess, QueryFullProcessImageNameW, TerminateProcess, PROCESS_NAME_WIN32,
+    PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_TERMINATE,
+};
+
+use crate::platform::ProcessInfo;
+
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+// Windows can't run without these, ending one logs the user out or blue screens
+const CRITICAL: &[&str] = &[
+    "system", "registry", "smss.exe", "csrss.exe", "wininit.exe", "winlogon.exe", "services.exe", "lsass.exe",
+    "svchost.exe", "dwm.exe", "explorer.exe", "fontdrvhost.exe", "lsaiso.exe", "memcompression",
+];
+
+// The executable's file name, e.g. "node.exe"
+pub fn name(pid: u32) -> Option<String> {
+    executable(pid)?.file_name().map(|name| name.to_string_lossy().to_string())
+}
+
+pub fn executable(pid: u32) -> Option<PathBuf> {
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) }.ok()?;
+    let mut buffer = [0u16; 1024];
+    let mut size = buffer.len() as u32;
+    let result = unsafe { QueryFullProcessImageNameW(handle, PROCESS_NAME_WIN32, PWSTR(buffer.as_mut_ptr()), &mut size) };
+    let _ = unsafe { CloseHandle(handle) };
+    result.ok()?;
+    Some(PathBuf::from(String::from_utf16_lossy(&buffer[..size as usize])))
+}
+
+fn filetime(time: FILETIME) -> Duration {
+    // in units of 100 nanoseconds
+    Duration::from_nanos(((time.dwHighDateTime as u64) << 32 | time.dwLowDateTime as u64) * 100)
+}
+
+// CPU time and working set, for processes this user may query
+fn usage(handle: HANDLE) -> (Duration, u64) {
+    let (mut created, mut exited, mut kernel, mut user) = Default::default();
+    let cpu_time = match unsafe { GetProcessTimes(handle, &mut created, &mut exited, &mut kernel, &mut user) } {
+        Ok(()) => filetime(kernel) + filetime(user),
+        Err(_) => Duration::ZERO,
+    };
+    let mut counters = PROCESS_MEMORY_COUNTERS::default();
+    let size = std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+    let memory = match unsafe { GetProcessMemoryInfo(handle, &mut counters, size) } {
+        Ok(()) => counters.WorkingSetSize as u64,
+        Err(_) => 0,
+    };
+    (cpu_time, memory)
+}
+
+fn process(entry: &PROCESSENTRY32W) -> ProcessInfo {
+    let length = entry.szExeFile.iter().position(|c| *c == 0).unwrap_or(entry.szExeFile.len());
+    let pid = entry.th32ProcessID;
+    let mut process = ProcessInfo {
+        pid,
+        parent: Some(entry.th32ParentProcessID).filter(|parent| *parent != 0),
+        name: String::from_utf16_lossy(&entry.szExeFile[..length]),
+        ..Default::default()
+    };
+    // reading another process' command line takes its memory, the path has to do
+    process.command = executable(pid).map(|path| path.to_string_lossy().to_string()).unwrap_or_else(|| process.name.clone());
+    if let Ok(handle) = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) } {
+        (process.cpu_time, process.memory) = usage(handle);
+        let _ = unsafe { CloseHandle(handle) };
+    }
+    process
+}
+
+pub fn list() -> anyhow::Result<Vec<ProcessInfo>> {
+    let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) }?;
+    let mut entry = PROCESSENTRY32W { dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32, ..Default::default() };
+    let mut processes = Vec::new();
+    let mut next = unsafe { Process32FirstW(snapshot, &mut entry) };
+    while next.is_ok() {
+        // the System Idle Process is a stand-in for idle time
+        if entry.th32ProcessID != 0 {
+            processes.push(process(&entry));
+        }
+        next = unsafe { Process32NextW(snapshot, &mut entry) };
+    }
+    let _ = unsafe { CloseHandle(snapshot) };
+    Ok(processes)
+}
+
+pub fn is_critical(process: &ProcessInfo) -> bool {
+    process.pid == 4 || CRITICAL.contains(&process.name.to_lowercase().as_str())
+}
+
+// taskkill asks the windows to close, TerminateProcess ends the process outright
+pub fn kill(pid: u32, force: bool) -> anyhow::Result<()> {
+    if !force {
+        let output = Command::new("taskkill")
+            .args(["/PID", &pid.to_string()])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()?;
+        if !output.status.success() {
+            anyhow::bail!("Failed to stop process {}: {}", pid, String::from_utf8_lossy(&output.stderr).trim());
+        }
+        return Ok(());
+    }
+    let handle = unsafe { OpenProcess(PROCESS_TERMINATE, false, pid) }?;
+    let result = unsafe { TerminateProcess(handle, 1) };
+    let _ = unsafe { CloseHandle(handle) };
+    result?;
+    Ok(())
+}
@@ -0,0 +1,12 @@
+use std::os::windows::process::CommandExt;
+
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+// Opens a file, folder or URL with its default handler
+pub fn open(target: &str) -> anyhow::Result<()> {
+    std::process::Command::new("cmd")
+        .args(["/C", "start", "", target])
+        .creation_flags(CREATE_NO_WINDOW)
+        .spawn()?;
+    Ok(())
+}
@@ -0,0 +1,89 @@
+// Power, session, display and volume commands through the Win32 API
+use std::os::windows::process::CommandExt;
+use std::process::Command;
+
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{LPARAM, WPARAM};
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYBD_EVENT_FLAGS, KEYEVENTF_KEYUP, VIRTUAL_KEY,
+    VK_VOLUME_DOWN, VK_VOLUME_MUTE, VK_VOLUME_UP,
+};
+use windows::Win32::UI::Shell::{SHEmptyRecycleBinW, SHERB_NOCONFIRMATION, SHERB_NOPROGRESSUI, SHERB_NOSOUND};
+use windows::Win32::UI::WindowsAndMessaging::{PostMessageW, HWND_BROADCAST, SC_MONITORPOWER, WM_SYSCOMMAND};
+
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+// lParam of SC_MONITORPOWER that turns the displays off
+const MONITOR_OFF: isize = 2;
+
+fn key_input(key: VIRTUAL_KEY, flags: KEYBD_EVENT_FLAGS) -> INPUT {
+    INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT { wVk: key, wScan: 0, dwFlags: flags, time: 0, dwExtraInfo: 0 },
+        },
+    }
+}
+
+// The media keys, so the change shows in the system volume flyout
+fn press_key(key: VIRTUAL_KEY) -> anyhow::Result<()> {
+    let inputs = [key_input(key, KEYBD_EVENT_FLAGS(0)), key_input(key, KEYEVENTF_KEYUP)];
+    let sent = unsafe { SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) };
+    if sent as usize != inputs.len() {
+        anyhow::bail!("Failed to send the key press: {}", std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+fn lock() -> anyhow::Result<()> {
+    Command::new("rundll32.exe")
+        .args(["user32.dll,LockWorkStation"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .spawn()?;
+    Ok(())
+}
+
+fn empty_recycle_bin() -> anyhow::Result<()> {
+    let flags = SHERB_NOCONFIRMATION | SHERB_NOPROGRESSUI | SHERB_NOSOUND;
+    unsafe { SHEmptyRecycleBinW(None, PCWSTR::null(), flags)? };
+    Ok(())
+}
+
+fn sleep_display() -> anyhow::Result<()> {
+    unsafe {
+        PostMessageW(
+            Some(HWND_BROADCAST),
+            WM_SYSCOMMAND,
+            WPARAM(SC_MONITORPOWER as usize),
+            LPARAM(MONITOR_OFF),
+        )?
+    };
+    Ok(())
+}
+
+// Starts a program without waiting for it or opening a console window
+pub fn spawn_detached(program: &str, args: &[String]) -> anyhow::Result<()> {
+    Command::new(program)
+        .args(args)
+        .creation_flags(CREATE_NO_WINDOW)
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to run {}: {}", program, e))?;
+    Ok(())
+}
+
+pub fn run(command: &str) -> anyhow::Result<()> {
+    match command {
+        "shutdown" => system_shutdown::shutdown()?,
+        "restart" => system_shutdown::reboot()?,
+        "sleep" => system_shutdown::sleep()?,
+        "hibernate" => system_shutdown::hibernate()?,
+        "logout" => system_shutdown::logout()?,
+        "lock" => lock()?,
+        "empty_trash" => empty_recycle_bin()?,
+        "sleep_display" => sleep_display()?,
+        "toggle_mute" => press_key(VK_VOLUME_MUTE)?,
+        "volume_up" => press_key(VK_VOLUME_UP)?,
+        "volume_down" => press_key(VK_VOLUME_DOWN)?,
+        _ => anyhow::bail!("Unknown system command: {}", command),
+    }
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use std::collections::BTreeSet;
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const CREATE_NEW_CONSOLE: u32 = 0x00000010;
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+// Also tries the PATHEXT extensions, so "pwsh" finds pwsh.exe
+pub fn find_in_path(name: &str) -> Option<PathBuf> {
+    let extensions = std::env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string());
+    let candidates: Vec<String> = std::iter::once(name.to_string())
+        .chain(extensions.split(';').filter(|e| !e.is_empty()).map(|e| format!("{}{}", name, e.to_lowercase())))
+        .collect();
+    if name.contains(['\\', '/']) {
+        return candidates.into_iter().map(PathBuf::from).find(|path| path.is_file());
+    }
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .flat_map(|dir| candidates.iter().map(move |c| dir.join(c)))
+        .find(|path| path.is_file())
+}
+
+// Names (without extension) of the PATHEXT files in the PATH directories, sorted
+pub fn path_executables() -> Vec<String> {
+    let Some(path) = std::env::var_os("PATH") else {
+        return Vec::new();
+    };
+    let extensions: Vec<String> = std::env::var("PATHEXT")
+        .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string())
+        .split(';')
+        .map(|e| e.trim_start_matches('.').to_lowercase())
+        .collect();
+    let mut names = BTreeSet::new();
+    for dir in std::env::split_paths(&path) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let runnable = path
+                .extension()
+                .is_some_and(|e| extensions.contains(&e.to_string_lossy().to_lowercase()));
+            if runnable {
+                if let Some(stem) = path.file_stem() {
+                    names.insert(stem.to_string_lossy().to_string());
+                }
+            }
+        }
+    }
+    names.into_iter().collect()
+}
+
+// Runs the program in a new console window, or in the configured terminal (e.g. "wt" for Windows Terminal)
+pub fn open_in_terminal(terminal: Option<&str>, program: &str, args: &[String], cwd: &Path) -> anyhow::Result<()> {
+    match terminal.filter(|t| !t.trim().is_empty()) {
+        Some(terminal) if Path::new(terminal).file_stem().is_some_and(|s| s.eq_ignore_ascii_case("wt")) => {
+            Command::new(terminal).arg("-d").arg(cwd).arg(program).args(args).spawn()?;
+        }
+        Some(terminal) => {
+            Command::new(terminal).arg(program).args(args).current_dir(cwd).spawn()?;
+        }
+        None => {
+            Command::new(program).args(args).current_dir(cwd).creation_flags(CREATE_NEW_CONSOLE).spawn()?;
+        }
+    }
+    Ok(())
+}
+
+// A command for capturing output without flashing a console window
+pub fn background_command(program: &str) -> tokio::process::Command {
+    let mut command = tokio::process::Command::new(program);
+    command.creation_flags(CREATE_NO_WINDOW);
+    command
+}
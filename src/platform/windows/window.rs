@@ -0,0 +1,106 @@
+// Top-level windows as the taskbar shows them, through the Win32 API
+use windows::core::BOOL;
+use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+use windows::Win32::Graphics::Dwm::{DwmGetWindowAttribute, DWMWA_CLOAKED};
+use windows::Win32::UI::WindowsAndMessaging::{
+    EnumWindows, GetWindow, GetWindowLongW, GetWindowTextLengthW, GetWindowTextW, GetWindowThreadProcessId, IsIconic,
+    IsWindowVisible, PostMessageW, SetForegroundWindow, ShowWindow, GWL_EXSTYLE, GW_OWNER, SW_MINIMIZE, SW_RESTORE,
+    WM_CLOSE, WS_EX_TOOLWINDOW,
+};
+
+use crate::platform::{process, WindowBackend, WindowInfo};
+
+pub struct Win32Windows;
+
+pub fn backend() -> anyhow::Result<Box<dyn WindowBackend>> {
+    Ok(Box::new(Win32Windows))
+}
+
+fn hwnd(id: u64) -> HWND {
+    HWND(id as usize as *mut core::ffi::c_void)
+}
+
+// Visible, unowned windows that aren't tool windows; UWP apps keep cloaked windows around on
+// other virtual desktops and after they are closed, those are left out as well
+fn is_switchable(window: HWND) -> bool {
+    unsafe {
+        if !IsWindowVisible(window).as_bool() || GetWindow(window, GW_OWNER).is_ok_and(|owner| !owner.is_invalid()) {
+            return false;
+        }
+        if GetWindowLongW(window, GWL_EXSTYLE) as u32 & WS_EX_TOOLWINDOW.0 != 0 {
+            return false;
+        }
+        let mut cloaked = 0u32;
+        let size = std::mem::size_of::<u32>() as u32;
+        let result = DwmGetWindowAttribute(window, DWMWA_CLOAKED, &mut cloaked as *mut u32 as *mut _, size);
+        result.is_err() || cloaked == 0
+    }
+}
+
+fn info(window: HWND) -> Option<WindowInfo> {
+    if !is_switchable(window) {
+        return None;
+    }
+    let length = unsafe { GetWindowTextLengthW(window) };
+    if length <= 0 {
+        return None;
+    }
+    let mut buffer = vec![0u16; length as usize + 1];
+    let copied = unsafe { GetWindowTextW(window, &mut buffer) };
+    let title = String::from_utf16_lossy(&buffer[..copied.max(0) as usize]);
+    let mut pid = 0u32;
+    unsafe { GetWindowThreadProcessId(window, Some(&mut pid)) };
+    let executable = process::executable(pid);
+    Some(WindowInfo {
+        id: window.0 as usize as u64,
+        title,
+        app: executable
+            .as_ref()
+            .and_then(|path| path.file_stem())
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        pid: Some(pid).filter(|pid| *pid != 0),
+        icon: executable.map(|path| path.to_string_lossy().to_string()),
+    })
+}
+
+unsafe extern "system" fn collect(window: HWND, lparam: LPARAM) -> BOOL {
+    let windows = unsafe { &mut *(lparam.0 as *mut Vec<WindowInfo>) };
+    windows.extend(info(window));
+    BOOL(1)
+}
+
+impl WindowBackend for Win32Windows {
+    // EnumWindows goes through the windows in z-order, the most recently active one first
+    fn windows(&self) -> anyhow::Result<Vec<WindowInfo>> {
+        let mut windows: Vec<WindowInfo> = Vec::new();
+        unsafe { EnumWindows(Some(collect), LPARAM(&mut windows as *mut Vec<WindowInfo> as isize)) }?;
+        let own_pid = std::process::id();
+        windows.retain(|info| info.pid != Some(own_pid));
+        Ok(windows)
+    }
+
+    fn focus(&self, id: u64) -> anyhow::Result<()> {
+        let window = hwnd(id);
+        unsafe {
+            if IsIconic(window).as_bool() {
+                let _ = ShowWindow(window, SW_RESTORE);
+            }
+            if !SetForegroundWindow(window).as_bool() {
+                anyhow::bail!("Windows didn't let the window come to the front");
+            }
+        }
+        Ok(())
+    }
+
+    // Asks the window to close, like its close button, so apps can still ask to save
+    fn close(&self, id: u64) -> anyhow::Result<()> {
+        unsafe { PostMessageW(Some(hwnd(id)), WM_CLOSE, WPARAM(0), LPARAM(0)) }?;
+        Ok(())
+    }
+
+    fn minimize(&self, id: u64) -> anyhow::Result<()> {
+        let _ = unsafe { ShowWindow(hwnd(id), SW_MINIMIZE) };
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+// Converts CSV, JSON and StarDict dictionaries into the SQLite schema the plugin searches
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use rusqlite::{params, Connection};
+use serde_json::Value;
+
+// (word, part of speech, definition)
+pub type Row = (String, Option<String>, String);
+
+pub const SUPPORTED: &[&str] = &["csv", "tsv", "json", "ifo"];
+
+pub fn is_supported(path: &Path) -> bool {
+    extension(path).is_some_and(|e| SUPPORTED.contains(&e.as_str()))
+}
+
+fn extension(path: &Path) -> Option<String> {
+    path.extension().map(|e| e.to_string_lossy().to_lowercase())
+}
+
+// Reads the file and writes its entries to `destination`, replacing any dictionary already there
+pub fn import(source: &Path, destination: &Path) -> anyhow::Result<usize> {
+    let rows = read_rows(source)?;
+    if rows.is_empty() {
+        anyhow::bail!("No entries found in {}", source.display());
+    }
+    write_database(destination, &rows)?;
+    Ok(rows.len())
+}
+
+pub fn read_rows(path: &Path) -> anyhow::Result<Vec<Row>> {
+    match extension(path).as_deref() {
+        Some("csv") => read_csv(&std::fs::read_to_string(path)?, b','),
+        Some("tsv") => read_csv(&std::fs::read_to_string(path)?, b'\t'),
+        Some("json") => read_json(&std::fs::read_to_string(path)?),
+        Some("ifo") => read_stardict(path),
+        _ => anyhow::bail!("Unsupported dictionary format, expected .csv, .tsv, .json or a StarDict .ifo file"),
+    }
+}
+
+fn column(headers: &[String], names: &[&str]) -> Option<usize> {
+    headers.iter().position(|h| names.contains(&h.trim().to_lowercase().as_str()))
+}
+
+// word,definition or word,part_of_speech,definition; a header row naming the columns is optional
+pub fn read_csv(content: &str, delimiter: u8) -> anyhow::Result<Vec<Row>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    let mut records = reader.records();
+    let Some(first) = records.next().transpose()? else {
+        return Ok(Vec::new());
+    };
+    let headers: Vec<String> = first.iter().map(str::to_string).collect();
+    let named = column(&headers, &["word", "term", "headword"]).zip(column(&headers, &["definition", "meaning", "description"]));
+    let (word, definition, part_of_speech, skip_first) = match named {
+        Some((word, definition)) => (word, definition, column(&headers, &["part_of_speech", "pos", "type", "wordtype"]), true),
+        None if headers.len() >= 3 => (0, headers.len() - 1, Some(1), false),
+        None => (0, headers.len().saturating_sub(1), None, false),
+    };
+    let mut rows = Vec::new();
+    let first = (!skip_first).then_some(Ok(first));
+    for record in first.into_iter().chain(records) {
+        let record = record?;
+        let (Some(w), Some(d)) = (record.get(word), record.get(definition)) else {
+            continue;
+        };
+        if w.trim().is_empty() || word == definition {
+            continue;
+        }
+        let pos = part_of_speech.and_then(|i| record.get(i)).map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+        rows.push((w.trim().to_string(), pos, d.trim().to_string()));
+    }
+    Ok(rows)
+}
+
+fn definitions(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(s) => vec![s.clone()],
+        Value::Array(items) => items.iter().flat_map(definitions).collect(),
+        Value::Object(map) => map.get("definition").or_else(|| map.get("meaning")).map(definitions).unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+// [{"word": .., "definition": .., "part_of_speech": ..}] or {"word": "definition" | ["definitions"]}
+pub fn read_json(content: &str) -> anyhow::Result<Vec<Row>> {
+    let mut rows = Vec::new();
+    match serde_json::from_str::<Value>(content)? {
+        Value::Array(entries) => {
+            for entry in entries {
+                let Some(word) = entry.get("word").or_else(|| entry.get("term")).and_then(|w| w.as_str()) else {
+                    continue;
+                };
+                let pos = ["part_of_speech", "pos", "type"]
+                    .iter()
+                    .find_map(|key| entry.get(*key).and_then(|p| p.as_str()))
+                    .map(str::to_string);
+                let value = entry.get("definitions").or_else(|| entry.get("definition")).or_else(|| entry.get("meaning"));
+                for definition in value.map(definitions).unwrap_or_default() {
+                    rows.push((word.to_string(), pos.clone(), definition));
+                }
+            }
+        }
+        Value::Object(map) => {
+            for (word, value) in map {
+                for definition in definitions(&value) {
+                    rows.push((word.clone(), None, definition));
+                }
+            }
+        }
+        _ => anyhow::bail!("Expected a JSON array of entries or an object of words"),
+    }
+    Ok(rows)
+}
+
+// Reads `path`, or `path.gz`/`path.dz` when only the compressed file exists
+fn read_maybe_compressed(path: &Path, compressed: &str) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    if path.exists() {
+        File::open(path)?.read_to_end(&mut bytes)?;
+    } else {
+        let mut name = path.as_os_str().to_owned();
+        name.push(compressed);
+        GzDecoder::new(File::open(PathBuf::from(name))?).read_to_end(&mut bytes)?;
+    }
+    Ok(bytes)
+}
+
+fn strip_tags(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => output.push(c),
+            _ => {}
+        }
+    }
+    output.replace("&lt;", "<").replace("&gt;", ">").replace("&amp;", "&").replace("&quot;", "\"")
+}
+
+// The text of a StarDict article: plain types are kept, markup types lose their tags
+fn article_text(data: &[u8], types: Option<&str>) -> String {
+    let (kind, data) = match types {
+        Some(types) => (types.chars().next().unwrap_or('m'), data),
+        // without sametypesequence every field is prefixed with its type
+        None => match data.split_first() {
+            Some((kind, rest)) => (*kind as char, rest.split(|b| *b == 0).next().unwrap_or(rest)),
+            None => ('m', data),
+        },
+    };
+    let text = String::from_utf8_lossy(data).trim().to_string();
+    match kind {
+        'h' | 'g' | 'x' => strip_tags(&text.replace("<br>", "\n").replace("<br/>", "\n")),
+        _ => text,
+    }
+}
+
+// name.ifo with name.idx(.gz) and name.dict(.dz) next to it
+pub fn read_stardict(ifo: &Path) -> anyhow::Result<Vec<Row>> {
+    let info = std::fs::read_to_string(ifo)?;
+    if !info.starts_with("StarDict's dict ifo file") {
+        anyhow::bail!("{} is not a StarDict .ifo file", ifo.display());
+    }
+    let option = |key: &str| {
+        info.lines()
+            .filter_map(|line| line.split_once('='))
+            .find(|(k, _)| k.trim() == key)
+            .map(|(_, v)| v.trim().to_string())
+    };
+    let offset_bits = option("idxoffsetbits").and_then(|v| v.parse().ok()).unwrap_or(32);
+    let types = option("sametypesequence");
+    let index = read_maybe_compressed(&ifo.with_extension("idx"), ".gz")?;
+    let dict = read_maybe_compressed(&ifo.with_extension("dict"), ".dz")?;
+
+    let mut rows = Vec::new();
+    let mut rest = index.as_slice();
+    let offset_size = if offset_bits == 64 { 8 } else { 4 };
+    while let Some(end) = rest.iter().position(|b| *b == 0) {
+        let word = String::from_utf8_lossy(&rest[..end]).to_string();
+        let numbers = &rest[end + 1..];
+        if numbers.len() < offset_size + 4 {
+            break;
+        }
+        let offset = numbers[..offset_size].iter().fold(0usize, |n, b| n << 8 | *b as usize);
+        let size = numbers[offset_size..offset_size + 4].iter().fold(0usize, |n, b| n << 8 | *b as usize);
+        rest = &numbers[offset_size + 4..];
+        let Some(data) = dict.get(offset..offset + size) else {
+            anyhow::bail!("Entry {} points outside the .dict file", word);
+        };
+        rows.push((word, None, article_text(data, types.as_deref())));
+    }
+    Ok(rows)
+}
+
+// Writes to a temporary file first so a failed import never leaves a half written dictionary
+pub fn write_database(path: &Path, rows: &[Row]) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let temp = path.with_extension("db.tmp");
+    if temp.exists() {
+        std::fs::remove_file(&temp)?;
+    }
+    let mut connection = Connection::open(&temp)?;
+    connection.execute_batch("CREATE TABLE definitions (word TEXT NOT NULL, part_of_speech TEXT, definition TEXT);")?;
+    let transaction = connection.transaction()?;
+    {
+        let mut stmt = transaction.prepare("INSERT INTO definitions (word, part_of_speech, definition) VALUES (?1, ?2, ?3)")?;
+        for (word, part_of_speech, definition) in rows {
+            stmt.execute(params![word, part_of_speech, definition])?;
+        }
+    }
+    transaction.commit()?;
+    connection.execute_batch("CREATE INDEX definitions_word ON definitions (word COLLATE NOCASE);")?;
+    super::Dictionary::ensure_index(&connection)?;
+    drop(connection);
+    std::fs::rename(temp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_with_and_without_headers() {
+        let rows = read_csv("Word,POS,Definition\nrun,verb,To move fast.\nrun,noun,An act of running.\n", b',').unwrap();
+        assert_eq!(rows[0], ("run".to_string(), Some("verb".to_string()), "To move fast.".to_string()));
+        assert_eq!(rows.len(), 2);
+        let rows = read_csv("API\tApplication programming interface\n", b'\t').unwrap();
+        assert_eq!(rows, vec![("API".to_string(), None, "Application programming interface".to_string())]);
+    }
+
+    #[test]
+    fn test_json_shapes() {
+        let rows = read_json(r#"[{"word": "run", "pos": "verb", "definitions": ["To move fast.", "To operate."]}]"#).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].2, "To operate.");
+        let rows = read_json(r#"{"SLA": "Service level agreement"}"#).unwrap();
+        assert_eq!(rows, vec![("SLA".to_string(), None, "Service level agreement".to_string())]);
+    }
+
+    #[test]
+    fn test_stardict() {
+        let dir = std::env::temp_dir().join(format!("catalyst-stardict-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("test.ifo"), "StarDict's dict ifo file\nversion=2.4.2\nwordcount=2\nsametypesequence=h\n").unwrap();
+        std::fs::write(dir.join("test.dict"), "<b>first</b>second &amp; more").unwrap();
+        let mut index = Vec::new();
+        for (word, offset, size) in [("alpha", 0u32, 12u32), ("beta", 12, 17)] {
+            index.extend_from_slice(word.as_bytes());
+            index.push(0);
+            index.extend_from_slice(&offset.to_be_bytes());
+            index.extend_from_slice(&size.to_be_bytes());
+        }
+        std::fs::write(dir.join("test.idx"), index).unwrap();
+        let rows = read_stardict(&dir.join("test.ifo")).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(rows[0], ("alpha".to_string(), None, "first".to_string()));
+        assert_eq!(rows[1].2, "second & more");
+    }
+}
\ No newline at end of file
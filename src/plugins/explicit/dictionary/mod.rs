@@ -0,0 +1,619 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use async_trait::async_trait;
+use futures::lock::Mutex;
+use arboard::Clipboard;
+use rusqlite::{params, Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+
+use crate::core::config::data_dir;
+use crate::core::plugin::{Plugin, PluginContext, Trigger};
+use crate::core::model::{Action, BuiltInIcon, PluginView, ResultIcon, ResultItem};
+
+pub mod import;
+
+const MAX_WORDS: usize = 8;
+const MAX_SUGGESTIONS: usize = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sense {
+    pub part_of_speech: Option<String>,
+    pub definition: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub word: String,
+    pub senses: Vec<Sense>,
+}
+
+impl Entry {
+    // "noun · 1. first sense (+2 more)"
+    fn summary(&self) -> String {
+        let Some(first) = self.senses.first() else {
+            return String::new();
+        };
+        let mut summary = match &first.part_of_speech {
+            Some(pos) => format!("{} · 1. {}", pos, first.definition),
+            None => first.definition.clone(),
+        };
+        if self.senses.len() > 1 {
+            summary.push_str(&format!(" (+{} more)", self.senses.len() - 1));
+        }
+        summary
+    }
+
+    // Senses numbered per part of speech, the way printed dictionaries list them
+    fn body(&self) -> String {
+        let mut sections: Vec<(Option<&String>, Vec<&String>)> = Vec::new();
+        for sense in &self.senses {
+            match sections.iter_mut().find(|(pos, _)| *pos == sense.part_of_speech.as_ref()) {
+                Some((_, definitions)) => definitions.push(&sense.definition),
+                None => sections.push((sense.part_of_speech.as_ref(), vec![&sense.definition])),
+            }
+        }
+        sections
+            .into_iter()
+            .map(|(pos, definitions)| {
+                let numbered: Vec<String> = definitions
+                    .iter()
+                    .enumerate()
+                    .map(|(i, definition)| format!("{}. {}", i + 1, definition))
+                    .collect();
+                match pos {
+                    Some(pos) => format!("{}\n{}", pos, numbered.join("\n")),
+                    None => numbered.join("\n"),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+// Groups (word, part of speech, definition) rows by word, in the order the words first appear
+pub fn group_senses(rows: Vec<(String, Option<String>, String)>) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = Vec::new();
+    for (word, part_of_speech, definition) in rows {
+        let sense = Sense { part_of_speech: part_of_speech.filter(|p| !p.is_empty()), definition };
+        match entries.iter_mut().find(|e| e.word.eq_ignore_ascii_case(&word)) {
+            Some(entry) => {
+                if !entry.senses.contains(&sense) {
+                    entry.senses.push(sense);
+                }
+            }
+            None => entries.push(Entry { word, senses: vec![sense] }),
+        }
+    }
+    entries
+}
+
+// Edits between two words, a swap of neighbouring letters counts as one
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut rows = vec![(0..=b.len()).collect::<Vec<_>>()];
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (rows[i - 1][j] + 1).min(row[j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+        rows.push(row);
+    }
+    rows[a.len()][b.len()]
+}
+
+// Any trigram of the word, so candidates for misspellings share at least part of it
+fn trigram_query(word: &str) -> Option<String> {
+    let chars: Vec<char> = word.to_lowercase().chars().filter(|c| *c != '"').collect();
+    let mut seen = HashSet::new();
+    let trigrams: Vec<String> = chars
+        .windows(3)
+        .map(|w| w.iter().collect::<String>())
+        .filter(|t| seen.insert(t.clone()))
+        .map(|t| format!("\"{}\"", t))
+        .collect();
+    (!trigrams.is_empty()).then(|| trigrams.join(" OR "))
+}
+
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+// A dictionary database with a `definitions(word, definition)` table and optionally a
+// part of speech column, indexed with an FTS5 trigram table for substring and fuzzy lookups
+pub struct Dictionary {
+    pub name: String,
+    connection: Connection,
+    part_of_speech: Option<String>,
+    trigram: bool,
+}
+
+impl Dictionary {
+    // Opens an existing database, read-only when it can't be written (the index is skipped then)
+    pub fn open(name: &str, path: &Path) -> anyhow::Result<Self> {
+        if !path.is_file() {
+            anyhow::bail!("{} does not exist", path.display());
+        }
+        let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+            .or_else(|_| Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY))?;
+        let tables: i64 = connection.query_row(
+            "SELECT count(*) FROM sqlite_master WHERE type = 'table' AND name = 'definitions'",
+            [],
+            |row| row.get(0),
+        )?;
+        if tables == 0 {
+            anyhow::bail!("{} has no definitions table", path.display());
+        }
+        Ok(Self::new(name, connection))
+    }
+
+    pub fn new(name: &str, connection: Connection) -> Self {
+        let part_of_speech = Self::part_of_speech_column(&connection);
+        let trigram = match Self::ensure_index(&connection) {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("Dictionary trigram index unavailable, using prefix search only: {}", e);
+                false
+            }
+        };
+        Self { name: name.to_string(), connection, part_of_speech, trigram }
+    }
+
+    pub fn word_count(&self) -> i64 {
+        self.connection
+            .query_row("SELECT count(DISTINCT word) FROM definitions", [], |row| row.get(0))
+            .unwrap_or(0)
+    }
+
+    fn part_of_speech_column(connection: &Connection) -> Option<String> {
+        let mut stmt = connection.prepare("SELECT name FROM pragma_table_info('definitions')").ok()?;
+        let columns: Vec<String> = stmt.query_map([], |row| row.get(0)).ok()?.flatten().collect();
+        ["part_of_speech", "pos", "wordtype", "type"]
+            .into_iter()
+            .find(|name| columns.iter().any(|c| c.eq_ignore_ascii_case(name)))
+            .map(str::to_string)
+    }
+
+    // Builds the index once and rebuilds it whenever the definitions table changed size
+    pub(super) fn ensure_index(connection: &Connection) -> rusqlite::Result<()> {
+        connection.execute_batch(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS definitions_trigram
+             USING fts5(word, content='definitions', tokenize='trigram');",
+        )?;
+        let indexed: i64 = connection.query_row("SELECT count(*) FROM definitions_trigram_docsize", [], |row| row.get(0))?;
+        let total: i64 = connection.query_row("SELECT count(*) FROM definitions", [], |row| row.get(0))?;
+        if indexed != total {
+            log::info!("Indexing {} dictionary entries", total);
+            connection.execute("INSERT INTO definitions_trigram(definitions_trigram) VALUES('rebuild')", [])?;
+        }
+        Ok(())
+    }
+
+    fn columns(&self) -> String {
+        match &self.part_of_speech {
+            Some(column) => format!("d.word, d.{}, d.definition", column),
+            None => "d.word, NULL, d.definition".to_string(),
+        }
+    }
+
+    fn rows(&self, sql: &str, param: &str) -> rusqlite::Result<Vec<(String, Option<String>, String)>> {
+        let mut stmt = self.connection.prepare_cached(sql)?;
+        let rows = stmt.query_map(params![param], |row| {
+            Ok((row.get(0)?, row.get::<_, Option<String>>(1)?, row.get::<_, Option<String>>(2)?.unwrap_or_default()))
+        })?;
+        rows.collect()
+    }
+
+    // Words starting with the query, then (with the index) words containing it
+    pub fn search(&self, query: &str) -> rusqlite::Result<Vec<Entry>> {
+        let sql = format!(
+            "SELECT {} FROM definitions d WHERE d.word LIKE ?1 ESCAPE '\\' ORDER BY length(d.word), d.word, d.rowid LIMIT 200",
+            self.columns()
+        );
+        let mut rows = self.rows(&sql, &format!("{}%", escape_like(query)))?;
+        if self.trigram && query.chars().count() >= 3 {
+            let sql = format!(
+                "SELECT {} FROM definitions_trigram t JOIN definitions d ON d.rowid = t.rowid
+                 WHERE definitions_trigram MATCH ?1 ORDER BY length(d.word), d.word, d.rowid LIMIT 200",
+                self.columns()
+            );
+            rows.extend(self.rows(&sql, &format!("\"{}\"", query.replace('"', "\"\"")))?);
+        }
+        let mut entries = group_senses(rows);
+        entries.truncate(MAX_WORDS);
+        Ok(entries)
+    }
+
+    pub fn lookup(&self, word: &str) -> rusqlite::Result<Option<Entry>> {
+        let sql = format!("SELECT {} FROM definitions d WHERE d.word = ?1 COLLATE NOCASE ORDER BY d.rowid", self.columns());
+        Ok(group_senses(self.rows(&sql, word)?).into_iter().next())
+    }
+
+    // Closest words by edit distance, for "did you mean" suggestions
+    pub fn suggest(&self, query: &str) -> rusqlite::Result<Vec<(usize, String)>> {
+        let candidates: Vec<String> = match trigram_query(query).filter(|_| self.trigram) {
+            Some(pattern) => {
+                let mut stmt = self.connection.prepare_cached(
+                    "SELECT DISTINCT d.word FROM definitions_trigram t JOIN definitions d ON d.rowid = t.rowid
+                     WHERE definitions_trigram MATCH ?1 LIMIT 2000",
+                )?;
+                let words = stmt.query_map(params![pattern], |row| row.get(0))?;
+                words.collect::<rusqlite::Result<_>>()?
+            }
+            // without the index only words sharing the first letter are considered
+            None => {
+                let first: String = query.chars().take(1).collect();
+                let mut stmt = self.connection.prepare_cached(
+                    "SELECT DISTINCT word FROM definitions WHERE word LIKE ?1 ESCAPE '\\' LIMIT 20000",
+                )?;
+                let words = stmt.query_map(params![format!("{}%", escape_like(&first))], |row| row.get(0))?;
+                words.collect::<rusqlite::Result<_>>()?
+            }
+        };
+        let max_distance = if query.chars().count() <= 4 { 1 } else { 2 };
+        let mut scored: Vec<(usize, String)> = candidates
+            .into_iter()
+            .map(|word| (edit_distance(query, &word), word))
+            .filter(|(distance, _)| *distance <= max_distance)
+            .collect();
+        scored.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.len().abs_diff(query.len()).cmp(&b.1.len().abs_diff(query.len()))));
+        let mut seen = HashSet::new();
+        Ok(scored
+            .into_iter()
+            .filter(|(_, word)| seen.insert(word.to_lowercase()))
+            .take(MAX_SUGGESTIONS)
+            .collect())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DictionarySource {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+// Where `def import` puts converted dictionaries, each of them is searched automatically
+fn imported_dir() -> PathBuf {
+    data_dir().join("dictionaries")
+}
+
+// "import ~/glossary.csv as Glossary" -> (path, name)
+fn parse_import(args: &str) -> (PathBuf, String) {
+    let (path, name) = match args.rsplit_once(" as ") {
+        Some((path, name)) if !name.trim().is_empty() => (path.trim(), Some(name.trim().to_string())),
+        _ => (args.trim(), None),
+    };
+    let path = match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().map(|home| home.join(rest)).unwrap_or_else(|| PathBuf::from(path)),
+        None => PathBuf::from(path.trim_matches('"')),
+    };
+    let name = name.unwrap_or_else(|| path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default());
+    (path, name)
+}
+
+// Keeps names usable as file names
+fn file_name(name: &str) -> String {
+    name.chars().map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' { c } else { '_' }).collect()
+}
+
+pub struct DictionaryPlugin {
+    // the sources the open dictionaries were loaded from, to reopen them when the list changes
+    dictionaries: Mutex<(Vec<DictionarySource>, Vec<Dictionary>)>,
+}
+
+impl DictionaryPlugin {
+    pub fn new() -> Self {
+        Self {
+            dictionaries: Mutex::new((Vec::new(), Vec::new())),
+        }
+    }
+
+    // `[plugins.dictionary] dictionaries` (the bundled English one by default) plus imported ones
+    fn sources(&self, context: &PluginContext) -> Vec<DictionarySource> {
+        let mut sources: Vec<DictionarySource> = context.get_setting(self.id(), "dictionaries").unwrap_or_else(|| {
+            vec![DictionarySource { name: "English".to_string(), path: PathBuf::from("assets/dictionary.db") }]
+        });
+        let mut imported: Vec<PathBuf> = std::fs::read_dir(imported_dir())
+            .map(|entries| entries.flatten().map(|e| e.path()).filter(|p| p.extension().is_some_and(|e| e == "db")).collect())
+            .unwrap_or_default();
+        imported.sort();
+        for path in imported {
+            if sources.iter().all(|s| s.path != path) {
+                let name = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+                sources.push(DictionarySource { name, path });
+            }
+        }
+        sources
+    }
+
+    async fn dictionaries(&self, context: &PluginContext) -> futures::lock::MutexGuard<'_, (Vec<DictionarySource>, Vec<Dictionary>)> {
+        let sources = self.sources(context);
+        let mut loaded = self.dictionaries.lock().await;
+        if loaded.0 != sources {
+            let dictionaries = sources
+                .iter()
+                .filter_map(|source| match Dictionary::open(&source.name, &source.path) {
+                    Ok(dictionary) => Some(dictionary),
+                    Err(e) => {
+                        log::warn!("Skipping dictionary {}: {}", source.name, e);
+                        None
+                    }
+                })
+                .collect();
+            *loaded = (sources, dictionaries);
+        }
+        loaded
+    }
+
+    fn entry_result(&self, dictionary: &Dictionary, entry: &Entry, show_name: bool, score: f32) -> ResultItem {
+        let subtitle = if show_name { format!("{} · {}", dictionary.name, entry.summary()) } else { entry.summary() };
+        ResultItem::new(
+            format!("dictionary {} {}", dictionary.name, entry.word),
+            entry.word.clone(),
+            Action::ShowPluginView {
+                plugin_id: self.id().to_string(),
+                view_id: "entry".to_string(),
+                data: serde_json::json!({ "dictionary": dictionary.name, "word": entry.word }),
+            },
+            self.id().to_string()
+        )
+        .with_subtitle(subtitle)
+        .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Dictionary))
+        .with_score(score)
+    }
+
+    fn import_results(&self, args: &str) -> Vec<ResultItem> {
+        let (path, name) = parse_import(args);
+        let (title, subtitle, action) = if !path.is_file() {
+            (format!("File not found: {}", path.display()), "Give the path of a .csv, .tsv, .json or StarDict .ifo file".to_string(), Action::None)
+        } else if !import::is_supported(&path) {
+            (format!("Can't import {}", path.display()), "Supported formats are .csv, .tsv, .json and StarDict .ifo".to_string(), Action::None)
+        } else {
+            (
+                format!("Import {} as “{}”", path.display(), name),
+                "Press Enter to add it to your dictionaries".to_string(),
+                Action::Custom {
+                    action_type: "dictionary.import".to_string(),
+                    data: serde_json::json!({ "path": path, "name": name }),
+                },
+            )
+        };
+        vec![
+            ResultItem::new("dictionary import", title, action, self.id().to_string())
+                .with_subtitle(subtitle)
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Dictionary))
+                .with_score(100.0)
+        ]
+    }
+
+    fn manage_results(&self, sources: &[DictionarySource], dictionaries: &[Dictionary], remove: Option<&str>) -> Vec<ResultItem> {
+        let mut results = Vec::new();
+        for source in sources {
+            let imported = source.path.starts_with(imported_dir());
+            let dictionary = dictionaries.iter().find(|d| d.name == source.name);
+            let status = match dictionary {
+                Some(dictionary) => format!("{} words · {}", dictionary.word_count(), source.path.display()),
+                None => format!("Unavailable · {}", source.path.display()),
+            };
+            let (title, action) = match remove {
+                Some(filter) if imported && source.name.to_lowercase().contains(&filter.to_lowercase()) => (
+                    format!("Remove {}", source.name),
+                    Action::Custom {
+                        action_type: "dictionary.remove".to_string(),
+                        data: serde_json::json!({ "path": source.path }),
+                    },
+                ),
+                Some(_) => continue,
+                None => (source.name.clone(), Action::None),
+            };
+            results.push(
+                ResultItem::new(format!("dictionary source {}", source.path.display()), title, action, self.id().to_string())
+                    .with_subtitle(status)
+                    .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Dictionary))
+                    .with_score(90.0)
+            );
+        }
+        if results.is_empty() {
+            let subtitle = if remove.is_some() { "Only imported dictionaries can be removed" } else { "Import one with def import <file>" };
+            results.push(
+                ResultItem::new("dictionary sources empty", "No dictionaries", Action::None, self.id().to_string())
+                    .with_subtitle(subtitle)
+                    .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Dictionary))
+            );
+        }
+        results
+    }
+}
+
+#[async_trait]
+impl Plugin for DictionaryPlugin {
+    fn id(&self) -> &str {
+        "dictionary"
+    }
+    fn name(&self) -> &str {
+        "Dictionary Plugin"
+    }
+    fn description(&self) -> &str {
+        "A plugin for getting dictionary definitions."
+    }
+    fn trigger(&self) -> Trigger {
+        Trigger::Explicit { keyword: "def".to_string() }
+    }
+    async fn search(&self, query: &str, context: &PluginContext) -> Vec<ResultItem> {
+        let mut results = Vec::new();
+        let query = query.trim();
+        if query.is_empty() {
+            return results;
+        }
+        if let Some(args) = query.strip_prefix("import ") {
+            return self.import_results(args);
+        }
+        let loaded = self.dictionaries(context).await;
+        let (sources, dictionaries) = &*loaded;
+        if query == "dictionaries" {
+            return self.manage_results(sources, dictionaries, None);
+        }
+        if let Some(name) = query.strip_prefix("remove ") {
+            return self.manage_results(sources, dictionaries, Some(name.trim()));
+        }
+        if dictionaries.is_empty() {
+            results.push(
+                ResultItem::new("dictionary missing", "No dictionary available", Action::None, self.id().to_string())
+                    .with_subtitle("Import one with def import <file> or set [plugins.dictionary] dictionaries")
+                    .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Dictionary))
+            );
+            return results;
+        }
+
+        let show_name = dictionaries.len() > 1;
+        let mut exact = false;
+        let mut found = false;
+        for dictionary in dictionaries {
+            let entries = dictionary.search(query).unwrap_or_else(|e| {
+                log::warn!("Dictionary search in {} failed: {}", dictionary.name, e);
+                Vec::new()
+            });
+            for (i, entry) in entries.iter().enumerate() {
+                let is_exact = entry.word.eq_ignore_ascii_case(query);
+                exact |= is_exact;
+                found = true;
+                let score = if is_exact { 100.0 } else { 90.0 - i as f32 };
+                results.push(self.entry_result(dictionary, entry, show_name, score));
+            }
+        }
+        if exact {
+            return results;
+        }
+        let mut suggestions: Vec<(usize, String, &Dictionary)> = dictionaries
+            .iter()
+            .flat_map(|dictionary| {
+                dictionary
+                    .suggest(query)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(move |(distance, word)| (distance, word, dictionary))
+            })
+            .collect();
+        suggestions.sort_by_key(|(distance, _, _)| *distance);
+        let mut seen = HashSet::new();
+        suggestions.retain(|(_, word, _)| seen.insert(word.to_lowercase()));
+        for (i, (_, word, dictionary)) in suggestions.into_iter().take(MAX_SUGGESTIONS).enumerate() {
+            let subtitle = dictionary
+                .lookup(&word)
+                .ok()
+                .flatten()
+                .map(|entry| entry.summary())
+                .unwrap_or_default();
+            results.push(
+                ResultItem::new(
+                    format!("dictionary suggestion {}", word),
+                    format!("Did you mean “{}”?", word),
+                    Action::InsertText(format!("def {}", word)),
+                    self.id().to_string()
+                )
+                .with_subtitle(subtitle)
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Dictionary))
+                // above loose substring matches when nothing starts with the query
+                .with_score(if found { 60.0 - i as f32 } else { 95.0 - i as f32 })
+            );
+        }
+        results
+    }
+    async fn execute(&self, action: &Action, _context: &PluginContext) -> anyhow::Result<()> {
+        match action {
+            Action::CopyToClipboard(text) => {
+                let mut clipboard = Clipboard::new()?;
+                clipboard.set_text(text)?;
+                println!("Copied to clipboard: {}", text);
+            }
+            Action::Custom { action_type, data } if action_type == "dictionary.import" => {
+                let path = PathBuf::from(data["path"].as_str().unwrap_or_default());
+                let name = data["name"].as_str().unwrap_or("Imported");
+                let destination = imported_dir().join(format!("{}.db", file_name(name)));
+                let count = import::import(&path, &destination)?;
+                println!("Imported {} entries into {}", count, name);
+            }
+            Action::Custom { action_type, data } if action_type == "dictionary.remove" => {
+                let path = PathBuf::from(data["path"].as_str().unwrap_or_default());
+                // only files we imported ourselves are ever deleted
+                if !path.starts_with(imported_dir()) {
+                    anyhow::bail!("{} is not an imported dictionary", path.display());
+                }
+                // close the open connections so the file can be removed on Windows, the next search reopens them
+                *self.dictionaries.lock().await = (Vec::new(), Vec::new());
+                std::fs::remove_file(&path)?;
+                println!("Removed dictionary {}", path.display());
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+    async fn view(&self, action: &Action, context: &PluginContext) -> Option<PluginView> {
+        let Action::ShowPluginView { data, .. } = action else {
+            return None;
+        };
+        let word = data["word"].as_str()?;
+        let name = data["dictionary"].as_str().unwrap_or_default();
+        let loaded = self.dictionaries(context).await;
+        let dictionary = loaded.1.iter().find(|d| d.name == name).or_else(|| loaded.1.first())?;
+        let entry = dictionary.lookup(word).ok()??;
+        let body = entry.body();
+        let actions = vec![
+            ResultItem::new("dictionary copy definition", "Copy definition", Action::CopyToClipboard(body.clone()), self.id().to_string())
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Dictionary)),
+            ResultItem::new("dictionary copy word", "Copy word", Action::CopyToClipboard(entry.word.clone()), self.id().to_string())
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Dictionary)),
+        ];
+        Some(PluginView::detail(entry.word.clone(), body, actions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictionary() -> Dictionary {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE definitions (word TEXT, wordtype TEXT, definition TEXT);
+                 INSERT INTO definitions VALUES
+                    ('Receive', 'v.', 'To take into possession.'),
+                    ('Receive', 'v.', 'To admit; to welcome.'),
+                    ('Receiver', 'n.', 'One who receives.'),
+                    ('Deceive', 'v.', 'To mislead.'),
+                    ('Perceive', 'v.', 'To become aware of.');",
+            )
+            .unwrap();
+        Dictionary::new("Test", connection)
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("recieve", "receive"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("Word", "word"), 0);
+    }
+
+    #[test]
+    fn test_senses_are_grouped() {
+        let dictionary = dictionary();
+        let entries = dictionary.search("receiv").unwrap();
+        assert_eq!(entries.iter().map(|e| e.word.as_str()).collect::<Vec<_>>(), ["Receive", "Receiver"]);
+        assert_eq!(entries[0].senses.len(), 2);
+        assert_eq!(entries[0].body(), "v.\n1. To take into possession.\n2. To admit; to welcome.");
+        assert_eq!(dictionary.search("ceiver").unwrap()[0].word, "Receiver");
+    }
+
+    #[test]
+    fn test_did_you_mean() {
+        let dictionary = dictionary();
+        assert!(dictionary.search("recieve").unwrap().is_empty());
+        assert_eq!(dictionary.suggest("recieve").unwrap()[0], (1, "Receive".to_string()));
+        assert!(dictionary.suggest("xyzzy").unwrap().is_empty());
+    }
+}
\ No newline at end of file
@@ -1,20 +1,126 @@
-use std::result;
-use std::sync::Arc;
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use async_trait::async_trait;
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
-use local_ip_address::local_ip;
-use public_ip::addr;
 use arboard::Clipboard;
 
-use crate::core::plugin::{Plugin, PluginContext, Trigger};
+use crate::core::plugin::{Plugin, PluginContext, SettingField, Trigger};
 use crate::core::model::{Action, BuiltInIcon, ResultIcon, ResultItem};
+use crate::platform::{network, NetworkInterface};
 
-pub struct IpPlugin {}
+// Looked up again after this long, failures sooner so a reconnect shows up quickly
+const PUBLIC_IP_TTL: Duration = Duration::from_secs(300);
+const PUBLIC_IP_RETRY: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Family {
+    V4,
+    V6,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Address,
+    Mac,
+    Gateway,
+    Public,
+}
+
+// What "ip v6 eth0" asks for; anything that isn't a family or kind filters by interface name
+#[derive(Debug, Default, PartialEq)]
+struct Filter {
+    family: Option<Family>,
+    kinds: Vec<Kind>,
+    names: Vec<String>,
+}
+
+impl Filter {
+    fn parse(query: &str) -> Self {
+        let mut filter = Filter::default();
+        for word in query.split_whitespace().map(str::to_lowercase) {
+            match word.as_str() {
+                "4" | "v4" | "ipv4" => filter.family = Some(Family::V4),
+                "6" | "v6" | "ipv6" => filter.family = Some(Family::V6),
+                "mac" | "ether" | "hw" => filter.kinds.push(Kind::Mac),
+                "gw" | "gateway" | "router" => filter.kinds.push(Kind::Gateway),
+                "public" | "external" | "wan" => filter.kinds.push(Kind::Public),
+                "local" | "private" | "lan" => filter.kinds.push(Kind::Address),
+                _ => filter.names.push(word),
+            }
+        }
+        filter
+    }
+    fn wants(&self, kind: Kind) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(&kind)
+    }
+    fn wants_address(&self, address: &IpAddr) -> bool {
+        match self.family {
+            Some(Family::V4) => address.is_ipv4(),
+            Some(Family::V6) => address.is_ipv6(),
+            None => true,
+        }
+    }
+    // Loopback interfaces only show up when asked for by name
+    fn wants_interface(&self, interface: &NetworkInterface, matcher: &SkimMatcherV2) -> bool {
+        if self.names.is_empty() {
+            return !interface.addresses.iter().all(|a| a.is_loopback());
+        }
+        let description = interface.description.as_deref().unwrap_or_default();
+        self.names.iter().all(|name| {
+            matcher.fuzzy_match(&interface.name, name).is_some() || matcher.fuzzy_match(description, name).is_some()
+        })
+    }
+}
+
+fn family_name(address: &IpAddr) -> &'static str {
+    if address.is_ipv4() { "IPv4" } else { "IPv6" }
+}
+
+type PublicIp = Option<(Instant, Result<IpAddr, String>)>;
+
+pub struct IpPlugin {
+    public_ip: Arc<Mutex<PublicIp>>,
+    looking_up: Arc<AtomicBool>,
+}
 
 impl IpPlugin {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            public_ip: Arc::new(Mutex::new(None)),
+            looking_up: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    // The last known answer, None before the first lookup finishes. When it's missing or old
+    // a lookup starts in the background, so typing after `ip` never waits on the network.
+    fn public_ip(&self, timeout: Duration) -> Option<Result<IpAddr, String>> {
+        let cached = self.public_ip.lock().unwrap().clone();
+        let fresh = cached.as_ref().is_some_and(|(fetched, result)| {
+            fetched.elapsed() < if result.is_ok() { PUBLIC_IP_TTL } else { PUBLIC_IP_RETRY }
+        });
+        if !fresh && !self.looking_up.swap(true, Ordering::SeqCst) {
+            let (public_ip, looking_up) = (self.public_ip.clone(), self.looking_up.clone());
+            tokio::spawn(async move {
+                let result = match tokio::time::timeout(timeout, public_ip::addr()).await {
+                    Ok(Some(address)) => Ok(address),
+                    Ok(None) => Err("No lookup service answered, check the connection".to_string()),
+                    Err(_) => Err(format!("No answer within {}s", timeout.as_secs())),
+                };
+                *public_ip.lock().unwrap() = Some((Instant::now(), result));
+                looking_up.store(false, Ordering::SeqCst);
+            });
+        }
+        cached.map(|(_, result)| result)
+    }
+
+    fn copy_result(&self, id: String, title: String, subtitle: String, score: f32) -> ResultItem {
+        ResultItem::new(id, title.clone(), Action::CopyToClipboard(title), self.id().to_string())
+            .with_subtitle(subtitle)
+            .with_icon(ResultIcon::BuiltIn(BuiltInIcon::IP))
+            .with_score(score)
     }
 }
 
@@ -32,40 +138,129 @@ impl Plugin for IpPlugin {
     fn trigger(&self) -> Trigger {
         Trigger::Explicit { keyword: "ip".to_string() }
     }
-    async fn search(&self, query: &str, _context: &PluginContext) -> Vec<ResultItem> {
-        let private_ip = local_ip().unwrap();
-        let public_ip = addr().await.unwrap();
+    fn settings_schema(&self) -> Vec<SettingField> {
+        vec![
+            SettingField::toggle("public_ip", "Look up the public IP address", true),
+            SettingField::number("public_ip_timeout", "Seconds to wait for the public IP lookup", 3),
+        ]
+    }
+    async fn search(&self, query: &str, context: &PluginContext) -> Vec<ResultItem> {
         let mut results = Vec::new();
-        results.push(
-            ResultItem::new(
-                "public ip",
-                public_ip.to_string(),
-                Action::CopyToClipboard(public_ip.to_string()),
-                self.id().to_string()
-            )
-            .with_subtitle("Public IP")
-            .with_icon(ResultIcon::BuiltIn(BuiltInIcon::IP))
-            .with_score(100.0)
-        );
-        results.push(
-            ResultItem::new(
-                "private ip",
-                private_ip.to_string(),
-                Action::CopyToClipboard(private_ip.to_string()),
-                self.id().to_string()
-            )
-            .with_subtitle("Private IP")
-            .with_icon(ResultIcon::BuiltIn(BuiltInIcon::IP))
-            .with_score(100.0)
-        );
+        let filter = Filter::parse(query);
+        let matcher = SkimMatcherV2::default().ignore_case();
+
+        match network::interfaces() {
+            Ok(interfaces) => {
+                let mut score = 90.0;
+                for interface in interfaces.iter().filter(|i| filter.wants_interface(i, &matcher)) {
+                    let mut rows = Vec::new();
+                    if filter.wants(Kind::Address) {
+                        for address in interface.addresses.iter().filter(|a| filter.wants_address(a)) {
+                            rows.push((format!("ip {} {}", interface.name, address), address.to_string(), format!("{} · {}", interface.name, family_name(address))));
+                        }
+                    }
+                    if filter.wants(Kind::Gateway) {
+                        for gateway in interface.gateways.iter().filter(|g| filter.wants_address(g)) {
+                            rows.push((format!("ip {} gateway {}", interface.name, gateway), gateway.to_string(), format!("{} · Gateway", interface.name)));
+                        }
+                    }
+                    if let Some(mac) = interface.mac.as_ref().filter(|_| filter.wants(Kind::Mac)) {
+                        rows.push((format!("ip {} mac", interface.name), mac.clone(), format!("{} · MAC address", interface.name)));
+                    }
+                    for (id, title, subtitle) in rows {
+                        results.push(self.copy_result(id, title, subtitle, score));
+                        score -= 0.1;
+                    }
+                }
+            }
+            Err(e) => {
+                log::warn!("Failed to list network interfaces: {}", e);
+                results.push(
+                    ResultItem::new("ip interfaces error", "Network interfaces unavailable", Action::None, self.id().to_string())
+                        .with_subtitle(e.to_string())
+                        .with_icon(ResultIcon::BuiltIn(BuiltInIcon::IP))
+                        .with_score(90.0)
+                );
+            }
+        }
+
+        let lookup = context.get_setting::<bool>(self.id(), "public_ip").unwrap_or(true);
+        if lookup && filter.wants(Kind::Public) && filter.names.is_empty() {
+            let timeout = context.get_setting::<u64>(self.id(), "public_ip_timeout").unwrap_or(3).max(1);
+            match self.public_ip(Duration::from_secs(timeout)) {
+                Some(Ok(address)) if filter.wants_address(&address) => results.push(self.copy_result(
+                    "ip public".to_string(),
+                    address.to_string(),
+                    format!("Public {}", family_name(&address)),
+                    100.0,
+                )),
+                Some(Ok(_)) => {}
+                Some(Err(e)) => results.push(
+                    ResultItem::new("ip public error", "Public IP unavailable", Action::None, self.id().to_string())
+                        .with_subtitle(e)
+                        .with_icon(ResultIcon::BuiltIn(BuiltInIcon::IP))
+                        .with_score(100.0)
+                ),
+                None => results.push(
+                    ResultItem::new("ip public pending", "Looking up the public IP…", Action::None, self.id().to_string())
+                        .with_subtitle("It shows up here on the next search")
+                        .with_icon(ResultIcon::BuiltIn(BuiltInIcon::IP))
+                        .with_score(100.0)
+                ),
+            }
+        }
+
+        if results.is_empty() {
+            results.push(
+                ResultItem::new("ip none", "No matching addresses", Action::None, self.id().to_string())
+                    .with_subtitle("Try `ip v4`, `ip v6`, `ip mac`, `ip gateway` or an interface name")
+                    .with_icon(ResultIcon::BuiltIn(BuiltInIcon::IP))
+                    .with_score(50.0)
+            );
+        }
         results
     }
     async fn execute(&self, action: &Action, _context: &PluginContext) -> anyhow::Result<()> {
-        let mut clipboard = Clipboard::new()?;
         if let Action::CopyToClipboard(ip) = action {
-            let _ = clipboard.set_text(ip)?;
+            let mut clipboard = Clipboard::new()?;
+            clipboard.set_text(ip)?;
             println!("Ip address copied to clipboard: {}", ip);
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        let filter = Filter::parse("v6 eth0");
+        assert_eq!(filter.family, Some(Family::V6));
+        assert_eq!(filter.names, ["eth0"]);
+        assert!(filter.wants(Kind::Address));
+        assert!(!filter.wants_address(&"10.0.0.1".parse().unwrap()));
+
+        let matcher = SkimMatcherV2::default().ignore_case();
+        let eth0 = NetworkInterface { name: "eth0".to_string(), addresses: vec!["10.0.0.1".parse().unwrap()], ..Default::default() };
+        let lo = NetworkInterface { name: "lo".to_string(), addresses: vec!["127.0.0.1".parse().unwrap()], ..Default::default() };
+        assert!(filter.wants_interface(&eth0, &matcher) && !filter.wants_interface(&lo, &matcher));
+        let all = Filter::parse("");
+        assert!(all.wants_interface(&eth0, &matcher) && !all.wants_interface(&lo, &matcher));
+        assert!(Filter::parse("lo").wants_interface(&lo, &matcher));
+
+        let gateways = Filter::parse("gateway 4");
+        assert!(gateways.wants(Kind::Gateway) && !gateways.wants(Kind::Mac) && !gateways.wants(Kind::Public));
+    }
+
+    #[test]
+    fn test_public_ip_comes_from_the_cache() {
+        let plugin = IpPlugin::new();
+        let address: IpAddr = "203.0.113.7".parse().unwrap();
+        *plugin.public_ip.lock().unwrap() = Some((Instant::now(), Ok(address)));
+        // a fresh answer is returned without starting a lookup, which would need a runtime
+        assert_eq!(plugin.public_ip(Duration::from_secs(1)), Some(Ok(address)));
+        assert!(!plugin.looking_up.load(Ordering::SeqCst));
+    }
+}
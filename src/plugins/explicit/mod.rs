@@ -1,11 +1,19 @@
 pub mod testing_explicit;
 pub mod ip;
+pub mod network_tools;
 pub mod dictionary;
 pub mod clipboard;
 pub mod terminal;
+pub mod settings;
+pub mod window_switcher;
+pub mod process_manager;
 
 use testing_explicit::*;
 use ip::*;
+use network_tools::*;
 use dictionary::*;
 use clipboard::*;
-use terminal::*;
\ No newline at end of file
+use terminal::*;
+use settings::*;
+use window_switcher::*;
+use process_manager::*;
\ No newline at end of file
@@ -0,0 +1,100 @@
+// Subnet calculator for "10.0.0.0/22" and "2001:db8::/48"
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Subnet {
+    // the address as typed, which may be a host inside the network
+    pub address: IpAddr,
+    pub prefix: u8,
+}
+
+impl Subnet {
+    pub fn parse(text: &str) -> Option<Self> {
+        let (address, prefix) = text.trim().split_once('/')?;
+        let address: IpAddr = address.parse().ok()?;
+        let prefix: u8 = prefix.parse().ok()?;
+        let bits = if address.is_ipv4() { 32 } else { 128 };
+        (prefix <= bits).then_some(Self { address, prefix })
+    }
+
+    // (label, value) rows, network first
+    pub fn describe(&self) -> Vec<(&'static str, String)> {
+        match self.address {
+            IpAddr::V4(address) => self.describe_v4(address),
+            IpAddr::V6(address) => self.describe_v6(address),
+        }
+    }
+
+    fn describe_v4(&self, address: Ipv4Addr) -> Vec<(&'static str, String)> {
+        let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or(0);
+        let network = u32::from(address) & mask;
+        let broadcast = network | !mask;
+        // /31 links and /32 hosts have no network and broadcast addresses to leave out
+        let (first, last, hosts) = match self.prefix {
+            32 => (network, network, 1u64),
+            31 => (network, broadcast, 2),
+            _ => (network + 1, broadcast - 1, (1u64 << (32 - self.prefix)) - 2),
+        };
+        let mut rows = vec![("Network", format!("{}/{}", Ipv4Addr::from(network), self.prefix))];
+        if u32::from(address) != network {
+            rows.push(("Address", address.to_string()));
+        }
+        rows.extend([
+            ("Host range", format!("{} – {}", Ipv4Addr::from(first), Ipv4Addr::from(last))),
+            ("Broadcast", Ipv4Addr::from(broadcast).to_string()),
+            ("Usable hosts", hosts.to_string()),
+            ("Netmask", Ipv4Addr::from(mask).to_string()),
+            ("Wildcard mask", Ipv4Addr::from(!mask).to_string()),
+        ]);
+        rows
+    }
+
+    fn describe_v6(&self, address: Ipv6Addr) -> Vec<(&'static str, String)> {
+        let mask = u128::MAX.checked_shl(128 - self.prefix as u32).unwrap_or(0);
+        let network = u128::from(address) & mask;
+        let last = network | !mask;
+        let count = match 128 - self.prefix as u32 {
+            128 => "2^128".to_string(),
+            bits if bits > 64 => format!("2^{}", bits),
+            bits => (1u128 << bits).to_string(),
+        };
+        let mut rows = vec![("Network", format!("{}/{}", Ipv6Addr::from(network), self.prefix))];
+        if u128::from(address) != network {
+            rows.push(("Address", address.to_string()));
+        }
+        rows.extend([
+            ("Range", format!("{} – {}", Ipv6Addr::from(network), Ipv6Addr::from(last))),
+            ("Addresses", count),
+        ]);
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(rows: &[(&str, String)], label: &str) -> String {
+        rows.iter().find(|(l, _)| *l == label).map(|(_, v)| v.clone()).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_subnets() {
+        let rows = Subnet::parse("10.0.1.7/22").unwrap().describe();
+        assert_eq!(value(&rows, "Network"), "10.0.0.0/22");
+        assert_eq!(value(&rows, "Address"), "10.0.1.7");
+        assert_eq!(value(&rows, "Host range"), "10.0.0.1 – 10.0.3.254");
+        assert_eq!(value(&rows, "Broadcast"), "10.0.3.255");
+        assert_eq!(value(&rows, "Usable hosts"), "1022");
+        assert_eq!(value(&rows, "Netmask"), "255.255.252.0");
+
+        assert_eq!(value(&Subnet::parse("192.168.1.0/31").unwrap().describe(), "Usable hosts"), "2");
+        assert_eq!(value(&Subnet::parse("0.0.0.0/0").unwrap().describe(), "Broadcast"), "255.255.255.255");
+        assert_eq!(Subnet::parse("10.0.0.0/33"), None);
+
+        let rows = Subnet::parse("2001:db8::1/64").unwrap().describe();
+        assert_eq!(value(&rows, "Network"), "2001:db8::/64");
+        assert_eq!(value(&rows, "Range"), "2001:db8:: – 2001:db8::ffff:ffff:ffff:ffff");
+        assert_eq!(value(&rows, "Addresses"), "18446744073709551616");
+    }
+}
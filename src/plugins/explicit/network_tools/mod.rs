@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use async_trait::async_trait;
+use arboard::Clipboard;
+use dns_lookup::AddrInfoHints;
+
+use crate::core::plugin::{Plugin, PluginContext, Trigger};
+use crate::core::model::{Action, BuiltInIcon, ResultIcon, ResultItem};
+use crate::platform::{network, process, Listener};
+
+pub mod cidr;
+
+use cidr::Subnet;
+
+// `port 8080`, `dns example.com` and bare subnets like `10.0.0.0/22`. Lookups wait for a dot
+// in the name, so typing "dns exa" doesn't ask the resolver about every prefix.
+const PATTERN: &str = r"(?i)^(port\s+\d{1,5}|dns\s+\S+\.\S+|\d{1,3}(\.\d{1,3}){3}/\d{1,2}|[0-9a-f]*:[0-9a-f:.]*/\d{1,3})$";
+const DNS_TIMEOUT: Duration = Duration::from_secs(5);
+// answers are reused while the name is edited, failures sooner so a fixed network shows up
+const DNS_TTL: Duration = Duration::from_secs(60);
+const DNS_RETRY: Duration = Duration::from_secs(10);
+// getaddrinfo values, the same on Linux and Windows
+const SOCK_STREAM: i32 = 1;
+const AI_CANONNAME: i32 = 2;
+
+// A/AAAA records and the canonical name at the end of any CNAME chain, from the system resolver
+fn resolve(host: &str) -> anyhow::Result<(Vec<IpAddr>, Option<String>)> {
+    let hints = AddrInfoHints { socktype: SOCK_STREAM, protocol: 0, address: 0, flags: AI_CANONNAME };
+    let mut addresses = Vec::new();
+    let mut canonical = None;
+    for info in dns_lookup::getaddrinfo(Some(host), None, Some(hints)).map_err(std::io::Error::from)? {
+        let info = info?;
+        canonical = canonical.or(info.canonname);
+        if !addresses.contains(&info.sockaddr.ip()) {
+            addresses.push(info.sockaddr.ip());
+        }
+    }
+    let canonical = canonical.filter(|name| !name.trim_end_matches('.').eq_ignore_ascii_case(host.trim_end_matches('.')));
+    Ok((addresses, canonical))
+}
+
+type Resolved = Result<(Vec<IpAddr>, Option<String>), String>;
+
+pub struct NetworkToolsPlugin {
+    answers: Mutex<HashMap<String, (Instant, Resolved)>>,
+}
+
+impl NetworkToolsPlugin {
+    pub fn new() -> Self {
+        Self {
+            answers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Cached per host, backspacing over a name doesn't look it up again
+    async fn lookup(&self, host: &str) -> Resolved {
+        let key = host.to_lowercase();
+        if let Some((resolved_at, result)) = self.answers.lock().unwrap().get(&key) {
+            if resolved_at.elapsed() < if result.is_ok() { DNS_TTL } else { DNS_RETRY } {
+                return result.clone();
+            }
+        }
+        let name = host.to_string();
+        let result = match tokio::time::timeout(DNS_TIMEOUT, tokio::task::spawn_blocking(move || resolve(&name))).await {
+            Ok(joined) => joined.map_err(anyhow::Error::from).and_then(|result| result).map_err(|e| e.to_string()),
+            Err(_) => Err(format!("No answer within {}s", DNS_TIMEOUT.as_secs())),
+        };
+        let mut answers = self.answers.lock().unwrap();
+        answers.retain(|_, (resolved_at, _)| resolved_at.elapsed() < DNS_TTL);
+        answers.insert(key, (Instant::now(), result.clone()));
+        result
+    }
+
+    fn row(&self, id: String, title: String, subtitle: String, action: Action, score: f32) -> ResultItem {
+        ResultItem::new(id, title, action, self.id().to_string())
+            .with_subtitle(subtitle)
+            .with_icon(ResultIcon::BuiltIn(BuiltInIcon::IP))
+            .with_score(score)
+    }
+
+    fn copy_row(&self, id: String, value: String, subtitle: String, score: f32) -> ResultItem {
+        self.row(id, value.clone(), subtitle, Action::CopyToClipboard(value), score)
+    }
+
+    async fn port_results(&self, port: u16) -> Vec<ResultItem> {
+        let listeners = tokio::task::spawn_blocking(move || network::listeners(port)).await;
+        let listeners: Vec<Listener> = match listeners.map_err(anyhow::Error::from).and_then(|result| result) {
+            Ok(listeners) => listeners,
+            Err(e) => {
+                return vec![self.row(format!("port {}", port), format!("Can't list sockets on port {}", port), e.to_string(), Action::None, 100.0)];
+            }
+        };
+        if listeners.is_empty() {
+            return vec![self.row(format!("port {}", port), format!("Nothing is listening on port {}", port), "No TCP listener or bound UDP socket".to_string(), Action::None, 100.0)];
+        }
+        // one row per process, with all of its sockets on the port
+        let mut owners: Vec<(Option<u32>, Vec<&Listener>)> = Vec::new();
+        for listener in &listeners {
+            match owners.iter_mut().find(|(pid, _)| *pid == listener.pid) {
+                Some((_, sockets)) => sockets.push(listener),
+                None => owners.push((listener.pid, vec![listener])),
+            }
+        }
+        owners
+            .into_iter()
+            .enumerate()
+            .map(|(i, (pid, sockets))| {
+                let sockets: Vec<String> = sockets.iter().map(|s| format!("{} {}", s.protocol, s.address)).collect();
+                let score = 100.0 - i as f32;
+                match pid {
+                    Some(pid) => {
+                        let name = process::name(pid).unwrap_or_else(|| "Unknown process".to_string());
+                        let action = Action::Custom {
+                            action_type: "network_tools.kill".to_string(),
+                            data: serde_json::json!({ "pid": pid }),
+                        }
+                        .confirm(format!("Stop {} (PID {})?", name, pid));
+                        self.row(format!("port {} {}", port, pid), format!("{} (PID {})", name, pid), format!("{} · Enter to stop it", sockets.join(", ")), action, score)
+                    }
+                    None => self.row(format!("port {} unknown {}", port, i), "Process of another user".to_string(), sockets.join(", "), Action::None, score),
+                }
+            })
+            .collect()
+    }
+
+    async fn dns_results(&self, host: &str) -> Vec<ResultItem> {
+        let (addresses, canonical) = match self.lookup(host).await {
+            Ok(result) => result,
+            Err(e) => return vec![self.row(format!("dns {}", host), format!("Can't resolve {}", host), e, Action::None, 100.0)],
+        };
+        let mut results = Vec::new();
+        if let Some(canonical) = canonical {
+            results.push(self.copy_row(format!("dns {} cname", host), canonical, format!("CNAME · {} is an alias", host), 100.0));
+        }
+        for (i, address) in addresses.into_iter().enumerate() {
+            let record = if address.is_ipv4() { "A" } else { "AAAA" };
+            results.push(self.copy_row(format!("dns {} {}", host, address), address.to_string(), format!("{} · {}", record, host), 99.0 - i as f32));
+        }
+        if results.is_empty() {
+            results.push(self.row(format!("dns {}", host), format!("No addresses for {}", host), "The resolver returned no A or AAAA records".to_string(), Action::None, 100.0));
+        }
+        results
+    }
+
+    fn subnet_results(&self, subnet: &Subnet) -> Vec<ResultItem> {
+        subnet
+            .describe()
+            .into_iter()
+            .enumerate()
+            .map(|(i, (label, value))| self.copy_row(format!("cidr {}", label), value, label.to_string(), 100.0 - i as f32))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Plugin for NetworkToolsPlugin {
+    fn id(&self) -> &str {
+        "network_tools"
+    }
+    fn name(&self) -> &str {
+        "Network Tools"
+    }
+    fn description(&self) -> &str {
+        "Port owners, DNS lookups and a subnet calculator."
+    }
+    fn trigger(&self) -> Trigger {
+        Trigger::Pattern(PATTERN.to_string())
+    }
+    async fn search(&self, query: &str, _context: &PluginContext) -> Vec<ResultItem> {
+        let (command, argument) = query.split_once(char::is_whitespace).unwrap_or((query, ""));
+        let argument = argument.trim();
+        match command.to_lowercase().as_str() {
+            "port" => match argument.parse::<u16>() {
+                Ok(port) => self.port_results(port).await,
+                Err(_) => vec![self.row("port invalid".to_string(), format!("{} isn't a port", argument), "Ports go from 0 to 65535".to_string(), Action::None, 100.0)],
+            },
+            "dns" => self.dns_results(argument).await,
+            _ => Subnet::parse(query).map(|subnet| self.subnet_results(&subnet)).unwrap_or_default(),
+        }
+    }
+    async fn execute(&self, action: &Action, _context: &PluginContext) -> anyhow::Result<()> {
+        match action {
+            Action::CopyToClipboard(text) => {
+                let mut clipboard = Clipboard::new()?;
+                clipboard.set_text(text)?;
+                println!("Copied to clipboard: {}", text);
+            }
+            Action::Custom { action_type, data } if action_type == "network_tools.kill" => {
+                let pid = data["pid"].as_u64().and_then(|pid| u32::try_from(pid).ok()).ok_or_else(|| anyhow::anyhow!("No process id to stop"))?;
+                process::kill(pid, false)?;
+                println!("Stopped process {}", pid);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dns_waits_for_a_dotted_name() {
+        let plugin = NetworkToolsPlugin::new();
+        let trigger = plugin.trigger();
+        assert_eq!(trigger.matches("dns exa"), None);
+        assert_eq!(trigger.matches("dns example."), None);
+        assert!(trigger.matches("dns example.com").is_some());
+        assert!(trigger.matches("port 8080").is_some());
+
+        // a cached answer comes back without going to the resolver
+        let address: IpAddr = "192.0.2.7".parse().unwrap();
+        plugin.answers.lock().unwrap().insert("cached.invalid".to_string(), (Instant::now(), Ok((vec![address], None))));
+        let results = futures::executor::block_on(plugin.dns_results("Cached.INVALID"));
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "192.0.2.7");
+    }
+}
@@ -0,0 +1,324 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use async_trait::async_trait;
+use arboard::Clipboard;
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+
+use crate::core::plugin::{Plugin, PluginContext, SettingField, Trigger};
+use crate::core::model::{Action, BuiltInIcon, PluginView, ResultIcon, ResultItem};
+use crate::platform::{process, ProcessInfo};
+
+// `ps firefox` looks processes up, `kill firefox` and `kill -9 firefox` stop them
+const PATTERN: &str = r"(?i)^(ps|kill)(\s|$)";
+// CPU usage is the CPU time used between two listings at least this far apart
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+// an older listing says nothing about what the processes do now
+const SAMPLE_MAX_AGE: Duration = Duration::from_secs(10);
+// Typing a PID puts its process above any name that happens to contain the digits
+const PID_MATCH: i64 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Inspect,
+    Kill { force: bool },
+}
+
+// The mode and the filter; like the shell, `kill -9` and `kill -KILL` don't give the process a choice
+fn parse_query(query: &str) -> (Mode, &str) {
+    let query = query.trim();
+    let (command, rest) = query.split_once(char::is_whitespace).unwrap_or((query, ""));
+    let rest = rest.trim();
+    if !command.eq_ignore_ascii_case("kill") {
+        return (Mode::Inspect, rest);
+    }
+    let (flag, filter) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    match flag {
+        "-9" | "-KILL" | "-f" => (Mode::Kill { force: true }, filter.trim()),
+        _ => (Mode::Kill { force: false }, rest),
+    }
+}
+
+fn format_memory(bytes: u64) -> String {
+    let kilobytes = bytes as f64 / 1024.0;
+    if kilobytes < 1024.0 {
+        format!("{:.0} KB", kilobytes)
+    } else if kilobytes < 1024.0 * 1024.0 {
+        format!("{:.0} MB", kilobytes / 1024.0)
+    } else {
+        format!("{:.1} GB", kilobytes / 1024.0 / 1024.0)
+    }
+}
+
+// The PID matches exactly, the name fuzzily; command lines are long enough to fuzzy match almost
+// anything, so they need the filter as a whole
+fn score(process: &ProcessInfo, filter: &str, matcher: &SkimMatcherV2) -> Option<i64> {
+    if filter.is_empty() {
+        return Some(0);
+    }
+    if process.pid.to_string() == filter {
+        return Some(PID_MATCH);
+    }
+    let command = Some(&process.command)
+        .filter(|command| command.to_lowercase().contains(&filter.to_lowercase()))
+        .and_then(|command| matcher.fuzzy_match(command, filter))
+        .map(|score| score / 2);
+    matcher.fuzzy_match(&process.name, filter).into_iter().chain(command).max()
+}
+
+// Matching processes best first, the busiest first among equally good matches
+fn rank(processes: Vec<(ProcessInfo, f32)>, filter: &str) -> Vec<(ProcessInfo, f32)> {
+    let matcher = SkimMatcherV2::default().ignore_case();
+    let mut matches: Vec<(i64, ProcessInfo, f32)> = processes
+        .into_iter()
+        .filter_map(|(process, cpu)| Some((score(&process, filter, &matcher)?, process, cpu)))
+        .collect();
+    matches.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then_with(|| b.2.total_cmp(&a.2))
+            .then_with(|| b.1.memory.cmp(&a.1.memory))
+    });
+    matches.into_iter().map(|(_, process, cpu)| (process, cpu)).collect()
+}
+
+// The launcher and the processes it runs in, which can't be stopped without closing it too
+fn own_lineage(processes: &[(ProcessInfo, f32)]) -> HashSet<u32> {
+    let parents: HashMap<u32, Option<u32>> = processes.iter().map(|(p, _)| (p.pid, p.parent)).collect();
+    let mut lineage = HashSet::new();
+    let mut pid = Some(std::process::id());
+    while let Some(current) = pid.filter(|current| lineage.insert(*current)) {
+        pid = parents.get(&current).copied().flatten();
+    }
+    lineage
+}
+
+struct Sample {
+    taken: Instant,
+    cpu_times: HashMap<u32, Duration>,
+}
+
+impl Sample {
+    fn of(processes: &[ProcessInfo]) -> Self {
+        Self {
+            taken: Instant::now(),
+            cpu_times: processes.iter().map(|p| (p.pid, p.cpu_time)).collect(),
+        }
+    }
+}
+
+async fn list() -> anyhow::Result<Vec<ProcessInfo>> {
+    tokio::task::spawn_blocking(process::list).await?
+}
+
+pub struct ProcessManagerPlugin {
+    last_sample: Mutex<Option<Sample>>,
+}
+
+impl ProcessManagerPlugin {
+    pub fn new() -> Self {
+        Self {
+            last_sample: Mutex::new(None),
+        }
+    }
+
+    // The processes with the CPU they used since the last listing, in percent of one core. Without
+    // a recent listing to compare with, one is taken and compared against shortly after.
+    async fn processes(&self) -> anyhow::Result<Vec<(ProcessInfo, f32)>> {
+        let previous = self.last_sample.lock().unwrap().take().filter(|s| s.taken.elapsed() < SAMPLE_MAX_AGE);
+        let previous = match previous {
+            Some(sample) => sample,
+            None => Sample::of(&list().await?),
+        };
+        if let Some(remaining) = SAMPLE_INTERVAL.checked_sub(previous.taken.elapsed()) {
+            tokio::time::sleep(remaining).await;
+        }
+        let processes = list().await?;
+        let elapsed = previous.taken.elapsed().as_secs_f32();
+        *self.last_sample.lock().unwrap() = Some(Sample::of(&processes));
+        Ok(processes
+            .into_iter()
+            .map(|process| {
+                let used = previous
+                    .cpu_times
+                    .get(&process.pid)
+                    .map_or(Duration::ZERO, |before| process.cpu_time.saturating_sub(*before));
+                let cpu = used.as_secs_f32() / elapsed * 100.0;
+                (process, cpu)
+            })
+            .collect())
+    }
+
+    fn kill_action(&self, process: &ProcessInfo, force: bool, critical: bool) -> Action {
+        let action = Action::Custom {
+            action_type: "process_manager.kill".to_string(),
+            data: serde_json::json!({ "pid": process.pid, "name": process.name, "force": force }),
+        };
+        if critical {
+            let verb = if force { "Force kill" } else { "Stop" };
+            action.confirm(format!("{} is needed by the system or the launcher. {} it anyway?", process.name, verb))
+        } else {
+            action
+        }
+    }
+
+    fn process_result(&self, process: &ProcessInfo, cpu: f32, mode: Mode, critical: bool, score: f32) -> ResultItem {
+        let usage = format!("PID {} · CPU {:.1}% · {}", process.pid, cpu, format_memory(process.memory));
+        let (action, subtitle) = match mode {
+            Mode::Inspect => (
+                Action::ShowPluginView {
+                    plugin_id: self.id().to_string(),
+                    view_id: "process".to_string(),
+                    data: serde_json::json!({ "pid": process.pid }),
+                },
+                format!("{} · {}", usage, process.command),
+            ),
+            Mode::Kill { force } => (
+                self.kill_action(process, force, critical),
+                format!("{} · Enter to {}", usage, if force { "force kill" } else { "stop" }),
+            ),
+        };
+        ResultItem::new(format!("process {}", process.pid), process.name.clone(), action, self.id().to_string())
+            .with_subtitle(subtitle)
+            .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Terminal))
+            .with_score(score)
+    }
+}
+
+#[async_trait]
+impl Plugin for ProcessManagerPlugin {
+    fn id(&self) -> &str {
+        "process_manager"
+    }
+    fn name(&self) -> &str {
+        "Process Manager"
+    }
+    fn description(&self) -> &str {
+        "List running processes and stop them."
+    }
+    fn trigger(&self) -> Trigger {
+        Trigger::Pattern(PATTERN.to_string())
+    }
+    fn settings_schema(&self) -> Vec<SettingField> {
+        vec![SettingField::number("max_results", "Maximum processes shown", 20)]
+    }
+    async fn search(&self, query: &str, context: &PluginContext) -> Vec<ResultItem> {
+        let max_results = context.get_setting::<usize>(self.id(), "max_results").unwrap_or(20);
+        let (mode, filter) = parse_query(query);
+        let processes = match self.processes().await {
+            Ok(processes) => processes,
+            Err(e) => {
+                return vec![
+                    ResultItem::new("process error", "Can't list the running processes", Action::None, self.id().to_string())
+                        .with_subtitle(e.to_string())
+                        .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Terminal))
+                        .with_score(100.0)
+                ];
+            }
+        };
+        let lineage = own_lineage(&processes);
+        rank(processes, filter)
+            .into_iter()
+            .take(max_results)
+            .enumerate()
+            .map(|(i, (process, cpu))| {
+                let critical = process::is_critical(&process) || lineage.contains(&process.pid);
+                self.process_result(&process, cpu, mode, critical, 100.0 - i as f32)
+            })
+            .collect()
+    }
+    async fn view(&self, action: &Action, _context: &PluginContext) -> Option<PluginView> {
+        let Action::ShowPluginView { data, .. } = action else {
+            return None;
+        };
+        let pid = data["pid"].as_u64().and_then(|pid| u32::try_from(pid).ok())?;
+        let processes = self.processes().await.ok()?;
+        let Some((process, cpu)) = processes.iter().find(|(p, _)| p.pid == pid) else {
+            return Some(PluginView::detail(format!("PID {}", pid), "The process has exited.", Vec::new()));
+        };
+        let parent = match process.parent {
+            Some(parent) => {
+                let name = processes.iter().find(|(p, _)| p.pid == parent).map(|(p, _)| p.name.as_str()).unwrap_or("exited");
+                format!("Started by {} (PID {})", name, parent)
+            }
+            None => "No parent process".to_string(),
+        };
+        let seconds = process.cpu_time.as_secs();
+        let cpu_time = format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60);
+        let body = format!(
+            "PID {}\n{}\nCPU {:.1}% · {} in total\nMemory {}\n\n{}",
+            process.pid,
+            parent,
+            cpu,
+            cpu_time,
+            format_memory(process.memory),
+            process.command
+        );
+        let critical = process::is_critical(process) || own_lineage(&processes).contains(&process.pid);
+        let row = |id: &str, title: &str, action: Action| {
+            ResultItem::new(format!("process {} {}", id, pid), title.to_string(), action, self.id().to_string())
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Terminal))
+        };
+        let actions = vec![
+            row("stop", "Stop process", self.kill_action(process, false, critical)),
+            row("kill", "Force kill", self.kill_action(process, true, critical)),
+            row("copy", "Copy PID", Action::CopyToClipboard(pid.to_string())),
+        ];
+        Some(PluginView::detail(process.name.clone(), body, actions))
+    }
+    async fn execute(&self, action: &Action, _context: &PluginContext) -> anyhow::Result<()> {
+        match action {
+            Action::CopyToClipboard(text) => {
+                let mut clipboard = Clipboard::new()?;
+                clipboard.set_text(text)?;
+                println!("Copied to clipboard: {}", text);
+            }
+            Action::Custom { action_type, data } if action_type == "process_manager.kill" => {
+                let pid = data["pid"].as_u64().and_then(|pid| u32::try_from(pid).ok()).ok_or_else(|| anyhow::anyhow!("No process id to stop"))?;
+                let force = data["force"].as_bool().unwrap_or(false);
+                process::kill(pid, force)?;
+                println!("{} {} (PID {})", if force { "Killed" } else { "Stopped" }, data["name"].as_str().unwrap_or("process"), pid);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, parent: u32, name: &str, command: &str) -> (ProcessInfo, f32) {
+        let info = ProcessInfo {
+            pid,
+            parent: Some(parent),
+            name: name.to_string(),
+            command: command.to_string(),
+            ..Default::default()
+        };
+        (info, pid as f32 / 100.0)
+    }
+
+    #[test]
+    fn test_query_and_ranking() {
+        assert_eq!(parse_query("ps"), (Mode::Inspect, ""));
+        assert_eq!(parse_query("kill fire fox"), (Mode::Kill { force: false }, "fire fox"));
+        assert_eq!(parse_query("KILL -9 firefox"), (Mode::Kill { force: true }, "firefox"));
+        assert_eq!(format_memory(340 * 1024 * 1024), "340 MB");
+        assert_eq!(format_memory(3 * 1024 * 1024 * 1024 / 2), "1.5 GB");
+
+        let processes = vec![
+            process(1, 0, "systemd", "/sbin/init splash"),
+            process(812, 1, "node", "node /home/me/app/server.js --port 3000"),
+            process(950, 1, "firefox", "/usr/lib/firefox/firefox"),
+            process(40, 1, "nodemon", "nodemon server.js"),
+        ];
+        let pids = |filter: &str| -> Vec<u32> { rank(processes.clone(), filter).into_iter().map(|(p, _)| p.pid).collect() };
+        assert_eq!(pids(""), [950, 812, 40, 1]);
+        assert_eq!(pids("fire"), [950]);
+        assert_eq!(pids("server.js"), [812, 40]);
+        assert_eq!(pids("812"), [812]);
+        assert!(pids("zzz").is_empty());
+    }
+}
@@ -0,0 +1,68 @@
+use async_trait::async_trait;
+
+use crate::core::config::Config;
+use crate::core::plugin::{Plugin, PluginContext, Trigger};
+use crate::core::model::{Action, BuiltInIcon, ResultIcon, ResultItem};
+use crate::platform::shell;
+
+pub struct SettingsPlugin {}
+
+impl SettingsPlugin {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl Plugin for SettingsPlugin {
+    fn id(&self) -> &str {
+        "settings"
+    }
+    fn name(&self) -> &str {
+        "Settings"
+    }
+    fn description(&self) -> &str {
+        "A plugin for changing launcher settings."
+    }
+    fn trigger(&self) -> Trigger {
+        Trigger::Explicit { keyword: "settings".to_string() }
+    }
+    async fn search(&self, query: &str, _context: &PluginContext) -> Vec<ResultItem> {
+        let mut results = Vec::new();
+        results.push(
+            ResultItem::new(
+                "open settings",
+                "Open Settings",
+                Action::OpenSettings,
+                self.id().to_string()
+            )
+            .with_subtitle("Edit general, appearance, hotkey and plugin settings")
+            .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Settings))
+            .with_score(100.0)
+        );
+        let config_path = Config::user_path();
+        results.push(
+            ResultItem::new(
+                "open config file",
+                "Open Config File",
+                Action::OpenFile(config_path.clone()),
+                self.id().to_string()
+            )
+            .with_subtitle(config_path.display().to_string())
+            .with_icon(ResultIcon::BuiltIn(BuiltInIcon::File))
+            .with_score(50.0)
+        );
+        results
+    }
+    async fn execute(&self, action: &Action, _context: &PluginContext) -> anyhow::Result<()> {
+        if let Action::OpenFile(path) = action {
+            if !path.exists() {
+                // write the current settings so there is a file to open
+                Config::load()?.save()?;
+            }
+            shell::open(&path.to_string_lossy())?;
+            println!("Config file opened: {}", path.display());
+        }
+        Ok(())
+    }
+}
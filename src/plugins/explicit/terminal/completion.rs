@@ -0,0 +1,90 @@
+// Completes the word being typed: a program name first, file paths after that
+use std::path::{Path, PathBuf};
+
+const MAX_COMPLETIONS: usize = 8;
+
+// Splits "git checkout src/ma" into ("git checkout ", "src/ma")
+pub fn split_last_word(command: &str) -> (&str, &str) {
+    match command.rfind(char::is_whitespace) {
+        Some(i) => (&command[..=i], &command[i + 1..]),
+        None => ("", command),
+    }
+}
+
+pub fn looks_like_path(word: &str) -> bool {
+    word.starts_with(['.', '~', '/']) || word.contains(['/', '\\']) || (word.len() >= 2 && word.as_bytes()[1] == b':')
+}
+
+// Entries of the directory the word points into, completed to the full word
+pub fn complete_path(word: &str, cwd: &Path, home: Option<&Path>) -> Vec<String> {
+    let split = word.rfind(['/', '\\']).map_or(0, |i| i + 1);
+    let (dir_part, prefix) = word.split_at(split);
+    let dir = match (dir_part.strip_prefix("~"), home) {
+        (Some(rest), Some(home)) => home.join(rest.trim_start_matches(['/', '\\'])),
+        _ if dir_part.is_empty() => cwd.to_path_buf(),
+        _ if Path::new(dir_part).is_absolute() => PathBuf::from(dir_part),
+        _ => cwd.join(dir_part),
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+    let mut completions: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            // dotfiles only when asked for
+            let wanted = name.to_lowercase().starts_with(&prefix.to_lowercase()) && (prefix.starts_with('.') || !name.starts_with('.'));
+            if !wanted {
+                return None;
+            }
+            let separator = if entry.path().is_dir() { "/" } else { "" };
+            Some(format!("{}{}{}", dir_part, name, separator))
+        })
+        .collect();
+    completions.sort();
+    completions.truncate(MAX_COMPLETIONS);
+    completions
+}
+
+// Programs starting with the word, the ones used most often first
+pub fn complete_program(word: &str, executables: &[String], ranked_commands: &[String]) -> Vec<String> {
+    if word.is_empty() {
+        return Vec::new();
+    }
+    let rank = |name: &str| {
+        ranked_commands
+            .iter()
+            .position(|command| command.split_whitespace().next() == Some(name))
+            .unwrap_or(usize::MAX)
+    };
+    let mut matches: Vec<&String> = executables.iter().filter(|name| name.starts_with(word) && *name != word).collect();
+    matches.sort_by_key(|name| (rank(name), name.len()));
+    matches.into_iter().take(MAX_COMPLETIONS).cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_and_programs() {
+        assert_eq!(split_last_word("git checkout src/ma"), ("git checkout ", "src/ma"));
+        assert_eq!(split_last_word("car"), ("", "car"));
+        let executables = ["cargo".to_string(), "cat".to_string(), "carbon".to_string()];
+        let ranked = ["cargo build".to_string()];
+        assert_eq!(complete_program("ca", &executables, &ranked), ["cargo", "cat", "carbon"]);
+    }
+
+    #[test]
+    fn test_complete_path() {
+        let dir = std::env::temp_dir().join(format!("catalyst-completion-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("Cargo.toml"), "").unwrap();
+        std::fs::write(dir.join(".hidden"), "").unwrap();
+        assert_eq!(complete_path("", &dir, None), ["Cargo.toml", "src/"]);
+        assert_eq!(complete_path("./s", &dir, None), ["./src/"]);
+        assert_eq!(complete_path(".h", &dir, None), [".hidden"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(looks_like_path("~/Documents") && looks_like_path("C:") && !looks_like_path("git"));
+    }
+}
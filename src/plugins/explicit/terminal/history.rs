@@ -0,0 +1,263 @@
+// Commands run from the launcher plus the user's shell history files, ranked by frecency
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::config::data_dir;
+
+// Shell history files can be huge, only their most recent commands are used
+const MAX_SHELL_COMMANDS: usize = 5000;
+const RELOAD_AFTER: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HistoryFormat {
+    Bash,
+    Zsh,
+    Fish,
+    PowerShell,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Usage {
+    pub count: u32,
+    pub last_used: u64,
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+// Uses weighted by how recent the last one was, like browser address bars
+pub fn frecency(usage: &Usage, now: u64) -> f64 {
+    let hours = now.saturating_sub(usage.last_used) / 3600;
+    let recency = match hours {
+        0..=3 => 100.0,
+        4..=23 => 80.0,
+        24..=167 => 60.0,
+        168..=719 => 40.0,
+        720..=2159 => 20.0,
+        _ => 10.0,
+    };
+    usage.count as f64 * recency
+}
+
+// (command, timestamp if the format records one), oldest first
+pub fn parse_history(content: &str, format: HistoryFormat) -> Vec<(String, Option<u64>)> {
+    let mut commands = Vec::new();
+    match format {
+        HistoryFormat::Bash => {
+            let mut timestamp = None;
+            for line in content.lines() {
+                // HISTTIMEFORMAT writes "#1700000000" before each command
+                if let Some(time) = line.strip_prefix('#').and_then(|t| t.parse().ok()) {
+                    timestamp = Some(time);
+                } else if !line.trim().is_empty() {
+                    commands.push((line.to_string(), timestamp.take()));
+                }
+            }
+        }
+        HistoryFormat::Zsh | HistoryFormat::PowerShell => {
+            // multi-line commands end their lines with \ in zsh and ` in PowerShell
+            let continuation = if format == HistoryFormat::Zsh { '\\' } else { '`' };
+            let mut pending: Option<(String, Option<u64>)> = None;
+            for line in content.lines() {
+                let (command, timestamp) = match pending.take() {
+                    Some((mut command, timestamp)) => {
+                        command.push('\n');
+                        command.push_str(line);
+                        (command, timestamp)
+                    }
+                    // extended history: ": 1700000000:0;command"
+                    None => match line.strip_prefix(": ").and_then(|rest| rest.split_once(';')) {
+                        Some((meta, command)) if format == HistoryFormat::Zsh => {
+                            (command.to_string(), meta.split(':').next().and_then(|t| t.parse().ok()))
+                        }
+                        _ => (line.to_string(), None),
+                    },
+                };
+                if command.ends_with(continuation) {
+                    pending = Some((command[..command.len() - 1].to_string(), timestamp));
+                } else if !command.trim().is_empty() {
+                    commands.push((command, timestamp));
+                }
+            }
+        }
+        HistoryFormat::Fish => {
+            // "- cmd: ls -la\n  when: 1700000000"
+            for line in content.lines() {
+                if let Some(command) = line.strip_prefix("- cmd: ") {
+                    commands.push((command.replace("\\n", "\n").replace("\\\\", "\\"), None));
+                } else if let Some(when) = line.trim_start().strip_prefix("when: ") {
+                    if let Some(last) = commands.last_mut() {
+                        last.1 = when.trim().parse().ok();
+                    }
+                }
+            }
+        }
+    }
+    commands
+}
+
+fn shell_history_files() -> Vec<(PathBuf, HistoryFormat)> {
+    let mut files = Vec::new();
+    if let Some(home) = dirs::home_dir() {
+        files.push((home.join(".bash_history"), HistoryFormat::Bash));
+        let zdotdir = std::env::var_os("ZDOTDIR").map(PathBuf::from).unwrap_or_else(|| home.clone());
+        files.push((zdotdir.join(".zsh_history"), HistoryFormat::Zsh));
+    }
+    if let Some(data) = dirs::data_dir() {
+        files.push((data.join("fish").join("fish_history"), HistoryFormat::Fish));
+        let psreadline = if cfg!(target_os = "windows") {
+            data.join("Microsoft").join("Windows").join("PowerShell")
+        } else {
+            data.join("powershell")
+        };
+        files.push((psreadline.join("PSReadLine").join("ConsoleHost_history.txt"), HistoryFormat::PowerShell));
+    }
+    files
+}
+
+fn read_shell_history() -> HashMap<String, Usage> {
+    let mut usages: HashMap<String, Usage> = HashMap::new();
+    for (path, format) in shell_history_files() {
+        let Ok(bytes) = std::fs::read(&path) else {
+            continue;
+        };
+        let modified = std::fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map_or(now(), |d| d.as_secs());
+        let commands = parse_history(&String::from_utf8_lossy(&bytes), format);
+        let skip = commands.len().saturating_sub(MAX_SHELL_COMMANDS);
+        let total = commands.len() - skip;
+        for (i, (command, timestamp)) in commands.into_iter().skip(skip).enumerate() {
+            // without timestamps the position in the file stands in for the time
+            let time = timestamp.unwrap_or(modified.saturating_sub((total - i) as u64 * 60));
+            let usage = usages.entry(command.trim().to_string()).or_insert(Usage { count: 0, last_used: 0 });
+            usage.count += 1;
+            usage.last_used = usage.last_used.max(time);
+        }
+    }
+    usages
+}
+
+pub struct TerminalHistory {
+    commands: HashMap<String, Usage>,
+    path: Option<PathBuf>,
+    shell: HashMap<String, Usage>,
+    shell_loaded: Option<Instant>,
+    executables: Vec<String>,
+    executables_loaded: Option<Instant>,
+}
+
+impl TerminalHistory {
+    pub fn load() -> Self {
+        let path = data_dir().join("terminal_history.json");
+        let commands = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self::new(commands, Some(path))
+    }
+
+    fn new(commands: HashMap<String, Usage>, path: Option<PathBuf>) -> Self {
+        Self {
+            commands,
+            path,
+            shell: HashMap::new(),
+            shell_loaded: None,
+            executables: Vec::new(),
+            executables_loaded: None,
+        }
+    }
+
+    pub fn record(&mut self, command: &str) {
+        let usage = self.commands.entry(command.trim().to_string()).or_insert(Usage { count: 0, last_used: 0 });
+        usage.count += 1;
+        usage.last_used = now();
+        self.save();
+    }
+
+    // All known commands, best first; launcher history and shell history are merged
+    pub fn ranked(&mut self, include_shell: bool) -> Vec<(String, Usage)> {
+        let mut merged = self.commands.clone();
+        if include_shell {
+            if self.shell_loaded.is_none_or(|loaded| loaded.elapsed() > RELOAD_AFTER) {
+                self.shell = read_shell_history();
+                self.shell_loaded = Some(Instant::now());
+            }
+            for (command, usage) in &self.shell {
+                let merged = merged.entry(command.clone()).or_insert(Usage { count: 0, last_used: 0 });
+                merged.count += usage.count;
+                merged.last_used = merged.last_used.max(usage.last_used);
+            }
+        }
+        let now = now();
+        let mut ranked: Vec<(String, Usage)> = merged.into_iter().collect();
+        ranked.sort_by(|a, b| frecency(&b.1, now).total_cmp(&frecency(&a.1, now)).then_with(|| a.0.cmp(&b.0)));
+        ranked
+    }
+
+    // Programs on PATH, listed again at most once a minute
+    pub fn executables(&mut self) -> &[String] {
+        if self.executables_loaded.is_none_or(|loaded| loaded.elapsed() > RELOAD_AFTER) {
+            self.executables = crate::platform::terminal::path_executables();
+            self.executables_loaded = Some(Instant::now());
+        }
+        &self.executables
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = std::fs::create_dir_all(data_dir())
+            .map_err(anyhow::Error::from)
+            .and_then(|_| Ok(serde_json::to_string_pretty(&self.commands)?))
+            .and_then(|content| Ok(std::fs::write(path, content)?));
+        if let Err(e) = result {
+            log::warn!("Failed to save terminal history: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_history_formats() {
+        let bash = parse_history("#1700000000\nls -la\ncd /tmp\n", HistoryFormat::Bash);
+        assert_eq!(bash, vec![("ls -la".to_string(), Some(1700000000)), ("cd /tmp".to_string(), None)]);
+
+        let zsh = parse_history(": 1700000000:0;git status\n: 1700000100:0;echo a \\\nb\nls\n", HistoryFormat::Zsh);
+        assert_eq!(zsh[0], ("git status".to_string(), Some(1700000000)));
+        assert_eq!(zsh[1], ("echo a \nb".to_string(), Some(1700000100)));
+        assert_eq!(zsh[2], ("ls".to_string(), None));
+
+        let fish = parse_history("- cmd: cargo build\n  when: 1700000000\n- cmd: echo a\\nb\n", HistoryFormat::Fish);
+        assert_eq!(fish, vec![("cargo build".to_string(), Some(1700000000)), ("echo a\nb".to_string(), None)]);
+
+        let pwsh = parse_history("Get-ChildItem `\n-Force\n", HistoryFormat::PowerShell);
+        assert_eq!(pwsh, vec![("Get-ChildItem \n-Force".to_string(), None)]);
+    }
+
+    #[test]
+    fn test_frecency_ranking() {
+        let now = 10_000_000;
+        let recent = Usage { count: 1, last_used: now - 60 };
+        let frequent_but_old = Usage { count: 3, last_used: now - 400 * 3600 };
+        assert!(frecency(&recent, now) < frecency(&frequent_but_old, now));
+        assert!(frecency(&Usage { count: 3, last_used: now }, now) > frecency(&frequent_but_old, now));
+
+        let mut history = TerminalHistory::new(HashMap::new(), None);
+        history.record("ls");
+        history.record("git status");
+        history.record("git status");
+        let ranked: Vec<String> = history.ranked(false).into_iter().map(|(c, _)| c).collect();
+        assert_eq!(ranked, ["git status", "ls"]);
+    }
+}
\ No newline at end of file
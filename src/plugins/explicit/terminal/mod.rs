@@ -0,0 +1,347 @@
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use async_trait::async_trait;
+use arboard::Clipboard;
+
+use crate::core::plugin::{Plugin, PluginContext, SettingField, Trigger};
+use crate::core::model::{Action, BuiltInIcon, PluginView, ResultIcon, ResultItem};
+use crate::platform::terminal;
+
+pub mod completion;
+pub mod history;
+pub mod shell;
+
+use history::TerminalHistory;
+use shell::Shell;
+
+// Both modes read their settings from `[plugins.terminal]`
+const SETTINGS_ID: &str = "terminal";
+// Characters of output shown in the result view, copying always gets all of it
+const MAX_SHOWN_OUTPUT: usize = 20_000;
+const MAX_HISTORY_ROWS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    // `/cmd` runs in a new terminal window
+    Window,
+    // `>cmd` runs in the background and shows the output
+    Capture,
+}
+
+pub struct TerminalPlugin {
+    mode: Mode,
+    history: Arc<Mutex<TerminalHistory>>,
+}
+
+// stdout and stderr as one text, with stderr marked when both have something
+pub fn combine_output(stdout: &str, stderr: &str) -> String {
+    match (stdout.trim_end(), stderr.trim_end()) {
+        ("", "") => String::new(),
+        (stdout, "") => stdout.to_string(),
+        ("", stderr) => stderr.to_string(),
+        (stdout, stderr) => format!("{}\n\nstderr:\n{}", stdout, stderr),
+    }
+}
+
+// The end of long output, where errors and results usually are
+fn tail(output: &str, max: usize) -> String {
+    if output.len() <= max {
+        return output.to_string();
+    }
+    let mut start = output.len() - max;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    let omitted = output[..start].lines().count();
+    format!("… {} lines omitted\n{}", omitted, &output[start..])
+}
+
+impl TerminalPlugin {
+    pub fn new() -> Self {
+        Self {
+            mode: Mode::Window,
+            history: Arc::new(Mutex::new(TerminalHistory::load())),
+        }
+    }
+    // The `>` plugin, running commands in the background and showing what they print
+    pub fn capture_plugin(&self) -> Self {
+        Self {
+            mode: Mode::Capture,
+            history: self.history.clone(),
+        }
+    }
+
+    fn keyword(&self) -> &str {
+        match self.mode {
+            Mode::Window => "/",
+            Mode::Capture => ">",
+        }
+    }
+
+    fn run_action(&self, command: &str) -> Action {
+        match self.mode {
+            Mode::Window => Action::ExecuteCommand { command: command.to_string(), args: vec![] },
+            Mode::Capture => Self::output_action(command),
+        }
+    }
+
+    fn history_result(&self, command: &str, count: u32, score: f32) -> ResultItem {
+        let times = if count == 1 { "once".to_string() } else { format!("{} times", count) };
+        ResultItem::new(
+            format!("terminal history {}", command),
+            command.to_string(),
+            self.run_action(command),
+            self.id().to_string()
+        )
+        .with_subtitle(format!("From history · run {}", times))
+        .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Terminal))
+        .with_score(score)
+    }
+
+    // Rows putting the completed command back in the search bar
+    fn completion_results(&self, command: &str, context: &PluginContext, ranked: &[String]) -> Vec<ResultItem> {
+        let (before, word) = completion::split_last_word(command);
+        let (completions, subtitle) = if completion::looks_like_path(word) || !before.is_empty() {
+            let home = dirs::home_dir();
+            (completion::complete_path(word, &Self::working_directory(context), home.as_deref()), "Complete path")
+        } else {
+            let mut history = self.history.lock().unwrap();
+            (completion::complete_program(word, history.executables(), ranked), "Complete program")
+        };
+        completions
+            .into_iter()
+            .enumerate()
+            .map(|(i, completed)| {
+                let text = format!("{}{}", before, completed);
+                ResultItem::new(
+                    format!("terminal complete {}", text),
+                    text.clone(),
+                    Action::InsertText(format!("{}{}", self.keyword(), text)),
+                    self.id().to_string()
+                )
+                .with_subtitle(subtitle)
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Terminal))
+                .with_score(80.0 - i as f32)
+            })
+            .collect()
+    }
+
+    fn shell(context: &PluginContext) -> Shell {
+        Shell::resolve(context.get_setting::<String>(SETTINGS_ID, "shell"))
+    }
+
+    fn working_directory(context: &PluginContext) -> PathBuf {
+        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+        match context.get_setting::<String>(SETTINGS_ID, "working_directory").filter(|d| !d.trim().is_empty()) {
+            Some(dir) => match dir.strip_prefix("~") {
+                Some(rest) => home.join(rest.trim_start_matches(['/', '\\'])),
+                None => PathBuf::from(dir),
+            },
+            None => home,
+        }
+    }
+
+    fn output_action(command: &str) -> Action {
+        Action::ShowPluginView {
+            plugin_id: "terminal_capture".to_string(),
+            view_id: "output".to_string(),
+            data: serde_json::json!({ "command": command }),
+        }
+    }
+
+    async fn run_captured(&self, command: &str, context: &PluginContext) -> PluginView {
+        self.history.lock().unwrap().record(command);
+        let shell = Self::shell(context);
+        let timeout = context.get_setting::<u64>(SETTINGS_ID, "capture_timeout").unwrap_or(30);
+        let started = Instant::now();
+        let mut process = terminal::background_command(&shell.program);
+        process
+            .args(shell.command_args(command))
+            .current_dir(Self::working_directory(context))
+            .stdin(Stdio::null())
+            .kill_on_drop(true);
+        let (status, output) = match tokio::time::timeout(Duration::from_secs(timeout), process.output()).await {
+            Ok(Ok(output)) => {
+                let status = match output.status.code() {
+                    Some(code) => format!("exit status {}", code),
+                    None => "terminated by a signal".to_string(),
+                };
+                let text = combine_output(&String::from_utf8_lossy(&output.stdout), &String::from_utf8_lossy(&output.stderr));
+                (status, text)
+            }
+            Ok(Err(e)) => (format!("failed to start {}", shell.name()), e.to_string()),
+            Err(_) => (format!("killed after {}s", timeout), String::new()),
+        };
+        log::info!("Captured `{}`: {}", command, status);
+
+        let mut actions = Vec::new();
+        if !output.is_empty() {
+            actions.push(
+                ResultItem::new("terminal copy output", "Copy output", Action::CopyToClipboard(output.clone()), self.id().to_string())
+                    .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Terminal))
+            );
+        }
+        actions.push(
+            ResultItem::new("terminal run again", "Run again", Self::output_action(command), self.id().to_string())
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Terminal))
+        );
+        actions.push(
+            ResultItem::new(
+                "terminal open in terminal",
+                "Run in a terminal window",
+                Action::ExecuteCommand { command: command.to_string(), args: vec![] },
+                SETTINGS_ID.to_string()
+            )
+            .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Terminal))
+        );
+        actions.push(
+            ResultItem::new("terminal copy command", "Copy command", Action::CopyToClipboard(command.to_string()), self.id().to_string())
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Terminal))
+        );
+
+        let shown = if output.is_empty() { "(no output)".to_string() } else { tail(&output, MAX_SHOWN_OUTPUT) };
+        let body = format!("{}\n\n{} · {:.1}s", shown, status, started.elapsed().as_secs_f32());
+        PluginView::detail(format!("{} · {}", command, status), body, actions)
+    }
+}
+
+#[async_trait]
+impl Plugin for TerminalPlugin {
+    fn id(&self) -> &str {
+        match self.mode {
+            Mode::Window => "terminal",
+            Mode::Capture => "terminal_capture",
+        }
+    }
+    fn name(&self) -> &str {
+        match self.mode {
+            Mode::Window => "Terminal Plugin",
+            Mode::Capture => "Terminal Output",
+        }
+    }
+    fn description(&self) -> &str {
+        match self.mode {
+            Mode::Window => "A plugin for running terminal commands.",
+            Mode::Capture => "A plugin for running commands in the background and showing their output.",
+        }
+    }
+    fn trigger(&self) -> Trigger {
+        match self.mode {
+            Mode::Window => Trigger::Explicit { keyword: "/".to_string() },
+            Mode::Capture => Trigger::Explicit { keyword: ">".to_string() },
+        }
+    }
+    // commands and paths are case sensitive
+    fn keeps_case(&self) -> bool {
+        true
+    }
+    fn settings_schema(&self) -> Vec<SettingField> {
+        if self.mode == Mode::Capture {
+            return Vec::new();
+        }
+        vec![
+            SettingField::text("shell", "Shell (empty for the system default)", ""),
+            SettingField::text("terminal", "Terminal emulator (empty to detect)", ""),
+            SettingField::text("working_directory", "Working directory", "~"),
+            SettingField::number("capture_timeout", "Background command timeout (seconds)", 30),
+            SettingField::toggle("shell_history", "Suggest commands from shell history files", true),
+        ]
+    }
+    async fn search(&self, query: &str, context: &PluginContext) -> Vec<ResultItem> {
+        let mut results = Vec::new();
+        let command = query.trim_start();
+        let shell = Self::shell(context);
+        let include_shell = context.get_setting::<bool>(SETTINGS_ID, "shell_history").unwrap_or(true);
+        let ranked = self.history.lock().unwrap().ranked(include_shell);
+        if command.trim().is_empty() {
+            let hint = match self.mode {
+                Mode::Window => "Type a command to run in a new terminal",
+                Mode::Capture => "Type a command to run in the background and see its output",
+            };
+            results.push(
+                ResultItem::new("terminal hint", hint, Action::None, self.id().to_string())
+                    .with_subtitle(format!("Runs with {}", shell.name()))
+                    .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Terminal))
+                    .with_score(100.0)
+            );
+            for (i, (previous, usage)) in ranked.iter().take(MAX_HISTORY_ROWS).enumerate() {
+                results.push(self.history_result(previous, usage.count, 90.0 - i as f32));
+            }
+            return results;
+        }
+        let subtitle = match self.mode {
+            Mode::Window => format!("Run in a new terminal with {}", shell.name()),
+            Mode::Capture => format!("Run with {} and show the output", shell.name()),
+        };
+        results.push(
+            ResultItem::new(
+                format!("Run: {}", command.trim()),
+                command.trim().to_string(),
+                self.run_action(command.trim()),
+                self.id().to_string()
+            )
+            .with_subtitle(subtitle)
+            .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Terminal))
+            .with_score(100.0)
+        );
+
+        // commands starting with what was typed come before ones merely containing it
+        let lowercase = command.trim().to_lowercase();
+        let (prefixed, containing): (Vec<_>, Vec<_>) = ranked
+            .iter()
+            .filter(|(previous, _)| previous.to_lowercase().contains(&lowercase) && previous != command.trim())
+            .partition(|(previous, _)| previous.to_lowercase().starts_with(&lowercase));
+        for (i, (previous, usage)) in prefixed.into_iter().chain(containing).take(MAX_HISTORY_ROWS).enumerate() {
+            results.push(self.history_result(previous, usage.count, 90.0 - i as f32));
+        }
+        let commands: Vec<String> = ranked.into_iter().map(|(previous, _)| previous).collect();
+        results.extend(self.completion_results(command, context, &commands));
+        results
+    }
+    async fn execute(&self, action: &Action, context: &PluginContext) -> anyhow::Result<()> {
+        match action {
+            Action::ExecuteCommand { command, .. } => {
+                self.history.lock().unwrap().record(command);
+                let shell = Self::shell(context);
+                let emulator = context.get_setting::<String>(SETTINGS_ID, "terminal");
+                terminal::open_in_terminal(
+                    emulator.as_deref(),
+                    &shell.program,
+                    &shell.interactive_args(command),
+                    &Self::working_directory(context),
+                )?;
+                println!("Command executed: {}", command);
+            }
+            Action::CopyToClipboard(text) => {
+                let mut clipboard = Clipboard::new()?;
+                clipboard.set_text(text)?;
+                println!("Copied to clipboard");
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+    async fn view(&self, action: &Action, context: &PluginContext) -> Option<PluginView> {
+        let Action::ShowPluginView { data, .. } = action else {
+            return None;
+        };
+        let command = data["command"].as_str()?;
+        Some(self.run_captured(command, context).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_formatting() {
+        assert_eq!(combine_output("out\n", ""), "out");
+        assert_eq!(combine_output("", "err\n"), "err");
+        assert_eq!(combine_output("out", "err"), "out\n\nstderr:\nerr");
+        let long = "line\n".repeat(10);
+        assert_eq!(tail(&long, 10), "… 8 lines omitted\nline\nline\n");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+// The shell commands run in, and how to hand it a command line
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShellKind {
+    // sh, bash, zsh, dash and friends
+    Posix,
+    Fish,
+    Nu,
+    PowerShell,
+    Cmd,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shell {
+    pub program: String,
+    pub kind: ShellKind,
+}
+
+// C:\Windows\System32\cmd.exe -> "cmd", whichever platform the path came from
+fn stem(program: &str) -> &str {
+    let file = program.rsplit(['/', '\\']).next().unwrap_or(program);
+    file.rsplit_once('.').map_or(file, |(stem, _)| stem)
+}
+
+impl Shell {
+    pub fn from_program(program: &str) -> Self {
+        let kind = match stem(program).to_lowercase().as_str() {
+            "fish" => ShellKind::Fish,
+            "nu" => ShellKind::Nu,
+            "pwsh" | "powershell" => ShellKind::PowerShell,
+            "cmd" => ShellKind::Cmd,
+            _ => ShellKind::Posix,
+        };
+        Self { program: program.to_string(), kind }
+    }
+
+    // The configured shell, otherwise $SHELL on Linux and PowerShell on Windows
+    pub fn resolve(configured: Option<String>) -> Self {
+        match configured.filter(|s| !s.trim().is_empty()) {
+            Some(program) => Self::from_program(program.trim()),
+            None => Self::platform_default(),
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn platform_default() -> Self {
+        if crate::platform::terminal::find_in_path("pwsh").is_some() {
+            Self::from_program("pwsh")
+        } else {
+            Self::from_program("powershell")
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn platform_default() -> Self {
+        Self::from_program(&std::env::var("SHELL").ok().filter(|s| !s.is_empty()).unwrap_or_else(|| "/bin/sh".to_string()))
+    }
+
+    pub fn name(&self) -> &str {
+        stem(&self.program)
+    }
+
+    // Arguments that run the command line and exit
+    pub fn command_args(&self, command: &str) -> Vec<String> {
+        let flags: &[&str] = match self.kind {
+            ShellKind::Posix | ShellKind::Fish | ShellKind::Nu => &["-c"],
+            ShellKind::PowerShell => &["-NoLogo", "-Command"],
+            ShellKind::Cmd => &["/C"],
+        };
+        flags.iter().map(|f| f.to_string()).chain([command.to_string()]).collect()
+    }
+
+    // Like `command_args`, but waits for a key press so the output stays readable in a new window
+    pub fn interactive_args(&self, command: &str) -> Vec<String> {
+        let command = match self.kind {
+            ShellKind::Posix => format!("{}; printf '\\nPress Enter to close this window'; read _", command),
+            ShellKind::Fish => format!("{}; echo; read -P 'Press Enter to close this window' _", command),
+            ShellKind::Nu => format!("{}; input 'Press Enter to close this window'", command),
+            ShellKind::PowerShell => format!(
+                "{}; Write-Host 'Press any key to close this window'; $null = [Console]::ReadKey($true)",
+                command
+            ),
+            ShellKind::Cmd => format!("{} & pause", command),
+        };
+        self.command_args(&command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shell_arguments() {
+        assert_eq!(Shell::from_program("/usr/bin/zsh").command_args("ls"), ["-c", "ls"]);
+        assert_eq!(Shell::from_program("C:\\Windows\\System32\\cmd.exe").command_args("dir"), ["/C", "dir"]);
+        let pwsh = Shell::from_program("pwsh");
+        assert_eq!(pwsh.kind, ShellKind::PowerShell);
+        assert!(pwsh.interactive_args("ls")[2].starts_with("ls; Write-Host"));
+        assert_eq!(Shell::from_program("fish").name(), "fish");
+    }
+}
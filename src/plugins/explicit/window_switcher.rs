@@ -0,0 +1,253 @@
+use async_trait::async_trait;
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+
+use crate::core::plugin::{Plugin, PluginContext, Trigger};
+use crate::core::model::{Action, BuiltInIcon, PluginView, ResultIcon, ResultItem};
+use crate::platform::{window, WindowBackend, WindowInfo};
+
+// "win" on its own, so "wine" and "winscp" stay app searches
+const PATTERN: &str = r"(?i)^win(\s|$)";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Command {
+    Focus,
+    Close,
+    Minimize,
+}
+
+impl Command {
+    fn action_type(&self) -> &'static str {
+        match self {
+            Command::Focus => "window_switcher.focus",
+            Command::Close => "window_switcher.close",
+            Command::Minimize => "window_switcher.minimize",
+        }
+    }
+
+    fn from_action_type(action_type: &str) -> Option<Command> {
+        [Command::Focus, Command::Close, Command::Minimize]
+            .into_iter()
+            .find(|command| command.action_type() == action_type)
+    }
+}
+
+fn perform(backend: &dyn WindowBackend, command: Command, id: u64) -> anyhow::Result<()> {
+    match command {
+        Command::Focus => backend.focus(id),
+        Command::Close => backend.close(id),
+        Command::Minimize => backend.minimize(id),
+    }
+}
+
+fn command_action(command: Command, id: u64, title: &str) -> Action {
+    Action::Custom {
+        action_type: command.action_type().to_string(),
+        data: serde_json::json!({ "id": id, "title": title }),
+    }
+}
+
+pub struct WindowSwitcherPlugin {}
+
+impl WindowSwitcherPlugin {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    // Matching windows best first; without a query they stay in the order they were last used
+    fn window_results(&self, windows: Vec<WindowInfo>, query: &str) -> Vec<ResultItem> {
+        // the pattern trigger hands over the whole query, "win" included
+        let query = query.trim().split_once(char::is_whitespace).map_or("", |(_, rest)| rest.trim());
+        let matcher = SkimMatcherV2::default().ignore_case();
+        let mut matches: Vec<(i64, WindowInfo)> = windows
+            .into_iter()
+            .filter_map(|window| {
+                if query.is_empty() {
+                    return Some((0, window));
+                }
+                let score = [&window.title, &window.app].into_iter().filter_map(|text| matcher.fuzzy_match(text, query)).max()?;
+                Some((score, window))
+            })
+            .collect();
+        matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        // Enter only ever switches; closing and minimizing sit one step away on the best match
+        let more = matches.first().map(|(_, window)| {
+            let action = Action::ShowPluginView {
+                plugin_id: self.id().to_string(),
+                view_id: "window".to_string(),
+                data: serde_json::json!({ "id": window.id, "title": window.title, "app": window.app }),
+            };
+            ResultItem::new(format!("window actions {}", window.id), "Close or minimize…", action, self.id().to_string())
+                .with_subtitle(window.title.clone())
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::App))
+                .with_score(100.0 - matches.len() as f32)
+        });
+        matches
+            .into_iter()
+            .enumerate()
+            .map(|(i, (_, window))| {
+                let subtitle = if window.app.is_empty() { "Window".to_string() } else { window.app.clone() };
+                let icon = match &window.icon {
+                    Some(location) => ResultIcon::AppIcon(location.clone()),
+                    None => ResultIcon::BuiltIn(BuiltInIcon::App),
+                };
+                let action = command_action(Command::Focus, window.id, &window.title);
+                ResultItem::new(format!("window {}", window.id), window.title, action, self.id().to_string())
+                    .with_subtitle(subtitle)
+                    .with_icon(icon)
+                    .with_score(100.0 - i as f32)
+            })
+            .chain(more)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Plugin for WindowSwitcherPlugin {
+    fn id(&self) -> &str {
+        "window_switcher"
+    }
+    fn name(&self) -> &str {
+        "Window Switcher"
+    }
+    fn description(&self) -> &str {
+        "Switch to, close or minimize open windows."
+    }
+    fn trigger(&self) -> Trigger {
+        Trigger::Pattern(PATTERN.to_string())
+    }
+    async fn search(&self, query: &str, _context: &PluginContext) -> Vec<ResultItem> {
+        // a new connection every time, so a restarted window manager doesn't leave a dead one behind
+        let windows = tokio::task::spawn_blocking(|| window::backend()?.windows()).await;
+        match windows.map_err(anyhow::Error::from).and_then(|result| result) {
+            Ok(windows) => self.window_results(windows, query),
+            Err(e) => vec![
+                ResultItem::new("window error", "Can't list the open windows", Action::None, self.id().to_string())
+                    .with_subtitle(e.to_string())
+                    .with_icon(ResultIcon::BuiltIn(BuiltInIcon::App))
+                    .with_score(100.0)
+            ],
+        }
+    }
+    async fn view(&self, action: &Action, _context: &PluginContext) -> Option<PluginView> {
+        let Action::ShowPluginView { data, .. } = action else {
+            return None;
+        };
+        let id = data["id"].as_u64()?;
+        let title = data["title"].as_str().unwrap_or_default();
+        let app = data["app"].as_str().unwrap_or_default();
+        let row = |name: &str, label: &str, command: Command| {
+            ResultItem::new(format!("window {} {}", name, id), label.to_string(), command_action(command, id, title), self.id().to_string())
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::App))
+        };
+        let actions = vec![
+            row("focus", "Switch to window", Command::Focus),
+            row("minimize", "Minimize", Command::Minimize),
+            row("close", "Close", Command::Close),
+        ];
+        let body = if app.is_empty() { "Window".to_string() } else { app.to_string() };
+        Some(PluginView::detail(title.to_string(), body, actions))
+    }
+    async fn execute(&self, action: &Action, _context: &PluginContext) -> anyhow::Result<()> {
+        let Action::Custom { action_type, data } = action else {
+            return Ok(());
+        };
+        let Some(command) = Command::from_action_type(action_type) else {
+            return Ok(());
+        };
+        let id = data["id"].as_u64().ok_or_else(|| anyhow::anyhow!("No window to act on"))?;
+        tokio::task::spawn_blocking(move || perform(&*window::backend()?, command, id)).await??;
+        let done = match command {
+            Command::Focus => "Switched to",
+            Command::Close => "Closed",
+            Command::Minimize => "Minimized",
+        };
+        println!("{} {}", done, data["title"].as_str().unwrap_or_default());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct FakeWindows {
+        windows: Vec<WindowInfo>,
+        calls: Mutex<Vec<(Command, u64)>>,
+    }
+
+    impl WindowBackend for FakeWindows {
+        fn windows(&self) -> anyhow::Result<Vec<WindowInfo>> {
+            Ok(self.windows.clone())
+        }
+        fn focus(&self, id: u64) -> anyhow::Result<()> {
+            self.calls.lock().unwrap().push((Command::Focus, id));
+            Ok(())
+        }
+        fn close(&self, id: u64) -> anyhow::Result<()> {
+            self.calls.lock().unwrap().push((Command::Close, id));
+            Ok(())
+        }
+        fn minimize(&self, id: u64) -> anyhow::Result<()> {
+            self.calls.lock().unwrap().push((Command::Minimize, id));
+            Ok(())
+        }
+    }
+
+    fn window(id: u64, title: &str, app: &str) -> WindowInfo {
+        WindowInfo { id, title: title.to_string(), app: app.to_string(), ..Default::default() }
+    }
+
+    #[test]
+    fn test_window_results() {
+        let backend = FakeWindows {
+            windows: vec![
+                window(1, "Inbox - Mail", "thunderbird"),
+                window(2, "main.rs - crate - Visual Studio Code", "code"),
+                window(3, "Rust docs - Mozilla Firefox", "firefox"),
+            ],
+            calls: Mutex::new(Vec::new()),
+        };
+        let plugin = WindowSwitcherPlugin::new();
+        let titles = |query: &str| -> Vec<String> {
+            plugin.window_results(backend.windows().unwrap(), query)
+                .into_iter()
+                .filter(|r| !matches!(r.action, Action::ShowPluginView { .. }))
+                .map(|r| r.title.to_string())
+                .collect()
+        };
+        assert_eq!(titles("win").len(), 3);
+        assert_eq!(titles("win")[0], "Inbox - Mail");
+        assert_eq!(titles("win firefox"), ["Rust docs - Mozilla Firefox"]);
+        assert_eq!(titles("Win  thunder"), ["Inbox - Mail"]);
+        assert!(titles("win zzz").is_empty());
+
+        let trigger = plugin.trigger();
+        assert!(trigger.matches("win").is_some());
+        assert!(trigger.matches("win code").is_some());
+        assert_eq!(trigger.matches("wine"), None);
+        assert_eq!(trigger.matches("winscp"), None);
+
+        // "close" is just more of the title to look for, Enter still switches
+        assert!(titles("win close code").is_empty());
+        let run = |action: &Action| {
+            let Action::Custom { action_type, data } = action else {
+                panic!("expected a custom action");
+            };
+            let command = Command::from_action_type(action_type).unwrap();
+            perform(&backend, command, data["id"].as_u64().unwrap()).unwrap();
+        };
+        let results = plugin.window_results(backend.windows().unwrap(), "win code");
+        assert_eq!(results.len(), 2);
+        run(&results[0].action);
+
+        let config = crate::core::config::Config::default();
+        let view = futures::executor::block_on(plugin.view(&results[1].action, &PluginContext::new(&config))).unwrap();
+        let PluginView::Detail { actions, .. } = view else {
+            panic!("expected a detail view");
+        };
+        run(&actions[2].action);
+        assert_eq!(*backend.calls.lock().unwrap(), [(Command::Focus, 2), (Command::Close, 2)]);
+    }
+}
@@ -1,8 +1,22 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use async_trait::async_trait;
-use crate::core::plugin::{Plugin, PluginContext, Trigger};
+use crate::core::plugin::{Plugin, PluginContext, SettingField, Trigger};
 use crate::core::model::{Action, BuiltInIcon, ResultIcon, ResultItem};
-use crate::platform::windows::app_index::AppIndex;
+use crate::platform::shell;
+use crate::services::app_index::{split_arguments, AppIndex, Launch};
+
+// "code -- --new-window" searches for "code" and launches it with "--new-window"
+fn split_query(query: &str) -> (&str, Vec<String>) {
+    let query = query.trim();
+    if let Some(app) = query.strip_suffix(" --") {
+        return (app, Vec::new());
+    }
+    match query.split_once(" -- ") {
+        Some((app, args)) => (app, split_arguments(args)),
+        None => (query, Vec::new()),
+    }
+}
 
 pub struct AppSearchPlugin {
     index: Arc<AppIndex>
@@ -11,9 +25,31 @@ pub struct AppSearchPlugin {
 impl AppSearchPlugin {
     pub fn new() -> Self {
         Self {
-            index: Arc::new(AppIndex::build()),
+            index: AppIndex::start(),
         }
     }
+
+    // Offered for queries starting with "reload" or "app index", for apps the directory watching missed
+    fn reload_result(&self, query: &str) -> Option<ResultItem> {
+        let query = query.trim().to_lowercase();
+        if !query.starts_with("reload") && !query.starts_with("app index") {
+            return None;
+        }
+        Some(
+            ResultItem::new(
+                "app_search.reload",
+                "Reload app index",
+                Action::Custom {
+                    action_type: "app_search.reload".to_string(),
+                    data: serde_json::Value::Null,
+                },
+                self.id().to_string()
+            )
+            .with_subtitle("Scan the application folders again")
+            .with_icon(ResultIcon::BuiltIn(BuiltInIcon::App))
+            .with_score(50.0)
+        )
+    }
 }
 
 #[async_trait]
@@ -30,32 +66,74 @@ impl Plugin for AppSearchPlugin {
     fn trigger(&self) -> Trigger {
         Trigger::Implicit
     }
-    async fn search(&self, query: &str, _context: &PluginContext) -> Vec<ResultItem> {
-        let matches = self.index.search(query);
-        matches.into_iter().take(10).map(|app| {
+    // launch arguments are passed on as typed
+    fn keeps_case(&self) -> bool {
+        true
+    }
+    fn settings_schema(&self) -> Vec<SettingField> {
+        vec![SettingField::number("max_results", "Maximum apps shown", 10)]
+    }
+    async fn search(&self, query: &str, context: &PluginContext) -> Vec<ResultItem> {
+        let max_results = context.get_setting::<usize>(self.id(), "max_results").unwrap_or(10);
+        let aliases = context.get_setting::<HashMap<String, Vec<String>>>(self.id(), "aliases").unwrap_or_default();
+        let (query, extra_args) = split_query(query);
+        let matches = self.index.search(query, &aliases);
+        let mut results: Vec<ResultItem> = matches.into_iter().take(max_results).map(|(score, app)| {
             let icon = match &app.icon {
-                Some(path) => ResultIcon::Path(path.to_string_lossy().to_string()),
+                Some(location) => ResultIcon::AppIcon(location.clone()),
                 None => ResultIcon::BuiltIn(BuiltInIcon::App)
             };
+            // only programs take arguments, shortcuts and Store apps are started by the shell
+            let action = match &app.launch {
+                Launch::Run => {
+                    let mut args = app.args.clone();
+                    args.extend(extra_args.iter().cloned());
+                    Action::LaunchApp {
+                        path: app.path.clone(),
+                        args,
+                    }
+                }
+                Launch::Open => Action::OpenFile(app.path.clone()),
+                Launch::Url(url) => Action::OpenUrl(url.clone()),
+            };
+            let subtitle = if extra_args.is_empty() || app.launch != Launch::Run {
+                app.description.clone().unwrap_or_else(|| "Application".to_string())
+            } else {
+                format!("Launch with {}", extra_args.join(" "))
+            };
             ResultItem::new(
-                app.path.to_string_lossy().to_string(),
+                app.id.clone(),
                 app.name.clone(),
-                Action::LaunchApp {
-                    path: app.path.clone(),
-                    args: vec![],
-                },
+                action,
                 self.id().to_string()
             )
-            .with_subtitle("Application")
+            .with_subtitle(subtitle)
             .with_icon(icon)
-        }).collect()
+            // fuzzy scores run into the hundreds, this keeps good matches level with other plugins'
+            .with_score((score as f32 / 4.0).min(50.0))
+        }).collect();
+        if extra_args.is_empty() {
+            results.extend(self.reload_result(query));
+        }
+        results
     }
     async fn execute(&self, action: &Action, _context: &PluginContext) -> anyhow::Result<()> {
-        if let Action::LaunchApp {path, args} = action {
-            std::process::Command::new(path)
-                .args(args)
-                .spawn()?;
+        match action {
+            Action::LaunchApp {path, args} => {
+                std::process::Command::new(path)
+                    .args(args)
+                    .spawn()?;
+            }
+            Action::OpenFile(path) => shell::open(&path.to_string_lossy())?,
+            Action::OpenUrl(url) => shell::open(url)?,
+            Action::Custom { action_type, .. } if action_type == "app_search.reload" => {
+                let index = self.index.clone();
+                let count = tokio::task::spawn_blocking(move || index.rebuild()).await?;
+                println!("App index rebuilt with {} apps", count);
+            }
+            _ => {}
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
+
@@ -0,0 +1,336 @@
+// Currency conversion from a rates file in the config dir, e.g. "100 usd to eur" or "$20 in gbp".
+// The file is read at query time and never fetched there; an optional background fetcher keeps
+// it fresh from a configurable URL, and stale rates keep working offline.
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use isahc::config::Configurable;
+use isahc::ReadResponseExt;
+use serde_json::Value;
+
+use crate::core::config::config_dir;
+
+const SYMBOLS: &[(&str, &str)] = &[("$", "USD"), ("€", "EUR"), ("£", "GBP"), ("¥", "JPY"), ("₹", "INR"), ("₩", "KRW"), ("₽", "RUB")];
+
+// A failed download is tried again after this long, or after the refresh interval if that is shorter
+const RETRY_AFTER_FAILURE: Duration = Duration::from_secs(15 * 60);
+
+// Codes we recognise even without a rates file, to explain how to add one
+const COMMON_CODES: &[&str] = &[
+    "USD", "EUR", "GBP", "JPY", "CNY", "INR", "CHF", "CAD", "AUD", "NZD", "SEK", "NOK", "DKK", "PLN", "CZK",
+    "HUF", "RUB", "TRY", "BRL", "MXN", "ZAR", "KRW", "SGD", "HKD",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateTable {
+    pub base: String,
+    // units of each currency for one unit of `base`
+    pub rates: HashMap<String, f64>,
+    pub updated: Option<DateTime<Utc>>,
+}
+
+fn parse_timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Number(n) => DateTime::from_timestamp(n.as_i64()?, 0),
+        Value::String(s) => DateTime::parse_from_rfc3339(s)
+            .map(|t| t.with_timezone(&Utc))
+            .ok()
+            .or_else(|| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0).map(|t| t.and_utc())),
+        _ => None,
+    }
+}
+
+impl RateTable {
+    // {"base": "USD", "timestamp": 1760745600, "rates": {"EUR": 0.86}}, also the shapes of the
+    // common free rate APIs ("base_code", "date", "time_last_update_unix")
+    pub fn parse_json(content: &str) -> anyhow::Result<Self> {
+        let json: Value = serde_json::from_str(content)?;
+        let base = json.get("base").or_else(|| json.get("base_code")).and_then(|b| b.as_str()).unwrap_or("USD");
+        let rates = json
+            .get("rates")
+            .or_else(|| json.get("conversion_rates"))
+            .and_then(|r| r.as_object())
+            .ok_or_else(|| anyhow::anyhow!("rates file has no \"rates\" object"))?;
+        let mut table = RateTable {
+            base: base.to_uppercase(),
+            rates: rates
+                .iter()
+                .filter_map(|(code, rate)| Some((code.to_uppercase(), rate.as_f64().filter(|r| *r > 0.0)?)))
+                .collect(),
+            updated: ["timestamp", "time_last_update_unix", "updated", "date"]
+                .iter()
+                .find_map(|key| json.get(*key).and_then(parse_timestamp)),
+        };
+        table.rates.insert(table.base.clone(), 1.0);
+        Ok(table)
+    }
+
+    // currency,rate lines, "# updated: 2026-10-18" sets the date. "# base: EUR" names the base,
+    // otherwise it is the first row with rate 1; pegged currencies can have rate 1 as well.
+    pub fn parse_csv(content: &str) -> anyhow::Result<Self> {
+        let mut rates = HashMap::new();
+        let mut updated = None;
+        let mut base: Option<String> = None;
+        let mut first_at_one: Option<String> = None;
+        for line in content.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+            if let Some(comment) = line.strip_prefix('#') {
+                if let Some((key, value)) = comment.split_once(':') {
+                    match key.trim().to_lowercase().as_str() {
+                        "updated" => updated = parse_timestamp(&Value::from(value.trim())),
+                        "base" => base = Some(value.trim().to_uppercase()),
+                        _ => {}
+                    }
+                }
+                continue;
+            }
+            let Some((code, rate)) = line.split_once(',') else {
+                continue;
+            };
+            // skips the "currency,rate" header
+            if let Ok(rate) = rate.trim().parse::<f64>() {
+                let code = code.trim().to_uppercase();
+                if rate == 1.0 && first_at_one.is_none() {
+                    first_at_one = Some(code.clone());
+                }
+                rates.insert(code, rate);
+            }
+        }
+        let base = base
+            .or(first_at_one)
+            .ok_or_else(|| anyhow::anyhow!("rates file needs a base currency with rate 1"))?;
+        if rates.get(&base) != Some(&1.0) {
+            anyhow::bail!("base currency {} needs rate 1", base);
+        }
+        Ok(RateTable { base, rates, updated })
+    }
+
+    pub fn convert(&self, amount: f64, from: &str, to: &str) -> Option<f64> {
+        let from = self.rates.get(from)?;
+        let to = self.rates.get(to)?;
+        Some(amount / from * to)
+    }
+}
+
+fn currency_code(text: &str) -> Option<String> {
+    let text = text.trim();
+    if let Some((_, code)) = SYMBOLS.iter().find(|(symbol, _)| *symbol == text) {
+        return Some(code.to_string());
+    }
+    (text.len() == 3 && text.chars().all(|c| c.is_ascii_alphabetic())).then(|| text.to_uppercase())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurrencyQuery {
+    pub amount: f64,
+    pub from: String,
+    pub to: String,
+}
+
+// "100 usd to eur", "$20 in gbp", "2*15 EUR as CHF" or just "usd to eur"
+pub fn parse_query(query: &str) -> Option<CurrencyQuery> {
+    let words: Vec<&str> = query.split_whitespace().collect();
+    let i = words.iter().rposition(|w| matches!(w.to_lowercase().as_str(), "to" | "in" | "as"))?;
+    if i == 0 || i + 2 != words.len() {
+        return None;
+    }
+    let to = currency_code(words[i + 1])?;
+    let left = words[..i].join(" ");
+    let (amount, from) = if let Some((symbol, code)) = SYMBOLS.iter().find(|(symbol, _)| left.starts_with(*symbol)) {
+        (left[symbol.len()..].trim().to_string(), code.to_string())
+    } else {
+        let split = left.rfind(|c: char| !c.is_ascii_alphabetic()).map_or(0, |i| i + 1);
+        (left[..split].trim().to_string(), currency_code(&left[split..])?)
+    };
+    let amount = if amount.is_empty() { 1.0 } else { meval::eval_str(&amount).ok()? };
+    Some(CurrencyQuery { amount, from, to })
+}
+
+// Two decimals like prices, more for amounts smaller than a cent
+pub fn format_amount(value: f64) -> String {
+    if value.abs() >= 0.01 || value == 0.0 {
+        format!("{:.2}", value)
+    } else {
+        super::units::format_number(value)
+    }
+}
+
+pub fn is_common_code(code: &str) -> bool {
+    COMMON_CODES.contains(&code)
+}
+
+// Rates from config_dir()/currency_rates.json (or .csv), reloaded when the file changes
+#[derive(Default)]
+pub struct CurrencyRates {
+    table: Mutex<Option<(SystemTime, PathBuf, RateTable)>>,
+    fetching: Arc<AtomicBool>,
+    // when the last download finished and whether it worked
+    last_attempt: Arc<Mutex<Option<(Instant, bool)>>>,
+}
+
+// Rates published less often than `max_age`, or a server that is down, would otherwise start a
+// download on every keystroke of a currency query
+fn attempt_due(last_attempt: Option<(Instant, bool)>, max_age: Duration) -> bool {
+    match last_attempt {
+        None => true,
+        Some((at, true)) => at.elapsed() >= max_age,
+        Some((at, false)) => at.elapsed() >= max_age.min(RETRY_AFTER_FAILURE),
+    }
+}
+
+impl CurrencyRates {
+    pub fn json_path() -> PathBuf {
+        config_dir().join("currency_rates.json")
+    }
+
+    fn csv_path() -> PathBuf {
+        config_dir().join("currency_rates.csv")
+    }
+
+    fn load(path: &PathBuf) -> anyhow::Result<RateTable> {
+        let content = std::fs::read_to_string(path)?;
+        let mut table = if path.extension().is_some_and(|e| e == "csv") {
+            RateTable::parse_csv(&content)?
+        } else {
+            RateTable::parse_json(&content)?
+        };
+        if table.updated.is_none() {
+            table.updated = std::fs::metadata(path).and_then(|m| m.modified()).ok().map(DateTime::<Utc>::from);
+        }
+        Ok(table)
+    }
+
+    pub fn table(&self) -> Option<RateTable> {
+        let path = [Self::json_path(), Self::csv_path()].into_iter().find(|p| p.exists())?;
+        let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+        let mut cached = self.table.lock().unwrap();
+        if let Some((loaded, loaded_path, table)) = cached.as_ref() {
+            if *loaded == modified && *loaded_path == path {
+                return Some(table.clone());
+            }
+        }
+        match Self::load(&path) {
+            Ok(table) => {
+                *cached = Some((modified, path, table.clone()));
+                Some(table)
+            }
+            Err(e) => {
+                log::warn!("Failed to read currency rates from {}: {}", path.display(), e);
+                // keep using the last good rates
+                cached.as_ref().map(|(_, _, table)| table.clone())
+            }
+        }
+    }
+
+    // Downloads new rates on a background thread when the file is older than `max_age` and the
+    // last download is too
+    pub fn refresh_if_stale(&self, url: &str, max_age: Duration) {
+        if url.is_empty() {
+            return;
+        }
+        let age = self
+            .table()
+            .and_then(|t| t.updated)
+            .and_then(|updated| (Utc::now() - updated).to_std().ok());
+        if age.is_some_and(|age| age < max_age) || !attempt_due(*self.last_attempt.lock().unwrap(), max_age) {
+            return;
+        }
+        if self.fetching.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let fetching = self.fetching.clone();
+        let last_attempt = self.last_attempt.clone();
+        let url = url.to_string();
+        std::thread::spawn(move || {
+            let result = fetch(&url);
+            match &result {
+                Ok(()) => log::info!("Currency rates updated from {}", url),
+                Err(e) => log::warn!("Failed to update currency rates from {}: {}", url, e),
+            }
+            *last_attempt.lock().unwrap() = Some((Instant::now(), result.is_ok()));
+            fetching.store(false, Ordering::SeqCst);
+        });
+    }
+}
+
+fn fetch(url: &str) -> anyhow::Result<()> {
+    let request = isahc::Request::get(url).timeout(Duration::from_secs(10)).body(())?;
+    let mut response = isahc::send(request)?;
+    if !response.status().is_success() {
+        anyhow::bail!("server returned {}", response.status());
+    }
+    let content = response.text()?;
+    let mut table = RateTable::parse_json(&content)?;
+    if table.rates.len() < 2 {
+        anyhow::bail!("response has no rates");
+    }
+    table.updated.get_or_insert_with(Utc::now);
+    let json = serde_json::json!({
+        "base": table.base,
+        "timestamp": table.updated.map(|t| t.timestamp()),
+        "rates": table.rates,
+    });
+    // write next to the file and rename, so a failed write never leaves broken rates behind
+    let path = CurrencyRates::json_path();
+    std::fs::create_dir_all(config_dir())?;
+    let temp = path.with_extension("json.tmp");
+    std::fs::write(&temp, serde_json::to_string_pretty(&json)?)?;
+    std::fs::rename(temp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query() {
+        let query = parse_query("100 usd to eur").unwrap();
+        assert_eq!((query.amount, query.from.as_str(), query.to.as_str()), (100.0, "USD", "EUR"));
+        let query = parse_query("$2*10 in gbp").unwrap();
+        assert_eq!((query.amount, query.from.as_str(), query.to.as_str()), (20.0, "USD", "GBP"));
+        assert_eq!(parse_query("eur as chf").unwrap().amount, 1.0);
+        assert_eq!(parse_query("5 km to mi").map(|q| q.to), None);
+        assert_eq!(parse_query("how to cook"), None);
+    }
+
+    #[test]
+    fn test_json_and_csv_tables() {
+        let json = RateTable::parse_json(r#"{"base_code": "USD", "time_last_update_unix": 1760745600, "rates": {"EUR": 0.8, "GBP": 0.5}}"#).unwrap();
+        assert_eq!(json.convert(100.0, "USD", "EUR"), Some(80.0));
+        assert_eq!(json.convert(100.0, "EUR", "GBP"), Some(62.5));
+        assert_eq!(json.updated.unwrap().timestamp(), 1760745600);
+
+        let csv = RateTable::parse_csv("# updated: 2026-10-18\ncurrency,rate\nEUR,1\nusd,1.25\n").unwrap();
+        assert_eq!(csv.base, "EUR");
+        assert_eq!(csv.convert(10.0, "EUR", "USD"), Some(12.5));
+        assert_eq!(csv.updated.unwrap().date_naive().to_string(), "2026-10-18");
+        assert!(RateTable::parse_csv("usd,2\n").is_err());
+
+        // the first rate-1 row in the file, or the one the header names
+        let pegged = "EUR,1\nUSD,1.25\nXOF,655.957\nBAM,1\nAED,1\n";
+        for _ in 0..10 {
+            assert_eq!(RateTable::parse_csv(pegged).unwrap().base, "EUR");
+        }
+        let named = RateTable::parse_csv("# base: usd\nAED,3.6725\nUSD,1\nPAB,1\n").unwrap();
+        assert_eq!(named.base, "USD");
+        assert!(RateTable::parse_csv("# base: GBP\nEUR,1\nGBP,0.87\n").is_err());
+    }
+
+    #[test]
+    fn test_refresh_backoff() {
+        let hour = Duration::from_secs(3600);
+        assert!(attempt_due(None, hour));
+        assert!(!attempt_due(Some((Instant::now(), true)), hour));
+        assert!(!attempt_due(Some((Instant::now(), false)), hour));
+        // Instant can't go back further than the machine's uptime
+        if let Some(long_ago) = Instant::now().checked_sub(RETRY_AFTER_FAILURE) {
+            assert!(attempt_due(Some((long_ago, false)), hour));
+            assert!(!attempt_due(Some((long_ago, true)), hour));
+            assert!(attempt_due(Some((long_ago, true)), Duration::from_secs(60)));
+        }
+    }
+}
\ No newline at end of file
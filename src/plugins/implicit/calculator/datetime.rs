@@ -0,0 +1,410 @@
+// Date and time expressions like "now + 3 weeks", "days until 2026-12-25", "unix 1700000000",
+// "3pm PST in IST" or "2026-10-18 - 2026-01-01". Time zones come from the tz database bundled
+// with chrono-tz, so nothing here needs the network.
+use chrono::{DateTime, Duration, FixedOffset, Local, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use chrono_tz::{Tz, TZ_VARIANTS};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DateValue {
+    Instant {
+        time: DateTime<FixedOffset>,
+        // "CET", "UTC" or "local"
+        zone: String,
+    },
+    Duration(Duration),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Zone {
+    Local,
+    Named(Tz),
+    // an abbreviation that names one side of daylight saving time, like "PST" or "CEST"
+    Fixed(FixedOffset, &'static str),
+}
+
+// Abbreviations for a region rather than a season, these follow daylight saving time
+const REGIONS: &[(&str, &str)] = &[
+    ("pt", "America/Los_Angeles"),
+    ("mt", "America/Denver"),
+    ("ct", "America/Chicago"),
+    ("et", "America/New_York"),
+];
+
+// Abbreviations that pin the offset, in minutes east of UTC: PST is UTC−8 even in October
+const OFFSETS: &[(&str, i32)] = &[
+    ("utc", 0),
+    ("gmt", 0),
+    ("z", 0),
+    ("pst", -8 * 60),
+    ("pdt", -7 * 60),
+    ("mst", -7 * 60),
+    ("mdt", -6 * 60),
+    ("cst", -6 * 60),
+    ("cdt", -5 * 60),
+    ("est", -5 * 60),
+    ("edt", -4 * 60),
+    ("bst", 60),
+    ("cet", 60),
+    ("cest", 2 * 60),
+    ("eet", 2 * 60),
+    ("eest", 3 * 60),
+    ("msk", 3 * 60),
+    ("ist", 5 * 60 + 30),
+    ("sgt", 8 * 60),
+    ("hkt", 8 * 60),
+    ("jst", 9 * 60),
+    ("kst", 9 * 60),
+    ("aest", 10 * 60),
+    ("aedt", 11 * 60),
+    ("nzst", 12 * 60),
+    ("nzdt", 13 * 60),
+];
+
+fn parse_zone(text: &str) -> Option<Zone> {
+    let name = text.trim().to_lowercase();
+    if name == "local" {
+        return Some(Zone::Local);
+    }
+    if let Some((abbreviation, minutes)) = OFFSETS.iter().find(|(abbreviation, _)| *abbreviation == name) {
+        return FixedOffset::east_opt(minutes * 60).map(|offset| Zone::Fixed(offset, abbreviation));
+    }
+    if let Some((_, zone)) = REGIONS.iter().find(|(abbreviation, _)| *abbreviation == name) {
+        return zone.parse::<Tz>().ok().map(Zone::Named);
+    }
+    // "Europe/Berlin", or just the city: "berlin", "new york"
+    let city = name.replace(' ', "_");
+    TZ_VARIANTS
+        .iter()
+        .find(|tz| tz.name().eq_ignore_ascii_case(&name))
+        .or_else(|| {
+            TZ_VARIANTS
+                .iter()
+                .find(|tz| tz.name().rsplit('/').next().is_some_and(|last| last.eq_ignore_ascii_case(&city)))
+        })
+        .map(|tz| Zone::Named(*tz))
+}
+
+fn in_zone(time: DateTime<FixedOffset>, zone: Zone) -> DateValue {
+    match zone {
+        Zone::Local => DateValue::Instant { time, zone: "local".to_string() },
+        Zone::Named(tz) => {
+            let time = time.with_timezone(&tz);
+            DateValue::Instant { zone: time.format("%Z").to_string(), time: time.fixed_offset() }
+        }
+        Zone::Fixed(offset, abbreviation) => {
+            DateValue::Instant { time: time.with_timezone(&offset), zone: abbreviation.to_uppercase() }
+        }
+    }
+}
+
+// The offset in effect on that date, so "2026-12-25" in Berlin is +01:00 even in summer
+fn localize(naive: NaiveDateTime, zone: Zone) -> Option<DateTime<FixedOffset>> {
+    match zone {
+        Zone::Local => Local.from_local_datetime(&naive).earliest().map(|t| t.fixed_offset()),
+        Zone::Named(tz) => tz.from_local_datetime(&naive).earliest().map(|t| t.fixed_offset()),
+        Zone::Fixed(offset, _) => offset.from_local_datetime(&naive).single(),
+    }
+}
+
+// "3pm", "3:30 pm", "15:00" or "15:00:30"
+fn parse_time(text: &str) -> Option<NaiveTime> {
+    let text = text.trim().to_lowercase().replace(' ', "");
+    let (clock, pm) = if let Some(clock) = text.strip_suffix("pm") {
+        (clock.to_string(), Some(true))
+    } else if let Some(clock) = text.strip_suffix("am") {
+        (clock.to_string(), Some(false))
+    } else {
+        (text, None)
+    };
+    let mut parts = clock.split(':');
+    let hour: u32 = parts.next()?.parse().ok()?;
+    let minute: u32 = parts.next().map_or(Some(0), |m| m.parse().ok())?;
+    let second: u32 = parts.next().map_or(Some(0), |s| s.parse().ok())?;
+    if parts.next().is_some() {
+        return None;
+    }
+    let hour = match pm {
+        Some(_) if hour == 0 || hour > 12 => return None,
+        Some(true) => hour % 12 + 12,
+        Some(false) => hour % 12,
+        // a bare number is not a time
+        None if !clock.contains(':') => return None,
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, second)
+}
+
+fn parse_date(text: &str) -> Option<NaiveDateTime> {
+    let text = text.trim();
+    for format in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"] {
+        if let Ok(time) = NaiveDateTime::parse_from_str(text, format) {
+            return Some(time);
+        }
+    }
+    NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0)
+}
+
+// A single point in time with an optional trailing zone, e.g. "now", "2026-12-25", "3pm PST"
+fn parse_base(text: &str, now: DateTime<FixedOffset>) -> Option<(DateTime<FixedOffset>, Zone)> {
+    let text = text.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+        return Some((time, Zone::Local));
+    }
+    let (body, zone) = match text.rsplit_once(' ') {
+        Some((body, zone)) => match parse_zone(zone) {
+            Some(zone) => (body, zone),
+            None => (text, Zone::Local),
+        },
+        None => (text, Zone::Local),
+    };
+    let today = match zone {
+        Zone::Local => now.date_naive(),
+        Zone::Named(tz) => now.with_timezone(&tz).date_naive(),
+        Zone::Fixed(offset, _) => now.with_timezone(&offset).date_naive(),
+    };
+    let naive = match body.to_lowercase().as_str() {
+        "now" => return Some((now, zone)),
+        "today" => today.and_hms_opt(0, 0, 0)?,
+        "tomorrow" => today.succ_opt()?.and_hms_opt(0, 0, 0)?,
+        "yesterday" => today.pred_opt()?.and_hms_opt(0, 0, 0)?,
+        _ => match parse_date(body) {
+            Some(naive) => naive,
+            None => today.and_time(parse_time(body)?),
+        },
+    };
+    Some((localize(naive, zone)?, zone))
+}
+
+// "+ 3 weeks - 2d" applied to a point in time
+fn apply_offsets(mut time: DateTime<FixedOffset>, text: &str) -> Option<DateTime<FixedOffset>> {
+    let mut rest = text.trim();
+    while !rest.is_empty() {
+        let negative = match rest.chars().next()? {
+            '+' => false,
+            '-' => true,
+            _ => return None,
+        };
+        rest = rest[1..].trim_start();
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let amount: i64 = rest[..digits].parse().ok()?;
+        rest = rest[digits..].trim_start();
+        let unit_len = rest.find(|c: char| !c.is_alphabetic()).unwrap_or(rest.len());
+        let unit = rest[..unit_len].to_lowercase();
+        rest = rest[unit_len..].trim_start();
+        let amount = if negative { -amount } else { amount };
+        time = match unit.as_str() {
+            "s" | "sec" | "secs" | "second" | "seconds" => time + Duration::try_seconds(amount)?,
+            "m" | "min" | "mins" | "minute" | "minutes" => time + Duration::try_minutes(amount)?,
+            "h" | "hr" | "hrs" | "hour" | "hours" => time + Duration::try_hours(amount)?,
+            "d" | "day" | "days" => time + Duration::try_days(amount)?,
+            "w" | "wk" | "week" | "weeks" => time + Duration::try_weeks(amount)?,
+            "mo" | "month" | "months" | "y" | "yr" | "year" | "years" => {
+                let months = if unit.starts_with('m') { amount } else { amount.checked_mul(12)? };
+                let months = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+                if amount < 0 {
+                    time.checked_sub_months(months)?
+                } else {
+                    time.checked_add_months(months)?
+                }
+            }
+            _ => return None,
+        };
+    }
+    Some(time)
+}
+
+// A point in time followed by offsets, "2026-10-18 14:00 + 90 days"
+fn parse_instant(text: &str, now: DateTime<FixedOffset>) -> Option<(DateTime<FixedOffset>, Zone)> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    for count in (1..=words.len()).rev() {
+        let rest = words[count..].join(" ");
+        if !rest.is_empty() && !rest.starts_with(['+', '-']) {
+            continue;
+        }
+        if let Some((time, zone)) = parse_base(&words[..count].join(" "), now) {
+            return Some((apply_offsets(time, &rest)?, zone));
+        }
+    }
+    None
+}
+
+fn is_date_query(text: &str) -> bool {
+    let lower = text.to_lowercase();
+    ["now", "today", "tomorrow", "yesterday"].iter().any(|word| lower.split_whitespace().any(|w| w == *word))
+        || parse_date(text.split_whitespace().next().unwrap_or_default()).is_some()
+        || parse_date(text).is_some()
+}
+
+pub fn evaluate(query: &str, now: DateTime<FixedOffset>) -> Option<DateValue> {
+    let query = query.trim();
+    let lower = query.to_lowercase();
+    let words: Vec<&str> = lower.split_whitespace().collect();
+
+    // unix 1700000000, epoch 1700000000000
+    if let [keyword, value] = words.as_slice() {
+        if matches!(*keyword, "unix" | "epoch" | "timestamp") {
+            let value: i64 = value.parse().ok()?;
+            // 13 digits are milliseconds
+            let time = if value.abs() >= 100_000_000_000 {
+                DateTime::from_timestamp_millis(value)?
+            } else {
+                DateTime::from_timestamp(value, 0)?
+            };
+            return Some(in_zone(time.fixed_offset(), Zone::Named(Tz::UTC)));
+        }
+    }
+
+    // days until 2026-12-25, time since 2026-01-01
+    if let [_, direction, ..] = words.as_slice() {
+        if matches!(*direction, "until" | "till" | "since") {
+            let target = query.splitn(3, char::is_whitespace).nth(2)?;
+            let (time, _) = parse_instant(target, now)?;
+            let duration = if *direction == "since" { now - time } else { time - now };
+            return Some(DateValue::Duration(duration));
+        }
+    }
+
+    // 3pm PST in IST, now in Tokyo
+    for (i, word) in words.iter().enumerate().rev() {
+        if !matches!(*word, "in" | "to") || i == 0 {
+            continue;
+        }
+        let target = query.split_whitespace().skip(i + 1).collect::<Vec<_>>().join(" ");
+        let Some(zone) = parse_zone(&target) else {
+            continue;
+        };
+        let source = query.split_whitespace().take(i).collect::<Vec<_>>().join(" ");
+        let (time, _) = parse_instant(&source, now)?;
+        return Some(in_zone(time, zone));
+    }
+
+    // 2026-10-18 - 2026-01-01
+    if let Some(index) = query.find(" - ") {
+        let (left, right) = (&query[..index], &query[index + 3..]);
+        if is_date_query(right) {
+            if let (Some((left, _)), Some((right, _))) = (parse_instant(left, now), parse_instant(right, now)) {
+                return Some(DateValue::Duration(left - right));
+            }
+        }
+    }
+
+    if !is_date_query(query) && parse_time(query.split_whitespace().next().unwrap_or_default()).is_none() {
+        return None;
+    }
+    let (time, zone) = parse_instant(query, now)?;
+    Some(in_zone(time, zone))
+}
+
+// "9 weeks, 5 days" or "3 hours, 20 minutes"
+pub fn describe_duration(duration: Duration) -> String {
+    let sign = if duration < Duration::zero() { "-" } else { "" };
+    let seconds = duration.num_seconds().unsigned_abs();
+    let parts = [
+        (seconds / 86_400 / 7, "week"),
+        (seconds / 86_400 % 7, "day"),
+        (seconds / 3600 % 24, "hour"),
+        (seconds / 60 % 60, "minute"),
+        (seconds % 60, "second"),
+    ];
+    let described: Vec<String> = parts
+        .iter()
+        .filter(|(value, _)| *value > 0)
+        .take(2)
+        .map(|(value, unit)| format!("{} {}{}", value, unit, if *value == 1 { "" } else { "s" }))
+        .collect();
+    if described.is_empty() {
+        return "0 seconds".to_string();
+    }
+    format!("{}{}", sign, described.join(", "))
+}
+
+// ISO 8601 duration, e.g. P68DT3H
+pub fn iso_duration(duration: Duration) -> String {
+    let sign = if duration < Duration::zero() { "-" } else { "" };
+    let seconds = duration.num_seconds().unsigned_abs();
+    let (days, hours, minutes, seconds) = (seconds / 86_400, seconds / 3600 % 24, seconds / 60 % 60, seconds % 60);
+    let mut iso = format!("{}P", sign);
+    if days > 0 {
+        iso.push_str(&format!("{}D", days));
+    }
+    if hours + minutes + seconds > 0 {
+        iso.push('T');
+        for (value, unit) in [(hours, 'H'), (minutes, 'M'), (seconds, 'S')] {
+            if value > 0 {
+                iso.push_str(&format!("{}{}", value, unit));
+            }
+        }
+    }
+    if iso.ends_with('P') {
+        iso.push_str("T0S");
+    }
+    iso
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339("2026-10-19T10:00:00+02:00").unwrap()
+    }
+
+    fn instant(query: &str) -> (String, String) {
+        match evaluate(query, now()) {
+            Some(DateValue::Instant { time, zone }) => (time.to_rfc3339(), zone),
+            other => panic!("{} gave {:?}", query, other),
+        }
+    }
+
+    // Dates without a zone are in the machine's zone, with the offset of that date
+    fn local(naive: &str) -> String {
+        let naive = NaiveDateTime::parse_from_str(naive, "%Y-%m-%d %H:%M").unwrap();
+        Local.from_local_datetime(&naive).earliest().unwrap().to_rfc3339()
+    }
+
+    fn duration(query: &str) -> Duration {
+        match evaluate(query, now()) {
+            Some(DateValue::Duration(duration)) => duration,
+            other => panic!("{} gave {:?}", query, other),
+        }
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(instant("now + 3 weeks").0, "2026-11-09T10:00:00+02:00");
+        assert_eq!(instant("tomorrow").0, local("2026-10-20 00:00"));
+        assert_eq!(instant("2026-01-31 + 1 month").0, local("2026-02-28 00:00"));
+        assert_eq!(instant("2026-10-18 14:00 - 2h + 30min").0, local("2026-10-18 12:30"));
+        assert_eq!(instant("2026-07-01").0, local("2026-07-01 00:00"));
+        assert_eq!(instant("2026-12-25").0, local("2026-12-25 00:00"));
+    }
+
+    #[test]
+    fn test_differences() {
+        assert_eq!(duration("days until 2026-12-25").num_days(), 66);
+        // a daylight saving change in between adds or takes an hour
+        assert_eq!((duration("2026-10-18 - 2026-01-01").num_hours() + 12).div_euclid(24), 290);
+        assert_eq!(describe_duration(Duration::days(68) + Duration::hours(3)), "9 weeks, 5 days");
+        assert_eq!(iso_duration(Duration::days(68) + Duration::hours(3)), "P68DT3H");
+    }
+
+    #[test]
+    fn test_unix_and_zones() {
+        assert_eq!(instant("unix 1700000000"), ("2023-11-14T22:13:20+00:00".to_string(), "UTC".to_string()));
+        // PST is UTC−8 as written, PT follows Los Angeles onto daylight time in October
+        assert_eq!(instant("3pm PST in IST"), ("2026-10-20T04:30:00+05:30".to_string(), "IST".to_string()));
+        assert_eq!(instant("3pm PT in IST"), ("2026-10-20T03:30:00+05:30".to_string(), "IST".to_string()));
+        assert_eq!(instant("3pm PDT in IST").0, "2026-10-20T03:30:00+05:30");
+        assert_eq!(instant("12:00 cet in est"), ("2026-10-19T06:00:00-05:00".to_string(), "EST".to_string()));
+        assert_eq!(instant("15:00 utc to berlin"), ("2026-10-19T17:00:00+02:00".to_string(), "CEST".to_string()));
+        assert_eq!(instant("now in new york").0, "2026-10-19T04:00:00-04:00");
+    }
+
+    #[test]
+    fn test_ignores_other_queries() {
+        assert_eq!(evaluate("2 + 3", now()), None);
+        assert_eq!(evaluate("5 km in mi", now()), None);
+        assert_eq!(evaluate("how to cook rice", now()), None);
+        assert_eq!(evaluate("100 - 50", now()), None);
+    }
+}
\ No newline at end of file
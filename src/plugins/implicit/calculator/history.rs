@@ -0,0 +1,168 @@
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+
+use crate::core::plugin::{Plugin, PluginContext, Trigger};
+use crate::core::model::{Action, BuiltInIcon, ResultIcon, ResultItem};
+
+use super::session::Session;
+use super::units::format_number;
+
+// "calc" on its own, so "calculator" and "calcium" still reach the other plugins
+const PATTERN: &str = r"(?i)^calc(\s|$)";
+
+// `calc` lists previous calculations and session variables, Enter puts them back in the search bar
+pub struct CalculatorHistoryPlugin {
+    session: Arc<Mutex<Session>>,
+}
+
+impl CalculatorHistoryPlugin {
+    pub fn new(session: Arc<Mutex<Session>>) -> Self {
+        Self { session }
+    }
+}
+
+#[async_trait]
+impl Plugin for CalculatorHistoryPlugin {
+    fn id(&self) -> &str {
+        "calculator_history"
+    }
+    fn name(&self) -> &str {
+        "Calculator History"
+    }
+    fn description(&self) -> &str {
+        "A plugin for reusing previous calculations."
+    }
+    fn trigger(&self) -> Trigger {
+        Trigger::Pattern(PATTERN.to_string())
+    }
+    async fn search(&self, query: &str, _context: &PluginContext) -> Vec<ResultItem> {
+        let mut results = Vec::new();
+        let session = self.session.lock().unwrap();
+        // the pattern trigger hands over the whole query, "calc" included
+        let filter = query.trim().split_once(char::is_whitespace).map_or("", |(_, rest)| rest.trim()).to_lowercase();
+
+        let mut variables: Vec<(&String, &f64)> = session.variables.iter().collect();
+        variables.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, value) in variables {
+            if !name.to_lowercase().contains(&filter) {
+                continue;
+            }
+            results.push(
+                ResultItem::new(
+                    format!("calculator variable {}", name),
+                    format!("{} = {}", name, format_number(*value)),
+                    Action::InsertText(name.clone()),
+                    self.id().to_string()
+                )
+                .with_subtitle("Variable")
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Calculator))
+                .with_score(60.0)
+            );
+        }
+        let mut functions: Vec<_> = session.functions.iter().collect();
+        functions.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, function) in functions {
+            if !name.to_lowercase().contains(&filter) {
+                continue;
+            }
+            let signature = format!("{}({})", name, function.params.join(", "));
+            results.push(
+                ResultItem::new(
+                    format!("calculator function {}", name),
+                    format!("{} = {}", signature, function.body),
+                    Action::InsertText(format!("{}(", name)),
+                    self.id().to_string()
+                )
+                .with_subtitle("Function")
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Calculator))
+                .with_score(60.0)
+            );
+        }
+
+        let count = session.history.len();
+        for (i, entry) in session.history.iter().enumerate() {
+            if !entry.expression.to_lowercase().contains(&filter) {
+                continue;
+            }
+            results.push(
+                ResultItem::new(
+                    format!("calculator history {}", i),
+                    entry.expression.clone(),
+                    Action::InsertText(entry.expression.clone()),
+                    self.id().to_string()
+                )
+                .with_subtitle(format!("= {}", entry.result))
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Calculator))
+                // newest first
+                .with_score(50.0 - 40.0 * i as f32 / count as f32)
+            );
+        }
+
+        if count > 0 && "clear".starts_with(&filter) && !filter.is_empty() {
+            results.push(
+                ResultItem::new(
+                    "calculator clear history",
+                    "Clear calculator history",
+                    Action::Custom {
+                        action_type: "calculator.clear_history".to_string(),
+                        data: serde_json::Value::Null,
+                    },
+                    self.id().to_string()
+                )
+                .with_subtitle(format!("Remove {} saved calculations", count))
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Exit))
+                .with_score(100.0)
+            );
+        }
+        if results.is_empty() {
+            results.push(
+                ResultItem::new(
+                    "calculator history empty",
+                    "No calculations yet",
+                    Action::None,
+                    self.id().to_string()
+                )
+                .with_subtitle("Copied calculator results show up here")
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Calculator))
+            );
+        }
+        results
+    }
+    async fn execute(&self, action: &Action, _context: &PluginContext) -> anyhow::Result<()> {
+        if let Action::Custom { action_type, .. } = action {
+            if action_type == "calculator.clear_history" {
+                self.session.lock().unwrap().clear_history();
+                println!("Calculator history cleared");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calc_keyword() {
+        let mut session = Session::new(Vec::new(), None);
+        session.variables.insert("rate".to_string(), 0.2);
+        session.variables.insert("width".to_string(), 3.0);
+        let plugin = CalculatorHistoryPlugin::new(Arc::new(Mutex::new(session)));
+        let trigger = plugin.trigger();
+        assert_eq!(trigger.matches("calc"), Some("calc".to_string()));
+        assert_eq!(trigger.matches("Calc rate"), Some("Calc rate".to_string()));
+        assert_eq!(trigger.matches("calculator"), None);
+        assert_eq!(trigger.matches("calcium"), None);
+
+        let config = crate::core::config::Config::default();
+        let titles = |query: &str| -> Vec<String> {
+            futures::executor::block_on(plugin.search(query, &PluginContext::new(&config)))
+                .into_iter()
+                .map(|r| r.title.to_string())
+                .collect()
+        };
+        assert_eq!(titles("calc"), ["rate = 0.2", "width = 3"]);
+        assert_eq!(titles("calc wid"), ["width = 3"]);
+    }
+}
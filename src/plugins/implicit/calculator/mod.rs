@@ -0,0 +1,271 @@
+use std::result;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use async_trait::async_trait;
+use arboard::Clipboard;
+use num_traits::ToPrimitive;
+use thousands::Separable;
+
+use crate::core::plugin::{Plugin, PluginContext, SettingField, Trigger};
+use crate::core::model::{Action, BuiltInIcon, ResultIcon, ResultItem};
+
+pub mod currency;
+pub mod datetime;
+pub mod history;
+pub mod programmer;
+pub mod session;
+pub mod units;
+
+use currency::CurrencyRates;
+use datetime::DateValue;
+use history::CalculatorHistoryPlugin;
+use session::{parse_assignment, Assignment, Session};
+use units::format_number;
+
+pub struct CalculatorPlugin {
+    session: Arc<Mutex<Session>>,
+    rates: CurrencyRates,
+}
+
+impl CalculatorPlugin {
+    pub fn new() -> Self {
+        Self {
+            session: Arc::new(Mutex::new(Session::load())),
+            rates: CurrencyRates::default(),
+        }
+    }
+    // The `calc` keyword plugin, sharing this calculator's session
+    pub fn history_plugin(&self) -> CalculatorHistoryPlugin {
+        CalculatorHistoryPlugin::new(self.session.clone())
+    }
+    fn assignment_result(&self, query: &str, assignment: &Assignment, session: &Session) -> Option<ResultItem> {
+        let preview = session.preview(assignment).ok()?;
+        let subtitle = match assignment {
+            Assignment::Variable { name, .. } => format!("Press Enter to store {} for this session", name),
+            Assignment::Function { .. } => "Press Enter to define this function for the session".to_string(),
+        };
+        Some(ResultItem::new(
+            "calculator assign",
+            format!("{} = {}", assignment.name(), preview),
+            Action::Custom {
+                action_type: "calculator.assign".to_string(),
+                data: serde_json::json!({ "query": query }),
+            },
+            self.id().to_string()
+        )
+        .with_subtitle(subtitle)
+        .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Calculator))
+        .with_score(100.0))
+    }
+}
+
+#[async_trait]
+impl Plugin for CalculatorPlugin {
+    fn id(&self) -> &str {
+        "calculator"
+    }
+    fn name(&self) -> &str {
+        "Calculator"
+    }
+    fn description(&self) -> &str {
+        "A plugin for performing calculations."
+    }
+    fn trigger(&self) -> Trigger {
+        Trigger::Implicit
+    }
+    // "MB" is megabytes and "Mb" megabits
+    fn keeps_case(&self) -> bool {
+        true
+    }
+    fn settings_schema(&self) -> Vec<SettingField> {
+        vec![
+            SettingField::text("currency_rates_url", "Currency rates URL", ""),
+            SettingField::number("currency_refresh_hours", "Refresh currency rates every (hours)", 24),
+        ]
+    }
+    async fn search(&self, query: &str, context: &PluginContext) -> Vec<ResultItem> {
+        let mut results = Vec::new();
+        let mut session = self.session.lock().unwrap();
+        session.clear_pending();
+        if let Some(assignment) = parse_assignment(query) {
+            results.extend(self.assignment_result(query, &assignment, &session));
+            return results;
+        }
+        if let Some(conversion) = units::convert(query) {
+            let value = format_number(conversion.value);
+            let unit = conversion.to.label(conversion.value);
+            let input = format!("{} {}", format_number(conversion.input), conversion.from.label(conversion.input));
+            session.add_pending(value.clone(), query, conversion.value);
+            session.add_pending(format!("{} {}", value, unit), query, conversion.value);
+            results.push(ResultItem::new(
+                "calculator",
+                format!("{} {}", value, unit),
+                Action::CopyToClipboard(value.clone()),
+                self.id().to_string()
+            )
+            .with_subtitle(format!("{} = {} {} · Copy value", input, value, unit))
+            .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Calculator))
+            .with_score(100.0)
+            );
+            results.push(ResultItem::new(
+                "calculator with unit",
+                format!("{} {}", value, unit),
+                Action::CopyToClipboard(format!("{} {}", value, unit)),
+                self.id().to_string()
+            )
+            .with_subtitle("Copy with unit")
+            .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Calculator))
+            .with_score(99.0)
+            );
+            return results;
+        }
+        if let Some(conversion) = currency::parse_query(query) {
+            let url = context.get_setting::<String>(self.id(), "currency_rates_url").unwrap_or_default();
+            let hours = context.get_setting::<u64>(self.id(), "currency_refresh_hours").unwrap_or(24);
+            self.rates.refresh_if_stale(&url, Duration::from_secs(hours * 3600));
+            let table = self.rates.table();
+            let value = table.as_ref().and_then(|t| t.convert(conversion.amount, &conversion.from, &conversion.to));
+            if let (Some(table), Some(value)) = (table.as_ref(), value) {
+                let rounded = currency::format_amount(value);
+                let with_code = format!("{} {}", rounded, conversion.to);
+                let updated = table
+                    .updated
+                    .map(|t| format!("rates from {}", t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")))
+                    .unwrap_or_else(|| "rates date unknown".to_string());
+                session.add_pending(rounded.clone(), query, value);
+                session.add_pending(with_code.clone(), query, value);
+                results.push(ResultItem::new(
+                    "calculator currency",
+                    with_code.clone(),
+                    Action::CopyToClipboard(rounded.clone()),
+                    self.id().to_string()
+                )
+                .with_subtitle(format!(
+                    "{} {} = {} · {}",
+                    currency::format_amount(conversion.amount),
+                    conversion.from,
+                    with_code,
+                    updated
+                ))
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Calculator))
+                .with_score(100.0)
+                );
+                results.push(ResultItem::new(
+                    "calculator currency with code",
+                    with_code.clone(),
+                    Action::CopyToClipboard(with_code),
+                    self.id().to_string()
+                )
+                .with_subtitle("Copy with currency code")
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Calculator))
+                .with_score(99.0)
+                );
+                return results;
+            }
+            if table.is_none() && currency::is_common_code(&conversion.from) && currency::is_common_code(&conversion.to) {
+                results.push(ResultItem::new(
+                    "calculator currency missing",
+                    "No currency rates available",
+                    Action::None,
+                    self.id().to_string()
+                )
+                .with_subtitle(format!(
+                    "Add {} or set a rates URL in the calculator settings",
+                    CurrencyRates::json_path().display()
+                ))
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Calculator))
+                .with_score(50.0)
+                );
+                return results;
+            }
+        }
+        if let Some(value) = datetime::evaluate(query, chrono::Local::now().fixed_offset()) {
+            let rows = match value {
+                DateValue::Instant { time, zone } => {
+                    let epoch = time.timestamp();
+                    vec![
+                        ("date", format!("{} {}", time.format("%A, %-d %B %Y, %H:%M:%S"), zone), time.to_rfc3339(), "ISO 8601".to_string(), epoch as f64),
+                        ("date rfc2822", time.to_rfc2822(), time.to_rfc2822(), "RFC 2822".to_string(), epoch as f64),
+                        ("date epoch", epoch.to_string(), epoch.to_string(), "Unix timestamp".to_string(), epoch as f64),
+                    ]
+                }
+                DateValue::Duration(duration) => {
+                    let days = duration.num_seconds() as f64 / 86_400.0;
+                    let iso = datetime::iso_duration(duration);
+                    vec![
+                        ("duration", datetime::describe_duration(duration), format_number(days), format!("{} days", format_number(days)), days),
+                        ("duration iso", iso.clone(), iso, "ISO 8601 duration".to_string(), days),
+                        ("duration seconds", duration.num_seconds().to_string(), duration.num_seconds().to_string(), "Seconds".to_string(), duration.num_seconds() as f64),
+                    ]
+                }
+            };
+            for (i, (id, title, copy, subtitle, value)) in rows.into_iter().enumerate() {
+                session.add_pending(copy.clone(), query, value);
+                results.push(ResultItem::new(
+                    format!("calculator {}", id),
+                    title,
+                    Action::CopyToClipboard(copy),
+                    self.id().to_string()
+                )
+                .with_subtitle(subtitle)
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Calculator))
+                .with_score(100.0 - i as f32)
+                );
+            }
+            return results;
+        }
+        // after the dates, "2026-10-18 - 2026-01-01" isn't integer arithmetic
+        if let Some(value) = programmer::evaluate(query) {
+            let approximate = value.to_f64().unwrap_or(f64::NAN);
+            for (i, (id, title, copy, subtitle)) in programmer::rows(&value).into_iter().enumerate() {
+                session.add_pending(copy.clone(), query, approximate);
+                results.push(ResultItem::new(
+                    format!("calculator {}", id),
+                    title,
+                    Action::CopyToClipboard(copy),
+                    self.id().to_string()
+                )
+                .with_subtitle(subtitle)
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Calculator))
+                .with_score(100.0 - i as f32)
+                );
+            }
+            return results;
+        }
+        let output = session.eval(query);
+        if let Ok(output) = output {
+            let formatted_output = output.separate_with_commas();
+            session.add_pending(format!("{}", output), query, output);
+            results.push(ResultItem::new(
+                "calculator",
+                formatted_output,
+                Action::CopyToClipboard(format!("{}", output)),
+                self.id().to_string()
+            )
+            .with_subtitle("Calculator")
+            .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Calculator))
+            .with_score(100.0)
+        );
+        }
+        results
+    }
+    async fn execute(&self, action: &Action, _context: &PluginContext) -> anyhow::Result<()> {
+        match action {
+            Action::CopyToClipboard(output) => {
+                let mut clipboard = Clipboard::new()?;
+                clipboard.set_text(output)?;
+                self.session.lock().unwrap().record_copy(output);
+                println!("Result copied to clipboard");
+            }
+            Action::Custom { action_type, data } if action_type == "calculator.assign" => {
+                let query = data["query"].as_str().unwrap_or_default();
+                let assignment = parse_assignment(query).ok_or_else(|| anyhow::anyhow!("Not an assignment: {}", query))?;
+                let name = assignment.name().to_string();
+                self.session.lock().unwrap().assign(assignment)?;
+                println!("Stored {}", name);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
\ No newline at end of file
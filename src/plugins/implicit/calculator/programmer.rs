@@ -0,0 +1,293 @@
+// Integer-exact evaluation for programmer queries like "0xFF & 0b1010" or "1 << 70"
+use num_bigint::BigInt;
+use num_traits::{Signed, ToPrimitive, Zero};
+use thousands::Separable;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(BigInt),
+    Op(&'static str),
+    Open,
+    Close,
+}
+
+const OPERATORS: [&str; 14] = ["**", "<<", ">>", "+", "-", "*", "/", "%", "&", "|", "^", "~", "(", ")"];
+
+const MAX_SHIFT: usize = 1 << 16;
+const MAX_BITS: u64 = 1 << 20;
+
+struct Tokens {
+    tokens: Vec<Token>,
+    // a 0x/0b/0o literal or a bitwise operator other than ^ was used
+    explicit: bool,
+}
+
+fn tokenize(query: &str) -> Option<Tokens> {
+    let mut tokens = Vec::new();
+    let mut explicit = false;
+    let chars: Vec<char> = query.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c.is_ascii_digit() {
+            let radix = match (c, chars.get(i + 1).map(|c| c.to_ascii_lowercase())) {
+                ('0', Some('x')) => 16,
+                ('0', Some('b')) => 2,
+                ('0', Some('o')) => 8,
+                _ => 10,
+            };
+            if radix != 10 {
+                explicit = true;
+                i += 2;
+            }
+            let start = i;
+            while i < chars.len() && (chars[i].is_digit(radix) || chars[i] == '_') {
+                i += 1;
+            }
+            let digits: String = chars[start..i].iter().filter(|c| **c != '_').collect();
+            // "1.5", "2e3" or "0x" without digits are not for us
+            if digits.is_empty() || chars.get(i).is_some_and(|c| c.is_alphanumeric() || *c == '.') {
+                return None;
+            }
+            tokens.push(Token::Number(BigInt::parse_bytes(digits.as_bytes(), radix)?));
+            continue;
+        }
+        let rest: String = chars[i..].iter().take(2).collect();
+        let op = OPERATORS.iter().find(|op| rest.starts_with(**op))?;
+        if matches!(*op, "<<" | ">>" | "&" | "|" | "~") {
+            explicit = true;
+        }
+        tokens.push(match *op {
+            "(" => Token::Open,
+            ")" => Token::Close,
+            op => Token::Op(op),
+        });
+        i += op.len();
+    }
+    Some(Tokens { tokens, explicit })
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    // ^ is xor in programmer queries and power otherwise, like meval
+    caret_is_xor: bool,
+    // a division dropped a remainder
+    inexact: bool,
+}
+
+impl Parser {
+    fn peek_op(&self) -> Option<&'static str> {
+        match self.tokens.get(self.position) {
+            Some(Token::Op(op)) => Some(op),
+            _ => None,
+        }
+    }
+    fn binding(&self, op: &str) -> Option<u8> {
+        Some(match op {
+            "|" => 1,
+            "^" if self.caret_is_xor => 2,
+            "&" => 3,
+            "<<" | ">>" => 4,
+            "+" | "-" => 5,
+            "*" | "/" | "%" => 6,
+            "**" | "^" => 8,
+            _ => return None,
+        })
+    }
+    fn expression(&mut self, min_binding: u8) -> Option<BigInt> {
+        let mut left = self.unary()?;
+        while let Some(op) = self.peek_op() {
+            let binding = self.binding(op)?;
+            if binding < min_binding {
+                break;
+            }
+            self.position += 1;
+            // powers are right associative
+            let next = if binding == 8 { binding } else { binding + 1 };
+            let right = self.expression(next)?;
+            left = self.apply(op, left, right)?;
+        }
+        Some(left)
+    }
+    fn unary(&mut self) -> Option<BigInt> {
+        match self.tokens.get(self.position).cloned()? {
+            Token::Op("-") => {
+                self.position += 1;
+                // -2 ** 2 is -(2 ** 2)
+                Some(-self.expression(7)?)
+            }
+            Token::Op("+") => {
+                self.position += 1;
+                self.expression(7)
+            }
+            Token::Op("~") => {
+                self.position += 1;
+                Some(!self.expression(7)?)
+            }
+            Token::Open => {
+                self.position += 1;
+                let value = self.expression(0)?;
+                match self.tokens.get(self.position) {
+                    Some(Token::Close) => {
+                        self.position += 1;
+                        Some(value)
+                    }
+                    _ => None,
+                }
+            }
+            Token::Number(value) => {
+                self.position += 1;
+                Some(value)
+            }
+            _ => None,
+        }
+    }
+    fn apply(&mut self, op: &str, left: BigInt, right: BigInt) -> Option<BigInt> {
+        Some(match op {
+            "+" => left + right,
+            "-" => left - right,
+            "*" => left * right,
+            "/" | "%" if right.is_zero() => return None,
+            "/" => {
+                if !(&left % &right).is_zero() {
+                    self.inexact = true;
+                }
+                left / right
+            }
+            "%" => left % right,
+            "&" => left & right,
+            "|" => left | right,
+            "^" if self.caret_is_xor => left ^ right,
+            "<<" => left << right.to_usize().filter(|s| *s <= MAX_SHIFT)?,
+            ">>" => left >> right.to_usize().filter(|s| *s <= MAX_SHIFT)?,
+            "**" | "^" => {
+                let exponent = right.to_u32()?;
+                if left.bits().saturating_mul(exponent as u64) > MAX_BITS {
+                    return None;
+                }
+                left.pow(exponent)
+            }
+            _ => return None,
+        })
+    }
+}
+
+// Evaluates integer-only queries, "255", "200 + 55" or "0xFF & 0b1010", exactly. Anything with
+// a fraction, "1.5 * 2" or "7 / 2", is left to the regular calculator.
+pub fn evaluate(query: &str) -> Option<BigInt> {
+    let Tokens { tokens, explicit } = tokenize(query)?;
+    if tokens.is_empty() {
+        return None;
+    }
+    let caret_is_xor = explicit;
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+        caret_is_xor,
+        inexact: false,
+    };
+    let value = parser.expression(0)?;
+    if parser.position != parser.tokens.len() {
+        return None;
+    }
+    // "7 / 2" is 3.5 for the regular calculator, and "0x7 / 2" would silently become 3
+    if parser.inexact {
+        return None;
+    }
+    Some(value)
+}
+
+// The value in each base, as (id, title, copied text, subtitle)
+pub fn rows(value: &BigInt) -> [(&'static str, String, String, &'static str); 4] {
+    let decimal = value.to_string();
+    [
+        ("decimal", decimal.separate_with_commas(), decimal, "Decimal"),
+        ("hex", to_hex(value), to_hex(value), "Hexadecimal"),
+        ("binary", to_binary(value), to_binary(value), "Binary"),
+        ("octal", to_octal(value), to_octal(value), "Octal"),
+    ]
+}
+
+fn with_sign(value: &BigInt, prefix: &str, digits: String) -> String {
+    if value.is_negative() {
+        format!("-{}{}", prefix, digits)
+    } else {
+        format!("{}{}", prefix, digits)
+    }
+}
+
+pub fn to_hex(value: &BigInt) -> String {
+    with_sign(value, "0x", format!("{:X}", value.abs()))
+}
+
+pub fn to_binary(value: &BigInt) -> String {
+    with_sign(value, "0b", format!("{:b}", value.abs()))
+}
+
+pub fn to_octal(value: &BigInt) -> String {
+    with_sign(value, "0o", format!("{:o}", value.abs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(query: &str) -> Option<String> {
+        evaluate(query).map(|v| v.to_string())
+    }
+
+    #[test]
+    fn test_base_literals_and_bitwise() {
+        assert_eq!(eval("0xFF & 0b1010"), Some("10".to_string()));
+        assert_eq!(eval("0x10 | 0o7"), Some("23".to_string()));
+        assert_eq!(eval("0xF0 ^ 0xFF"), Some("15".to_string()));
+        assert_eq!(eval("1 << 4 + 1"), Some("32".to_string()));
+        assert_eq!(eval("~0"), Some("-1".to_string()));
+        assert_eq!(eval("0xFF_FF >> 8"), Some("255".to_string()));
+    }
+
+    #[test]
+    fn test_big_integers_stay_exact() {
+        assert_eq!(eval("2^64"), Some("18446744073709551616".to_string()));
+        assert_eq!(eval("1 << 70"), Some("1180591620717411303424".to_string()));
+        assert_eq!(eval("99999999999999999 + 2"), Some("100000000000000001".to_string()));
+        assert_eq!(eval("-0x2 ** 2"), Some("-4".to_string()));
+    }
+
+    #[test]
+    fn test_plain_integers() {
+        for query in ["255", "200+55", " 200 + 55 "] {
+            let titles: Vec<String> = rows(&evaluate(query).unwrap()).into_iter().map(|(_, title, _, _)| title).collect();
+            assert_eq!(titles, ["255", "0xFF", "0b11111111", "0o377"]);
+        }
+        assert_eq!(rows(&BigInt::from(1234))[0].1, "1,234");
+        assert_eq!(rows(&BigInt::from(1234))[0].2, "1234");
+        assert_eq!(eval("2 + 3"), Some("5".to_string()));
+        assert_eq!(eval("2^10"), Some("1024".to_string()));
+        assert_eq!(eval("8 / 2"), Some("4".to_string()));
+    }
+
+    #[test]
+    fn test_leaves_regular_queries_to_meval() {
+        assert_eq!(eval("7 / 2"), None);
+        assert_eq!(eval("0x7 / 2"), None);
+        assert_eq!(eval("1.5 * 2"), None);
+        assert_eq!(eval("10**20 / 3"), None);
+        assert_eq!(eval("sqrt(4)"), None);
+        assert_eq!(eval("1 / 0x0"), None);
+        assert_eq!(eval("0x"), None);
+    }
+
+    #[test]
+    fn test_formats() {
+        let value = BigInt::from(-255);
+        assert_eq!(to_hex(&value), "-0xFF");
+        assert_eq!(to_binary(&BigInt::from(10)), "0b1010");
+        assert_eq!(to_octal(&BigInt::from(8)), "0o10");
+    }
+}
\ No newline at end of file
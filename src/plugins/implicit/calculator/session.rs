@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use meval::{Context, Expr};
+use serde::{Deserialize, Serialize};
+
+use crate::core::config::data_dir;
+
+const MAX_HISTORY: usize = 200;
+
+const BUILTINS: [&str; 25] = [
+    "pi", "e", "ans", "sqrt", "exp", "ln", "abs", "sin", "cos", "tan", "asin", "acos", "atan", "sinh", "cosh",
+    "tanh", "asinh", "acosh", "atanh", "floor", "ceil", "round", "signum", "max", "min",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub expression: String,
+    pub result: String,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserFunction {
+    pub params: Vec<String>,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Assignment {
+    // x = 5
+    Variable { name: String, expression: String },
+    // f(x, y) = x^2 + y
+    Function { name: String, function: UserFunction },
+}
+
+impl Assignment {
+    pub fn name(&self) -> &str {
+        match self {
+            Assignment::Variable { name, .. } | Assignment::Function { name, .. } => name,
+        }
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !BUILTINS.contains(&name)
+}
+
+pub fn parse_assignment(query: &str) -> Option<Assignment> {
+    let (left, right) = query.split_once('=')?;
+    let (left, right) = (left.trim(), right.trim());
+    // "x == 5", "x <= 5" and friends are not assignments
+    if right.is_empty() || right.starts_with('=') || left.ends_with(['<', '>', '!']) {
+        return None;
+    }
+    if is_identifier(left) {
+        return Some(Assignment::Variable { name: left.to_string(), expression: right.to_string() });
+    }
+    let (name, params) = left.strip_suffix(')')?.split_once('(')?;
+    let name = name.trim();
+    let params: Vec<String> = params.split(',').map(|p| p.trim().to_string()).collect();
+    if !is_identifier(name) || params.iter().any(|p| !is_identifier(p)) {
+        return None;
+    }
+    Some(Assignment::Function {
+        name: name.to_string(),
+        function: UserFunction { params, body: right.to_string() },
+    })
+}
+
+// Variables and functions defined this session plus the persisted calculation history
+pub struct Session {
+    pub ans: Option<f64>,
+    pub variables: HashMap<String, f64>,
+    pub functions: HashMap<String, UserFunction>,
+    pub history: Vec<HistoryEntry>,
+    // copied text -> (expression, value) of the rows shown last, to fill `ans` and the history
+    pending: HashMap<String, (String, f64)>,
+    path: Option<PathBuf>,
+}
+
+impl Session {
+    pub fn load() -> Self {
+        let path = data_dir().join("calculator_history.json");
+        let history = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self::new(history, Some(path))
+    }
+    pub(super) fn new(history: Vec<HistoryEntry>, path: Option<PathBuf>) -> Self {
+        Self {
+            ans: None,
+            variables: HashMap::new(),
+            functions: HashMap::new(),
+            history,
+            pending: HashMap::new(),
+            path,
+        }
+    }
+
+    // Builtins plus ans, session variables and user functions
+    pub fn context(&self) -> Context<'static> {
+        let mut context = Context::new();
+        if let Some(ans) = self.ans {
+            context.var("ans", ans);
+        }
+        for (name, value) in &self.variables {
+            context.var(name.clone(), *value);
+        }
+        for (name, function) in &self.functions {
+            let Ok(body) = function.body.parse::<Expr>() else {
+                continue;
+            };
+            let params = function.params.clone();
+            let variables = self.variables.clone();
+            let ans = self.ans;
+            context.funcn(
+                name.clone(),
+                move |args: &[f64]| {
+                    let mut context = Context::new();
+                    if let Some(ans) = ans {
+                        context.var("ans", ans);
+                    }
+                    for (name, value) in &variables {
+                        context.var(name.clone(), *value);
+                    }
+                    for (param, value) in params.iter().zip(args) {
+                        context.var(param.clone(), *value);
+                    }
+                    body.eval_with_context(context).unwrap_or(f64::NAN)
+                },
+                function.params.len(),
+            );
+        }
+        context
+    }
+
+    pub fn eval(&self, expression: &str) -> anyhow::Result<f64> {
+        let expr: Expr = expression.parse()?;
+        Ok(expr.eval_with_context(self.context())?)
+    }
+
+    // Checks the assignment and returns what it would store, without storing it
+    pub fn preview(&self, assignment: &Assignment) -> anyhow::Result<String> {
+        match assignment {
+            Assignment::Variable { expression, .. } => Ok(super::units::format_number(self.eval(expression)?)),
+            Assignment::Function { name, function } => {
+                let body: Expr = function.body.parse()?;
+                let mut context = self.context();
+                for param in &function.params {
+                    context.var(param.clone(), 1.0);
+                }
+                // recursive definitions can't be evaluated by meval
+                context.funcn(name.clone(), |_: &[f64]| f64::NAN, function.params.len());
+                body.eval_with_context(context)?;
+                Ok(format!("{}({})", name, function.params.join(", ")))
+            }
+        }
+    }
+
+    pub fn assign(&mut self, assignment: Assignment) -> anyhow::Result<()> {
+        match assignment {
+            Assignment::Variable { name, expression } => {
+                let value = self.eval(&expression)?;
+                self.functions.remove(&name);
+                self.variables.insert(name, value);
+            }
+            Assignment::Function { name, function } => {
+                self.preview(&Assignment::Function { name: name.clone(), function: function.clone() })?;
+                self.variables.remove(&name);
+                self.functions.insert(name, function);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn clear_pending(&mut self) {
+        self.pending.clear();
+    }
+
+    pub fn add_pending(&mut self, copy: String, expression: &str, value: f64) {
+        self.pending.insert(copy, (expression.trim().to_string(), value));
+    }
+
+    // A result was copied: it becomes `ans` and goes into the history
+    pub fn record_copy(&mut self, copied: &str) {
+        let Some((expression, value)) = self.pending.get(copied).cloned() else {
+            return;
+        };
+        self.ans = Some(value);
+        self.history.retain(|entry| entry.expression != expression);
+        self.history.insert(0, HistoryEntry {
+            expression,
+            result: copied.to_string(),
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+        });
+        self.history.truncate(MAX_HISTORY);
+        self.save();
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+        self.save();
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = std::fs::create_dir_all(data_dir())
+            .map_err(anyhow::Error::from)
+            .and_then(|_| Ok(serde_json::to_string_pretty(&self.history)?))
+            .and_then(|content| Ok(std::fs::write(path, content)?));
+        if let Err(e) = result {
+            log::warn!("Failed to save calculator history: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_assignment() {
+        assert_eq!(
+            parse_assignment("x = 5 * 2"),
+            Some(Assignment::Variable { name: "x".to_string(), expression: "5 * 2".to_string() })
+        );
+        assert_eq!(
+            parse_assignment("f(x, y) = x^2 + y"),
+            Some(Assignment::Function {
+                name: "f".to_string(),
+                function: UserFunction { params: vec!["x".to_string(), "y".to_string()], body: "x^2 + y".to_string() },
+            })
+        );
+        assert_eq!(parse_assignment("x == 5"), None);
+        assert_eq!(parse_assignment("pi = 3"), None);
+        assert_eq!(parse_assignment("2 = 3"), None);
+    }
+
+    #[test]
+    fn test_variables_functions_and_ans() {
+        let mut session = Session::new(Vec::new(), None);
+        session.assign(parse_assignment("x = 5").unwrap()).unwrap();
+        session.assign(parse_assignment("f(x) = x^2 + 1").unwrap()).unwrap();
+        assert_eq!(session.eval("f(x) + x").unwrap(), 31.0);
+
+        assert!(session.eval("ans + 1").is_err());
+        session.add_pending("26".to_string(), "f(x)", 26.0);
+        session.record_copy("26");
+        assert_eq!(session.eval("ans * 2").unwrap(), 52.0);
+        assert_eq!(session.history[0].expression, "f(x)");
+    }
+
+    #[test]
+    fn test_invalid_function_is_rejected() {
+        let mut session = Session::new(Vec::new(), None);
+        assert!(session.assign(parse_assignment("g(x) = x + y").unwrap()).is_err());
+        assert!(session.functions.is_empty());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,320 @@
+// Unit-aware conversions like "5 km in mi", "72 F to C" or "3 GiB as MB"
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dimension {
+    Length,
+    Mass,
+    Temperature,
+    Volume,
+    Data,
+    Time,
+    Speed,
+    Area,
+}
+
+struct UnitDef {
+    symbols: &'static [&'static str],
+    name: &'static str,
+    plural: &'static str,
+    dimension: Dimension,
+    // value in the base unit of the dimension = (value + offset) * factor
+    factor: f64,
+    offset: f64,
+    // accepts SI prefixes, e.g. km, mg, GB
+    prefixable: bool,
+}
+
+const fn unit(symbols: &'static [&'static str], name: &'static str, plural: &'static str, dimension: Dimension, factor: f64) -> UnitDef {
+    UnitDef { symbols, name, plural, dimension, factor, offset: 0.0, prefixable: false }
+}
+
+const fn prefixable(symbols: &'static [&'static str], name: &'static str, plural: &'static str, dimension: Dimension, factor: f64) -> UnitDef {
+    UnitDef { symbols, name, plural, dimension, factor, offset: 0.0, prefixable: true }
+}
+
+// base units: metre, kilogram, kelvin, litre, byte, second, metre per second, square metre
+const UNITS: &[UnitDef] = &[
+    prefixable(&["m"], "meter", "meters", Dimension::Length, 1.0),
+    unit(&["in", "\""], "inch", "inches", Dimension::Length, 0.0254),
+    unit(&["ft", "'"], "foot", "feet", Dimension::Length, 0.3048),
+    unit(&["yd"], "yard", "yards", Dimension::Length, 0.9144),
+    unit(&["mi"], "mile", "miles", Dimension::Length, 1609.344),
+    unit(&["nmi"], "nautical mile", "nautical miles", Dimension::Length, 1852.0),
+    unit(&["ly"], "light-year", "light-years", Dimension::Length, 9.460_730_472_580_8e15),
+    unit(&["au"], "astronomical unit", "astronomical units", Dimension::Length, 1.495_978_707e11),
+
+    prefixable(&["g"], "gram", "grams", Dimension::Mass, 0.001),
+    unit(&["t"], "tonne", "tonnes", Dimension::Mass, 1000.0),
+    unit(&["lb", "lbs"], "pound", "pounds", Dimension::Mass, 0.453_592_37),
+    unit(&["oz"], "ounce", "ounces", Dimension::Mass, 0.028_349_523_125),
+    unit(&["st"], "stone", "stone", Dimension::Mass, 6.350_293_18),
+
+    UnitDef { symbols: &["K"], name: "kelvin", plural: "kelvin", dimension: Dimension::Temperature, factor: 1.0, offset: 0.0, prefixable: false },
+    UnitDef { symbols: &["C", "°C", "degC", "celsius", "Celsius"], name: "degree Celsius", plural: "degrees Celsius", dimension: Dimension::Temperature, factor: 1.0, offset: 273.15, prefixable: false },
+    UnitDef { symbols: &["F", "°F", "degF", "fahrenheit", "Fahrenheit"], name: "degree Fahrenheit", plural: "degrees Fahrenheit", dimension: Dimension::Temperature, factor: 5.0 / 9.0, offset: 459.67, prefixable: false },
+
+    prefixable(&["l", "L"], "liter", "liters", Dimension::Volume, 1.0),
+    unit(&["gal"], "gallon", "gallons", Dimension::Volume, 3.785_411_784),
+    unit(&["qt"], "quart", "quarts", Dimension::Volume, 0.946_352_946),
+    unit(&["pt"], "pint", "pints", Dimension::Volume, 0.473_176_473),
+    unit(&["cup", "cups"], "cup", "cups", Dimension::Volume, 0.236_588_236_5),
+    unit(&["floz", "fl.oz"], "fluid ounce", "fluid ounces", Dimension::Volume, 0.029_573_529_562_5),
+    unit(&["tbsp"], "tablespoon", "tablespoons", Dimension::Volume, 0.014_786_764_781_25),
+    unit(&["tsp"], "teaspoon", "teaspoons", Dimension::Volume, 0.004_928_921_593_75),
+    unit(&["cc"], "cubic centimeter", "cubic centimeters", Dimension::Volume, 0.001),
+
+    prefixable(&["B"], "byte", "bytes", Dimension::Data, 1.0),
+    prefixable(&["b", "bit"], "bit", "bits", Dimension::Data, 0.125),
+    unit(&["KiB"], "kibibyte", "kibibytes", Dimension::Data, 1024.0),
+    unit(&["MiB"], "mebibyte", "mebibytes", Dimension::Data, 1_048_576.0),
+    unit(&["GiB"], "gibibyte", "gibibytes", Dimension::Data, 1_073_741_824.0),
+    unit(&["TiB"], "tebibyte", "tebibytes", Dimension::Data, 1_099_511_627_776.0),
+    unit(&["PiB"], "pebibyte", "pebibytes", Dimension::Data, 1_125_899_906_842_624.0),
+
+    prefixable(&["s", "sec"], "second", "seconds", Dimension::Time, 1.0),
+    unit(&["min", "mins"], "minute", "minutes", Dimension::Time, 60.0),
+    unit(&["h", "hr", "hrs"], "hour", "hours", Dimension::Time, 3600.0),
+    unit(&["d"], "day", "days", Dimension::Time, 86_400.0),
+    unit(&["wk"], "week", "weeks", Dimension::Time, 604_800.0),
+    unit(&["mo"], "month", "months", Dimension::Time, 2_629_800.0),
+    unit(&["yr", "y"], "year", "years", Dimension::Time, 31_557_600.0),
+
+    unit(&["m/s", "mps"], "meter per second", "meters per second", Dimension::Speed, 1.0),
+    unit(&["km/h", "kmh", "kph"], "kilometer per hour", "kilometers per hour", Dimension::Speed, 1.0 / 3.6),
+    unit(&["mph", "mi/h"], "mile per hour", "miles per hour", Dimension::Speed, 0.447_04),
+    unit(&["ft/s", "fps"], "foot per second", "feet per second", Dimension::Speed, 0.3048),
+    unit(&["kn", "kt"], "knot", "knots", Dimension::Speed, 1852.0 / 3600.0),
+
+    unit(&["ha"], "hectare", "hectares", Dimension::Area, 10_000.0),
+    unit(&["ac"], "acre", "acres", Dimension::Area, 4_046.856_422_4),
+];
+
+const PREFIXES: &[(&str, &str, f64)] = &[
+    ("P", "peta", 1e15),
+    ("T", "tera", 1e12),
+    ("G", "giga", 1e9),
+    ("M", "mega", 1e6),
+    ("k", "kilo", 1e3),
+    ("K", "kilo", 1e3),
+    ("h", "hecto", 1e2),
+    ("da", "deca", 1e1),
+    ("d", "deci", 1e-1),
+    ("c", "centi", 1e-2),
+    ("m", "milli", 1e-3),
+    ("µ", "micro", 1e-6),
+    ("u", "micro", 1e-6),
+    ("n", "nano", 1e-9),
+    ("p", "pico", 1e-12),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unit {
+    pub name: String,
+    pub plural: String,
+    pub dimension: Dimension,
+    factor: f64,
+    offset: f64,
+}
+
+impl Unit {
+    fn from_def(def: &UnitDef) -> Self {
+        Self {
+            name: def.name.to_string(),
+            plural: def.plural.to_string(),
+            dimension: def.dimension,
+            factor: def.factor,
+            offset: def.offset,
+        }
+    }
+    pub fn label(&self, value: f64) -> &str {
+        if value == 1.0 { &self.name } else { &self.plural }
+    }
+    fn to_base(&self, value: f64) -> f64 {
+        (value + self.offset) * self.factor
+    }
+    fn in_unit(&self, value: f64) -> f64 {
+        value / self.factor - self.offset
+    }
+}
+
+fn find_unit(text: &str) -> Option<Unit> {
+    let lower = text.to_lowercase();
+    // exact symbols are case sensitive (m vs M, b vs B), names are not
+    if let Some(def) = UNITS.iter().find(|u| u.symbols.contains(&text)) {
+        return Some(Unit::from_def(def));
+    }
+    if let Some(def) = UNITS.iter().find(|u| u.name == lower || u.plural == lower) {
+        return Some(Unit::from_def(def));
+    }
+    for (symbol, long, factor) in PREFIXES {
+        for def in UNITS.iter().filter(|u| u.prefixable) {
+            let by_symbol = text.strip_prefix(symbol).is_some_and(|rest| def.symbols.contains(&rest));
+            let by_name = lower
+                .strip_prefix(long)
+                .is_some_and(|rest| rest == def.name || rest == def.plural);
+            if by_symbol || by_name {
+                let mut unit = Unit::from_def(def);
+                unit.name = format!("{}{}", long, def.name);
+                unit.plural = format!("{}{}", long, def.plural);
+                unit.factor *= factor;
+                return Some(unit);
+            }
+        }
+    }
+    None
+}
+
+// Units with a trailing power, e.g. km2, m³ or "square feet"
+pub fn parse_unit(text: &str) -> Option<Unit> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    if let Some(unit) = find_unit(text) {
+        return Some(unit);
+    }
+    let lower = text.to_lowercase();
+    let (base, power) = if let Some(base) = text.strip_suffix('2').or_else(|| text.strip_suffix('²')) {
+        (base.to_string(), 2)
+    } else if let Some(base) = text.strip_suffix('3').or_else(|| text.strip_suffix('³')) {
+        (base.to_string(), 3)
+    } else if let Some(base) = lower.strip_prefix("square ").or_else(|| lower.strip_prefix("sq ")) {
+        (base.to_string(), 2)
+    } else if let Some(base) = lower.strip_prefix("cubic ") {
+        (base.to_string(), 3)
+    } else {
+        return None;
+    };
+    let length = find_unit(base.trim()).filter(|u| u.dimension == Dimension::Length)?;
+    let (word, dimension, factor) = match power {
+        // cubic metres in litres, the base unit of volume
+        2 => ("square", Dimension::Area, length.factor.powi(2)),
+        _ => ("cubic", Dimension::Volume, length.factor.powi(3) * 1000.0),
+    };
+    Some(Unit {
+        name: format!("{} {}", word, length.name),
+        plural: format!("{} {}", word, length.plural),
+        dimension,
+        factor,
+        offset: 0.0,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conversion {
+    pub input: f64,
+    pub from: Unit,
+    pub value: f64,
+    pub to: Unit,
+}
+
+// "2*3 km" or "5km" -> (6, kilometer)
+fn parse_quantity(text: &str) -> Option<(f64, Unit)> {
+    let text = text.trim();
+    let words: Vec<&str> = text.split_whitespace().collect();
+    // the unit may be one or two words, e.g. "5 square feet"
+    for count in 1..=2.min(words.len().saturating_sub(1)) {
+        let split = words.len() - count;
+        if let Some(unit) = parse_unit(&words[split..].join(" ")) {
+            if let Ok(value) = meval::eval_str(words[..split].join(" ")) {
+                return Some((value, unit));
+            }
+        }
+    }
+    let start = text.find(|c: char| c.is_alphabetic() || "°µ\"'".contains(c))?;
+    let unit = parse_unit(&text[start..])?;
+    let value = meval::eval_str(&text[..start]).ok()?;
+    Some((value, unit))
+}
+
+pub fn convert(query: &str) -> Option<Conversion> {
+    let words: Vec<&str> = query.split_whitespace().collect();
+    // the last "to", "in" or "as" that leaves a valid unit on both sides
+    for (i, word) in words.iter().enumerate().rev() {
+        if !matches!(word.to_lowercase().as_str(), "to" | "in" | "as") || i == 0 {
+            continue;
+        }
+        let Some(to) = parse_unit(&words[i + 1..].join(" ")) else {
+            continue;
+        };
+        let Some((input, from)) = parse_quantity(&words[..i].join(" ")) else {
+            continue;
+        };
+        if from.dimension != to.dimension {
+            return None;
+        }
+        let value = to.in_unit(from.to_base(input));
+        if !value.is_finite() {
+            return None;
+        }
+        return Some(Conversion { input, from, value, to });
+    }
+    None
+}
+
+// Rounds away float noise like 0.30000000000000004 while keeping small values readable
+pub fn format_number(value: f64) -> String {
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    let magnitude = value.abs().log10().floor() as i32;
+    if !(-6..15).contains(&magnitude) {
+        return format!("{:e}", value);
+    }
+    let decimals = (10 - magnitude).clamp(0, 12) as usize;
+    let formatted = format!("{:.*}", decimals, value);
+    let formatted = if formatted.contains('.') {
+        formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        formatted
+    };
+    if formatted == "-0" { "0".to_string() } else { formatted }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn converted(query: &str) -> (String, String) {
+        let conversion = convert(query).unwrap();
+        (format_number(conversion.value), conversion.to.label(conversion.value).to_string())
+    }
+
+    #[test]
+    fn test_length_and_prefixes() {
+        assert_eq!(converted("5 km in mi"), ("3.1068559612".to_string(), "miles".to_string()));
+        assert_eq!(converted("1 inch to cm"), ("2.54".to_string(), "centimeters".to_string()));
+        assert_eq!(converted("5km to m"), ("5000".to_string(), "meters".to_string()));
+        assert_eq!(converted("2*3 feet as yd"), ("2".to_string(), "yards".to_string()));
+    }
+
+    #[test]
+    fn test_temperature() {
+        assert_eq!(converted("72 F to C"), ("22.222222222".to_string(), "degrees Celsius".to_string()));
+        assert_eq!(converted("0 °C in K"), ("273.15".to_string(), "kelvin".to_string()));
+        assert_eq!(converted("-40 celsius to fahrenheit").0, "-40");
+    }
+
+    #[test]
+    fn test_data_sizes() {
+        assert_eq!(converted("3 GiB in MB"), ("3221.225472".to_string(), "megabytes".to_string()));
+        assert_eq!(converted("100 Mb to MB"), ("12.5".to_string(), "megabytes".to_string()));
+    }
+
+    #[test]
+    fn test_other_dimensions() {
+        assert_eq!(converted("90 min to h"), ("1.5".to_string(), "hours".to_string()));
+        assert_eq!(converted("100 km/h in mph").0, "62.137119224");
+        assert_eq!(converted("1 ha to m2"), ("10000".to_string(), "square meters".to_string()));
+        assert_eq!(converted("1 gal to l"), ("3.785411784".to_string(), "liters".to_string()));
+        assert_eq!(converted("1 m3 to l"), ("1000".to_string(), "liters".to_string()));
+        assert_eq!(converted("1 lb in g"), ("453.59237".to_string(), "grams".to_string()));
+    }
+
+    #[test]
+    fn test_rejects_mismatched_or_plain_queries() {
+        assert!(convert("5 km to kg").is_none());
+        assert!(convert("1 + 2").is_none());
+        assert!(convert("what is love").is_none());
+    }
+}
\ No newline at end of file
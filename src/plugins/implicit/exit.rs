@@ -29,7 +29,7 @@ impl Plugin for ExitPlugin {
     }
     async fn search(&self, query: &str, _context: &PluginContext) -> Vec<ResultItem> {
         let mut results = Vec::new();
-        let matcher = SkimMatcherV2::default();
+        let matcher = SkimMatcherV2::default().ignore_case();
         
         if let Some(_) = matcher.fuzzy_match("exit", query) {
             results.push(
@@ -0,0 +1,413 @@
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use chrono::{DateTime, Local, TimeZone};
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use serde::{Deserialize, Serialize};
+use crate::core::plugin::{Plugin, PluginContext, SettingField, Trigger};
+use crate::core::model::{Action, BuiltInIcon, PluginView, ResultIcon, ResultItem};
+use crate::platform::system;
+use crate::plugins::implicit::calculator::datetime::describe_duration;
+
+pub mod schedule;
+
+use schedule::{ScheduledAction, Scheduler};
+
+// Commands that wait out the countdown setting before running
+const COUNTDOWN_COMMANDS: &[&str] = &["shutdown", "restart"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SystemCommand {
+    // defaults to the name in snake case; a user command with a built-in id replaces it
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    // searched along with the name
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    // asked before running when set
+    #[serde(default)]
+    pub confirm: Option<String>,
+    // the program user commands run, built-in commands are run by the platform module
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+impl SystemCommand {
+    fn builtin(id: &str, name: &str, description: &str, keywords: &[&str], confirm: Option<&str>) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            confirm: confirm.map(str::to_string),
+            command: None,
+            args: Vec::new(),
+        }
+    }
+    // Shutdown and restart open a countdown view once confirmed, which starts the countdown and
+    // stays up with a Cancel row until it runs out
+    fn action(&self, countdown: u64) -> Action {
+        let action = match &self.command {
+            Some(program) => Action::ExecuteCommand { command: program.clone(), args: self.args.clone() },
+            None if countdown > 0 && COUNTDOWN_COMMANDS.contains(&self.id.as_str()) => Action::ShowPluginView {
+                plugin_id: "system_commands".to_string(),
+                view_id: "countdown".to_string(),
+                data: serde_json::json!({ "command": self.id, "seconds": countdown }),
+            },
+            None => Action::SystemCommand(self.id.clone()),
+        };
+        match &self.confirm {
+            Some(message) => action.confirm(message.clone()),
+            None => action,
+        }
+    }
+    // Best fuzzy score of the name and the keywords
+    fn match_score(&self, matcher: &SkimMatcherV2, query: &str) -> Option<i64> {
+        std::iter::once(&self.name)
+            .chain(&self.keywords)
+            .filter_map(|text| matcher.fuzzy_match(text, query))
+            .max()
+    }
+}
+
+fn builtin_commands() -> Vec<SystemCommand> {
+    let trash = if cfg!(target_os = "windows") { "Empty Recycle Bin" } else { "Empty Trash" };
+    vec![
+        SystemCommand::builtin("shutdown", "Shutdown", "Shut down the computer", &["power off", "turn off"], Some("Shut down the computer?")),
+        SystemCommand::builtin("restart", "Restart", "Restart the computer", &["reboot"], Some("Restart the computer?")),
+        SystemCommand::builtin("sleep", "Sleep", "Put the computer to sleep", &["suspend"], None),
+        SystemCommand::builtin("hibernate", "Hibernate", "Save the session to disk and power off", &[], None),
+        SystemCommand::builtin("logout", "Logout", "Log out of the session", &["log out", "sign out"], Some("Log out of the session?")),
+        SystemCommand::builtin("lock", "Lock Screen", "Lock the session", &["lock"], None),
+        SystemCommand::builtin("empty_trash", trash, "Permanently delete the deleted files", &["trash", "recycle bin"], Some("Permanently delete everything in the trash?")),
+        SystemCommand::builtin("sleep_display", "Sleep Display", "Turn the displays off", &["screen off", "monitor off"], None),
+        SystemCommand::builtin("toggle_mute", "Toggle Mute", "Mute or unmute the sound", &["mute", "unmute", "sound"], None),
+        SystemCommand::builtin("volume_up", "Volume Up", "Raise the volume", &["louder", "sound"], None),
+        SystemCommand::builtin("volume_down", "Volume Down", "Lower the volume", &["quieter", "sound"], None),
+    ]
+}
+
+// The built-in commands with the user's `[[plugins.system_commands.commands]]` merged in
+fn merge_commands(custom: Vec<SystemCommand>) -> Vec<SystemCommand> {
+    let mut commands = builtin_commands();
+    for mut command in custom {
+        if command.id.is_empty() {
+            command.id = command.name.to_lowercase().split_whitespace().collect::<Vec<_>>().join("_");
+        }
+        match commands.iter_mut().find(|c| c.id == command.id) {
+            Some(existing) => *existing = command,
+            None if command.command.is_none() => {
+                log::warn!("System command {} has no command to run, skipping", command.name);
+            }
+            None => commands.push(command),
+        }
+    }
+    commands
+}
+
+// Commands matching the query, best first
+fn matching_commands<'a>(commands: &'a [SystemCommand], query: &str) -> Vec<&'a SystemCommand> {
+    let matcher = SkimMatcherV2::default().ignore_case();
+    let mut matches: Vec<(i64, &SystemCommand)> = commands
+        .iter()
+        .filter_map(|command| Some((command.match_score(&matcher, query)?, command)))
+        .collect();
+    matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    matches.into_iter().map(|(_, command)| command).collect()
+}
+
+fn run_command(command: &SystemCommand) {
+    println!("Running system command: {}", command.name);
+    let result = match &command.command {
+        Some(program) => system::spawn_detached(program, &command.args),
+        None => system::run(&command.id),
+    };
+    if let Err(e) = result {
+        log::error!("System command {} failed: {}", command.name, e);
+    }
+}
+
+// "18:00" today, "Tue 18:00" on other days
+fn format_due(due: &DateTime<Local>) -> String {
+    if due.date_naive() == Local::now().date_naive() {
+        due.format("%H:%M").to_string()
+    } else {
+        due.format("%a %H:%M").to_string()
+    }
+}
+
+fn describe_wait(due: &DateTime<Local>) -> String {
+    let seconds = (*due - Local::now()).num_seconds().max(0);
+    describe_duration(chrono::Duration::seconds(seconds))
+}
+
+// Commands that ask before running ask before they are scheduled too
+fn schedule_action(command: &SystemCommand, due: &DateTime<Local>) -> Action {
+    let action = Action::Custom {
+        action_type: "system_commands.schedule".to_string(),
+        data: serde_json::json!({ "command": command.id, "due": due.timestamp() }),
+    };
+    match &command.confirm {
+        Some(_) => action.confirm(format!("{} at {}?", command.name, format_due(due))),
+        None => action,
+    }
+}
+
+pub struct SystemCommandsPlugin {
+    scheduler: Arc<Scheduler>,
+}
+
+impl SystemCommandsPlugin {
+    pub fn new() -> Self {
+        Self {
+            scheduler: Scheduler::start(run_command),
+        }
+    }
+
+    fn commands(&self, context: &PluginContext) -> Vec<SystemCommand> {
+        merge_commands(context.get_setting(self.id(), "commands").unwrap_or_default())
+    }
+
+    fn schedule_result(&self, command: &SystemCommand, due: &DateTime<Local>) -> ResultItem {
+        ResultItem::new(
+            format!("system_commands schedule {}", command.id),
+            format!("{} at {}", command.name, format_due(due)),
+            schedule_action(command, due),
+            self.id().to_string()
+        )
+        .with_subtitle(format!("Schedule in {}, runs even while the launcher is hidden", describe_wait(due)))
+        .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Terminal))
+        .with_score(100.0)
+    }
+
+    // Starts the countdown, or picks up the one already running for the command
+    fn start_countdown(&self, command: SystemCommand, seconds: u64) -> ScheduledAction {
+        let due = (Local::now() + Duration::from_secs(seconds)).timestamp();
+        let running = self
+            .scheduler
+            .pending()
+            .into_iter()
+            .find(|action| action.command.id == command.id && action.due <= due);
+        match running {
+            Some(action) => action,
+            None => {
+                println!("Running {} in {}s", command.name, seconds);
+                self.scheduler.schedule(command, due)
+            }
+        }
+    }
+
+    fn countdown_view(&self, action: &ScheduledAction) -> PluginView {
+        let due = Local.timestamp_opt(action.due, 0).single().unwrap_or_else(Local::now);
+        let name = &action.command.name;
+        let body = format!(
+            "{} in {}, at {}.\n\nHiding the launcher doesn't stop it; search for \"cancel\" to get back here.",
+            name,
+            describe_wait(&due),
+            due.format("%H:%M:%S")
+        );
+        let row = |kind: &str, title: String, icon: BuiltInIcon| {
+            ResultItem::new(
+                format!("system_commands {} {}", kind, action.id),
+                title,
+                Action::Custom {
+                    action_type: format!("system_commands.{}", kind),
+                    data: serde_json::json!({ "id": action.id }),
+                },
+                self.id().to_string()
+            )
+            .with_icon(ResultIcon::BuiltIn(icon))
+        };
+        let actions = vec![
+            row("cancel", format!("Cancel {}", name), BuiltInIcon::Exit),
+            row("run_now", format!("{} now", name), BuiltInIcon::Terminal),
+        ];
+        PluginView::detail(format!("{} at {}", name, due.format("%H:%M:%S")), body, actions)
+    }
+
+    fn cancel_result(&self, action: &ScheduledAction, score: f32) -> ResultItem {
+        let due = Local.timestamp_opt(action.due, 0).single().unwrap_or_else(Local::now);
+        ResultItem::new(
+            format!("system_commands cancel {}", action.id),
+            format!("Cancel {} at {}", action.command.name, format_due(&due)),
+            Action::Custom {
+                action_type: "system_commands.cancel".to_string(),
+                data: serde_json::json!({ "id": action.id }),
+            },
+            self.id().to_string()
+        )
+        .with_subtitle(format!("Scheduled, runs in {}", describe_wait(&due)))
+        .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Exit))
+        .with_score(score)
+    }
+}
+
+#[async_trait]
+impl Plugin for SystemCommandsPlugin {
+    fn id(&self) -> &str {
+        "system_commands"
+    }
+    fn name(&self) -> &str {
+        "System Commands"
+    }
+    fn description(&self) -> &str {
+        "A plugin for running system commands."
+    }
+    fn trigger(&self) -> Trigger {
+        Trigger::Implicit
+    }
+    fn settings_schema(&self) -> Vec<SettingField> {
+        vec![SettingField::number("countdown", "Seconds before shutdown and restart (0 for none)", 10)]
+    }
+    async fn search(&self, query: &str, context: &PluginContext) -> Vec<ResultItem> {
+        let mut results = Vec::new();
+        let query = query.trim();
+        if query.is_empty() {
+            return results;
+        }
+        let matcher = SkimMatcherV2::default().ignore_case();
+        let commands = self.commands(context);
+        let countdown = context.get_setting::<u64>(self.id(), "countdown").unwrap_or(10);
+
+        if let Some((text, due)) = schedule::parse_schedule(query, &Local::now()) {
+            if let Some(command) = matching_commands(&commands, &text).first() {
+                results.push(self.schedule_result(command, &due));
+            }
+        }
+
+        // pending actions show up for "scheduled", "cancel" or their command's name
+        let pending = self.scheduler.pending();
+        let wanted = pending.iter().filter(|action| {
+            ["scheduled", "cancel", action.command.name.as_str()]
+                .iter()
+                .any(|text| matcher.fuzzy_match(text, query).is_some())
+        });
+        for (i, action) in wanted.enumerate() {
+            results.push(self.cancel_result(action, 90.0 - i as f32));
+        }
+
+        for (i, command) in matching_commands(&commands, query).into_iter().enumerate() {
+            let mut item = ResultItem::new(
+                format!("system_commands {}", command.id),
+                command.name.clone(),
+                command.action(countdown),
+                self.id().to_string()
+            )
+            .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Terminal))
+            .with_score(25.0 - i as f32);
+            if !command.description.is_empty() {
+                item = item.with_subtitle(command.description.clone());
+            }
+            results.push(item);
+        }
+        results
+    }
+    async fn view(&self, action: &Action, context: &PluginContext) -> Option<PluginView> {
+        let Action::ShowPluginView { view_id, data, .. } = action else {
+            return None;
+        };
+        if view_id != "countdown" {
+            return None;
+        }
+        let id = data["command"].as_str()?;
+        let command = self.commands(context).into_iter().find(|c| c.id == id)?;
+        let action = self.start_countdown(command, data["seconds"].as_u64().unwrap_or(10));
+        Some(self.countdown_view(&action))
+    }
+    async fn execute(&self, action: &Action, context: &PluginContext) -> anyhow::Result<()> {
+        match action {
+            Action::Custom { action_type, data } if action_type == "system_commands.cancel" => {
+                let id = data["id"].as_u64().unwrap_or_default();
+                if let Some(action) = self.scheduler.cancel(id) {
+                    println!("Cancelled {}", action.command.name);
+                }
+            }
+            Action::Custom { action_type, data } if action_type == "system_commands.schedule" => {
+                let id = data["command"].as_str().unwrap_or_default();
+                let command = self
+                    .commands(context)
+                    .into_iter()
+                    .find(|c| c.id == id)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown system command: {}", id))?;
+                let due = data["due"].as_i64().unwrap_or_default();
+                println!("Scheduled {} for {}", command.name, due);
+                self.scheduler.schedule(command, due);
+            }
+            Action::Custom { action_type, data } if action_type == "system_commands.run_now" => {
+                let id = data["id"].as_u64().unwrap_or_default();
+                if let Some(action) = self.scheduler.cancel(id) {
+                    run_command(&action.command);
+                }
+            }
+            Action::SystemCommand(command) => system::run(command)?,
+            Action::ExecuteCommand { command, args } => {
+                system::spawn_detached(command, args)?;
+                println!("Command executed: {}", command);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_matches_are_returned() {
+        let commands = builtin_commands();
+        let names: Vec<&str> = matching_commands(&commands, "sleep").iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Sleep", "Sleep Display"]);
+        let names: Vec<&str> = matching_commands(&commands, "sound").iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(names.len(), 3);
+        assert!(matching_commands(&commands, "reboot")[0].id == "restart");
+    }
+
+    #[test]
+    fn test_user_commands_are_merged() {
+        let custom: Vec<SystemCommand> = serde_json::from_value(serde_json::json!([
+            {"name": "Restart Audio", "command": "systemctl", "args": ["--user", "restart", "pipewire"]},
+            {"id": "sleep", "name": "Nap", "keywords": ["suspend"]},
+            {"name": "Nothing"}
+        ]))
+        .unwrap();
+        let commands = merge_commands(custom);
+        let audio = commands.iter().find(|c| c.id == "restart_audio").unwrap();
+        assert_eq!(audio.action(0), Action::ExecuteCommand { command: "systemctl".to_string(), args: vec!["--user".to_string(), "restart".to_string(), "pipewire".to_string()] });
+        assert_eq!(commands.iter().find(|c| c.id == "sleep").unwrap().name, "Nap");
+        assert!(commands.iter().all(|c| c.name != "Nothing"));
+        assert!(commands.iter().find(|c| c.id == "shutdown").unwrap().action(0).requires_confirmation());
+    }
+
+    #[test]
+    fn test_scheduling_asks_first() {
+        let commands = builtin_commands();
+        let due = Local::now() + Duration::from_secs(60);
+        for id in ["shutdown", "restart", "logout"] {
+            let command = commands.iter().find(|c| c.id == id).unwrap();
+            assert!(schedule_action(command, &due).requires_confirmation(), "{}", id);
+        }
+        let lock = commands.iter().find(|c| c.id == "lock").unwrap();
+        assert!(!schedule_action(lock, &due).requires_confirmation());
+    }
+
+    #[test]
+    fn test_countdown_opens_a_view() {
+        let commands = builtin_commands();
+        let shutdown = commands.iter().find(|c| c.id == "shutdown").unwrap();
+        let Action::Confirm { action, .. } = shutdown.action(10) else {
+            panic!("shutdown has to be confirmed");
+        };
+        assert!(matches!(*action, Action::ShowPluginView { ref view_id, .. } if view_id == "countdown"));
+        assert!(matches!(shutdown.action(0), Action::Confirm { action, .. } if *action == Action::SystemCommand("shutdown".to_string())));
+        let logout = commands.iter().find(|c| c.id == "logout").unwrap();
+        assert_eq!(logout.action(10), logout.action(0));
+    }
+}
@@ -0,0 +1,217 @@
+// System commands scheduled for later ("shutdown in 30m", "restart at 18:00"), kept in a file so
+// they survive launcher restarts and run from a background thread while the launcher is hidden
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use super::SystemCommand;
+use crate::core::config::data_dir;
+
+// Actions that should have run this long before the launcher started are dropped, not run late
+const MISSED_AFTER: i64 = 120;
+const CHECK_EVERY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledAction {
+    pub id: u64,
+    pub command: SystemCommand,
+    // unix seconds
+    pub due: i64,
+}
+
+// "30m", "1h 30m", "90 seconds", "2 hours"; a bare number counts as minutes
+pub fn parse_delay(text: &str) -> Option<Duration> {
+    let text = text.trim().to_lowercase();
+    let mut seconds = 0.0;
+    let mut rest = text.as_str();
+    while !rest.is_empty() {
+        let number_end = rest.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(rest.len());
+        let number: f64 = rest[..number_end].parse().ok()?;
+        rest = rest[number_end..].trim_start();
+        let unit_end = rest.find(|c: char| !c.is_alphabetic()).unwrap_or(rest.len());
+        let unit = match &rest[..unit_end] {
+            "s" | "sec" | "secs" | "second" | "seconds" => 1.0,
+            "" | "m" | "min" | "mins" | "minute" | "minutes" => 60.0,
+            "h" | "hr" | "hrs" | "hour" | "hours" => 3600.0,
+            "d" | "day" | "days" => 86_400.0,
+            _ => return None,
+        };
+        seconds += number * unit;
+        rest = rest[unit_end..].trim_start_matches([' ', ',']);
+        rest = rest.strip_prefix("and ").unwrap_or(rest);
+    }
+    (seconds >= 1.0).then(|| Duration::from_secs(seconds as u64))
+}
+
+// "18:00", "6pm", "6:30 am"
+fn parse_clock(text: &str) -> Option<NaiveTime> {
+    let text = text.trim().to_lowercase();
+    let (time, meridiem) = match text.strip_suffix("am").or_else(|| text.strip_suffix("pm")) {
+        Some(time) => (time.trim(), Some(text.ends_with("pm"))),
+        None => (text.as_str(), None),
+    };
+    let (hour, minute) = match time.split_once(':') {
+        Some((hour, minute)) => (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?),
+        None if meridiem.is_some() => (time.parse::<u32>().ok()?, 0),
+        None => return None,
+    };
+    let hour = match meridiem {
+        Some(_) if hour == 0 || hour > 12 => return None,
+        Some(pm) => hour % 12 + if pm { 12 } else { 0 },
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+// The next time the clock shows `time`, today or tomorrow
+fn next_occurrence<Tz: TimeZone>(time: NaiveTime, now: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+    let zone = now.timezone();
+    let today = zone.from_local_datetime(&now.date_naive().and_time(time)).earliest()?;
+    if today > *now {
+        return Some(today);
+    }
+    let tomorrow = now.date_naive().succ_opt()?.and_time(time);
+    zone.from_local_datetime(&tomorrow).earliest()
+}
+
+// Splits "restart at 18:00" into ("restart", due time)
+pub fn parse_schedule<Tz: TimeZone>(query: &str, now: &DateTime<Tz>) -> Option<(String, DateTime<Tz>)> {
+    let lowercase = query.trim().to_lowercase();
+    let in_split = lowercase.rsplit_once(" in ");
+    let at_split = lowercase.rsplit_once(" at ");
+    let (command, due) = match (in_split, at_split) {
+        (Some((command, delay)), _) if parse_delay(delay).is_some() => {
+            let delay = chrono::Duration::from_std(parse_delay(delay)?).ok()?;
+            (command, now.clone() + delay)
+        }
+        (_, Some((command, time))) => (command, next_occurrence(parse_clock(time)?, now)?),
+        _ => return None,
+    };
+    let command = command.trim();
+    (!command.is_empty()).then(|| (command.to_string(), due))
+}
+
+pub struct Scheduler {
+    actions: Mutex<Vec<ScheduledAction>>,
+    path: Option<PathBuf>,
+}
+
+impl Scheduler {
+    // Reads the saved schedule and starts the thread that runs actions when they are due
+    pub fn start(run: impl Fn(&SystemCommand) + Send + 'static) -> Arc<Self> {
+        let path = data_dir().join("scheduled_actions.json");
+        let actions: Vec<ScheduledAction> = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        let now = chrono::Utc::now().timestamp();
+        let (missed, actions): (Vec<_>, Vec<_>) = actions.into_iter().partition(|a| a.due < now - MISSED_AFTER);
+        for action in &missed {
+            log::warn!("Dropping scheduled {} that was due while the launcher wasn't running", action.command.name);
+        }
+        let scheduler = Arc::new(Self::new(actions, Some(path)));
+        if !missed.is_empty() {
+            scheduler.save(&scheduler.actions.lock().unwrap());
+        }
+        let weak: Weak<Self> = Arc::downgrade(&scheduler);
+        std::thread::spawn(move || {
+            while let Some(scheduler) = weak.upgrade() {
+                for action in scheduler.take_due(chrono::Utc::now().timestamp()) {
+                    run(&action.command);
+                }
+                drop(scheduler);
+                std::thread::sleep(CHECK_EVERY);
+            }
+        });
+        scheduler
+    }
+
+    fn new(actions: Vec<ScheduledAction>, path: Option<PathBuf>) -> Self {
+        Self { actions: Mutex::new(actions), path }
+    }
+
+    pub fn schedule(&self, command: SystemCommand, due: i64) -> ScheduledAction {
+        let mut actions = self.actions.lock().unwrap();
+        let id = actions.iter().map(|a| a.id).max().unwrap_or(0) + 1;
+        let action = ScheduledAction { id, command, due };
+        actions.push(action.clone());
+        actions.sort_by_key(|a| a.due);
+        self.save(&actions);
+        action
+    }
+
+    pub fn cancel(&self, id: u64) -> Option<ScheduledAction> {
+        let mut actions = self.actions.lock().unwrap();
+        let position = actions.iter().position(|a| a.id == id)?;
+        let action = actions.remove(position);
+        self.save(&actions);
+        Some(action)
+    }
+
+    // Soonest first
+    pub fn pending(&self) -> Vec<ScheduledAction> {
+        self.actions.lock().unwrap().clone()
+    }
+
+    fn take_due(&self, now: i64) -> Vec<ScheduledAction> {
+        let mut actions = self.actions.lock().unwrap();
+        if actions.iter().all(|a| a.due > now) {
+            return Vec::new();
+        }
+        let (due, pending) = actions.drain(..).partition(|a| a.due <= now);
+        *actions = pending;
+        self.save(&actions);
+        due
+    }
+
+    fn save(&self, actions: &[ScheduledAction]) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = std::fs::create_dir_all(data_dir())
+            .map_err(anyhow::Error::from)
+            .and_then(|_| Ok(serde_json::to_string_pretty(actions)?))
+            .and_then(|content| Ok(std::fs::write(path, content)?));
+        if let Err(e) = result {
+            log::warn!("Failed to save scheduled actions: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    #[test]
+    fn test_parse_schedule() {
+        let now = FixedOffset::east_opt(3600).unwrap().with_ymd_and_hms(2026, 10, 19, 17, 30, 0).unwrap();
+        let (command, due) = parse_schedule("Shutdown in 30m", &now).unwrap();
+        assert_eq!((command.as_str(), due.format("%H:%M").to_string()), ("shutdown", "18:00".to_string()));
+        assert_eq!(parse_schedule("sleep in 1h 30 min", &now).unwrap().1.format("%H:%M").to_string(), "19:00");
+        assert_eq!(parse_schedule("restart at 18:00", &now).unwrap().1.format("%d %H:%M").to_string(), "19 18:00");
+        assert_eq!(parse_schedule("restart at 9am", &now).unwrap().1.format("%d %H:%M").to_string(), "20 09:00");
+        assert_eq!(parse_schedule("lock in 10", &now).unwrap().1.format("%H:%M").to_string(), "17:40");
+        assert_eq!(parse_schedule("in 10m", &now), None);
+        assert_eq!(parse_schedule("log in at home", &now), None);
+        assert_eq!(parse_delay("2 hours and 15 minutes"), Some(Duration::from_secs(8100)));
+    }
+
+    #[test]
+    fn test_due_actions_are_taken_once() {
+        let command: SystemCommand = serde_json::from_value(serde_json::json!({"id": "lock", "name": "Lock Screen"})).unwrap();
+        let scheduler = Scheduler::new(Vec::new(), None);
+        let first = scheduler.schedule(command.clone(), 200);
+        scheduler.schedule(command.clone(), 100);
+        let third = scheduler.schedule(command, 300);
+        assert_eq!(scheduler.pending().iter().map(|a| a.due).collect::<Vec<_>>(), [100, 200, 300]);
+        assert_eq!(scheduler.take_due(150).len(), 1);
+        assert!(scheduler.take_due(150).is_empty());
+        assert_eq!(scheduler.cancel(first.id).map(|a| a.due), Some(200));
+        assert_eq!(scheduler.take_due(1000), vec![third]);
+        assert!(scheduler.pending().is_empty());
+    }
+}
@@ -3,7 +3,6 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
-use everything_sdk::{EverythingError, global};
 // use everything_rs::{Everything, EverythingError};
 
 use crate::core::plugin::{Plugin, PluginContext, Trigger};
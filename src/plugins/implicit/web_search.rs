@@ -1,16 +1,146 @@
-use std::result;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
 use async_trait::async_trait;
-use google_search::search;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::Deserialize;
+use url::{Host, Url};
 
-use crate::core::plugin::{Plugin, PluginContext, Trigger};
+use crate::core::plugin::{Plugin, PluginContext, SettingField, Trigger};
 use crate::core::model::{Action, BuiltInIcon, ResultIcon, ResultItem};
+use crate::platform::shell;
+use crate::services::suggestions::{LocalSuggestions, SuggestionProvider};
+
+// Everything except the unreserved characters of RFC 3986
+const QUERY_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_').remove(b'~');
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SearchEngine {
+    pub name: String,
+    // used as `!keyword` in the query
+    pub keyword: String,
+    // `{query}` is replaced by the percent-encoded search terms
+    pub url: String,
+}
+
+impl SearchEngine {
+    fn new(name: &str, keyword: &str, url: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            keyword: keyword.to_string(),
+            url: url.to_string(),
+        }
+    }
+    pub fn search_url(&self, terms: &str) -> String {
+        let encoded = utf8_percent_encode(terms, QUERY_ENCODE_SET).to_string();
+        self.url.replace("{query}", &encoded)
+    }
+}
+
+fn default_engines() -> Vec<SearchEngine> {
+    vec![
+        SearchEngine::new("Google", "g", "https://www.google.com/search?q={query}"),
+        SearchEngine::new("DuckDuckGo", "ddg", "https://duckduckgo.com/?q={query}"),
+        SearchEngine::new("Bing", "b", "https://www.bing.com/search?q={query}"),
+        SearchEngine::new("Wikipedia", "w", "https://en.wikipedia.org/w/index.php?search={query}"),
+        SearchEngine::new("YouTube", "yt", "https://www.youtube.com/results?search_query={query}"),
+        SearchEngine::new("GitHub", "gh", "https://github.com/search?q={query}"),
+        SearchEngine::new("Stack Overflow", "so", "https://stackoverflow.com/search?q={query}"),
+        SearchEngine::new("docs.rs", "rs", "https://docs.rs/releases/search?query={query}"),
+    ]
+}
 
-pub struct WebSearchPlugin {}
+// Splits "rust traits !gh" or "!gh rust traits" into ("gh", "rust traits")
+fn parse_bang(query: &str) -> Option<(&str, String)> {
+    let words: Vec<&str> = query.split_whitespace().collect();
+    let position = words.iter().position(|w| w.len() > 1 && w.starts_with('!'))?;
+    let keyword = &words[position][1..];
+    let terms = words
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != position)
+        .map(|(_, w)| *w)
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some((keyword, terms))
+}
+
+// Returns a normalized URL when the query is a URL, a domain like "example.com/docs",
+// localhost or an IP address
+pub fn detect_url(query: &str) -> Option<String> {
+    let query = query.trim();
+    if query.is_empty() || query.chars().any(char::is_whitespace) {
+        return None;
+    }
+    let lower = query.to_lowercase();
+    if lower.starts_with("http://") || lower.starts_with("https://") {
+        let url = Url::parse(query).ok()?;
+        return url.host().is_some().then(|| url.to_string());
+    }
+    if query.contains("://") {
+        return None;
+    }
+    let host = query.split(['/', '?', '#']).next()?;
+    let host = host.rsplit_once(':').map_or(host, |(host, port)| {
+        if port.chars().all(|c| c.is_ascii_digit()) { host } else { "" }
+    });
+    let local = host.eq_ignore_ascii_case("localhost") || host.parse::<std::net::Ipv4Addr>().is_ok();
+    let scheme = if local { "http" } else { "https" };
+    let url = Url::parse(&format!("{}://{}", scheme, query)).ok()?;
+    match url.host()? {
+        Host::Domain(domain) if !local => {
+            let labels: Vec<&str> = domain.split('.').collect();
+            let tld = labels.last()?;
+            let valid = labels.len() > 1
+                && labels.iter().all(|l| !l.is_empty())
+                && tld.len() >= 2
+                && (tld.chars().all(|c| c.is_ascii_alphabetic()) || tld.starts_with("xn--"));
+            valid.then(|| url.to_string())
+        }
+        _ if local => Some(url.to_string()),
+        _ => None,
+    }
+}
+
+pub struct WebSearchPlugin {
+    suggestions: Box<dyn SuggestionProvider>,
+    // search url -> terms of the rows shown last, so executed searches end up in the history
+    pending: Mutex<HashMap<String, String>>,
+}
 
 impl WebSearchPlugin {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            suggestions: Box::new(LocalSuggestions::load()),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+    // Built-in engines, with `[plugins.web_search] engines` entries replacing those with the same keyword
+    fn engines(&self, context: &PluginContext) -> Vec<SearchEngine> {
+        let mut engines = default_engines();
+        let custom: Vec<SearchEngine> = context.get_setting(self.id(), "engines").unwrap_or_default();
+        for engine in custom {
+            if !engine.url.contains("{query}") {
+                log::warn!("Search engine {} has no {{query}} in its url, skipping", engine.name);
+                continue;
+            }
+            match engines.iter_mut().find(|e| e.keyword == engine.keyword) {
+                Some(existing) => *existing = engine,
+                None => engines.push(engine),
+            }
+        }
+        engines
+    }
+    fn search_result(&self, engine: &SearchEngine, terms: &str, score: f32) -> ResultItem {
+        ResultItem::new(
+            format!("web search {}", engine.keyword),
+            terms.to_string(),
+            Action::OpenUrl(engine.search_url(terms)),
+            self.id().to_string()
+        )
+        .with_subtitle(format!("Search {}", engine.name))
+        .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Search))
+        .with_score(score)
     }
 }
 
@@ -28,26 +158,115 @@ impl Plugin for WebSearchPlugin {
     fn trigger(&self) -> Trigger {
         Trigger::Implicit
     }
-    async fn search(&self, query: &str, _context: &PluginContext) -> Vec<ResultItem> {
+    fn settings_schema(&self) -> Vec<SettingField> {
+        vec![
+            SettingField::text("default_engine", "Default engine keyword", "g"),
+            SettingField::number("suggestions", "Suggestions shown", 3),
+        ]
+    }
+    async fn search(&self, query: &str, context: &PluginContext) -> Vec<ResultItem> {
         let mut results = Vec::new();
-        results.push(
-            ResultItem::new(
-                "web search",
-                query.to_string(),
-                Action::OpenUrl(query.to_string()),
-                self.id().to_string()
-            )
-            .with_subtitle("Search the web")
-            .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Search))
-            .with_score(-100.0)
-        );
+        let query = query.trim();
+        if query.is_empty() {
+            return results;
+        }
+        let engines = self.engines(context);
+
+        if let Some(url) = detect_url(query) {
+            // bare domains like "main.rs" may just as well be file names
+            let score = if query.contains("://") { 90.0 } else { 40.0 };
+            results.push(
+                ResultItem::new(
+                    "web open url",
+                    format!("Open {}", query),
+                    Action::OpenUrl(url.clone()),
+                    self.id().to_string()
+                )
+                .with_subtitle(url)
+                .with_icon(ResultIcon::BuiltIn(BuiltInIcon::Web))
+                .with_score(score)
+            );
+        }
+
+        let default_keyword = context
+            .get_setting::<String>(self.id(), "default_engine")
+            .unwrap_or_else(|| "g".to_string());
+        let bang = parse_bang(query).and_then(|(keyword, terms)| {
+            let engine = engines.iter().find(|e| e.keyword.eq_ignore_ascii_case(keyword))?;
+            Some((engine, terms))
+        });
+        let (engine, terms, score) = match bang {
+            Some((engine, terms)) => (engine, terms, 100.0),
+            None => match engines.iter().find(|e| e.keyword == default_keyword).or(engines.first()) {
+                Some(engine) => (engine, query.to_string(), -100.0),
+                None => return results,
+            },
+        };
+        if terms.is_empty() {
+            return results;
+        }
+
+        {
+            let mut pending = self.pending.lock().unwrap();
+            pending.clear();
+            pending.insert(engine.search_url(&terms), terms.clone());
+        }
+        results.push(self.search_result(engine, &terms, score));
+
+        let limit = context.get_setting::<usize>(self.id(), "suggestions").unwrap_or(3);
+        let suggestions = self.suggestions.suggest(&terms, limit).await;
+        let mut pending = self.pending.lock().unwrap();
+        for (i, suggestion) in suggestions.into_iter().enumerate() {
+            // keep suggestions right below the search they complete
+            let mut result = self.search_result(engine, &suggestion, score - 1.0 - i as f32);
+            result.id = format!("web suggestion {} {}", engine.keyword, i);
+            pending.insert(engine.search_url(&suggestion), suggestion);
+            results.push(result);
+        }
         results
     }
     async fn execute(&self, action: &Action, _context: &PluginContext) -> anyhow::Result<()> {
         if let Action::OpenUrl(url) = action {
-            let _ = search(url);
-            println!("Search results for {}", url);
+            shell::open(url)?;
+            if let Some(terms) = self.pending.lock().unwrap().get(url) {
+                self.suggestions.record(terms);
+            }
+            println!("Opened {}", url);
         }
         Ok(())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_url_is_percent_encoded() {
+        let engine = SearchEngine::new("Google", "g", "https://www.google.com/search?q={query}");
+        assert_eq!(engine.search_url("c++ & rust"), "https://www.google.com/search?q=c%2B%2B%20%26%20rust");
+        assert_eq!(engine.search_url("café"), "https://www.google.com/search?q=caf%C3%A9");
+    }
+
+    #[test]
+    fn test_parse_bang() {
+        assert_eq!(parse_bang("!gh gpui"), Some(("gh", "gpui".to_string())));
+        assert_eq!(parse_bang("rust traits !w"), Some(("w", "rust traits".to_string())));
+        assert_eq!(parse_bang("hello world"), None);
+        assert_eq!(parse_bang("wow !"), None);
+    }
+
+    #[test]
+    fn test_detect_url() {
+        assert_eq!(detect_url("example.com"), Some("https://example.com/".to_string()));
+        assert_eq!(detect_url("github.com/rust-lang/rust"), Some("https://github.com/rust-lang/rust".to_string()));
+        assert_eq!(detect_url("http://example.com/a b"), None);
+        assert_eq!(detect_url("localhost:8080"), Some("http://localhost:8080/".to_string()));
+        assert_eq!(detect_url("192.168.1.1"), Some("http://192.168.1.1/".to_string()));
+        assert_eq!(detect_url("HTTPS://Example.com/Path"), Some("https://example.com/Path".to_string()));
+        assert_eq!(detect_url("3.14"), None);
+        assert_eq!(detect_url("main.rs"), Some("https://main.rs/".to_string()));
+        assert_eq!(detect_url("hello"), None);
+        assert_eq!(detect_url("ftp://example.com"), None);
+    }
 }
\ No newline at end of file
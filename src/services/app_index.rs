@@ -0,0 +1,306 @@
+// The installed applications as the platform's app_index module finds them, searched fuzzily over
+// their name, executable, keywords and description as well as the aliases users give them. The
+// index is cached on disk for the next start and kept current by watching the app directories.
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc, RwLock, Weak};
+use std::time::Duration;
+
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+
+use crate::platform::app_index;
+
+// Typing an alias exactly puts its app above anything that merely fuzzy matches
+const EXACT_ALIAS_BONUS: i64 = 1000;
+// Installers write many files, changes are applied once the directories were quiet this long
+const SETTLE: Duration = Duration::from_secs(1);
+
+// How an app is started: programs run with their arguments, anything else goes through the shell
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum Launch {
+    #[default]
+    Run,
+    // shortcuts to scripts, consoles and shell items, opened like a double click would
+    Open,
+    Url(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppEntry {
+    // what the app was found as, the .lnk path on Windows and the desktop file id on Linux
+    pub id: String,
+    pub name: String,
+    pub path: PathBuf,
+    // passed before the arguments typed after "--"
+    pub args: Vec<String>,
+    // "path,index" on Windows, an icon name or file on Linux, resolved by the icon service
+    pub icon: Option<String>,
+    // executable file name without extension, "code" for Visual Studio Code
+    pub exec: String,
+    pub description: Option<String>,
+    pub keywords: Vec<String>,
+    pub launch: Launch,
+}
+
+fn cache_path() -> PathBuf {
+    dirs::cache_dir()
+        .map(|dir| dir.join("Catalyst"))
+        .unwrap_or_else(|| std::env::temp_dir().join("catalyst"))
+        .join("app_index.json")
+}
+
+// Splits a command line into arguments, keeping quoted parts together
+pub fn split_arguments(text: &str) -> Vec<String> {
+    let mut arguments = Vec::new();
+    let mut current = String::new();
+    let mut in_argument = false;
+    let mut quote = None;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match (c, quote) {
+            ('\\', Some('"')) | ('\\', None) => match chars.next() {
+                Some(escaped) => current.push(escaped),
+                None => current.push('\\'),
+            },
+            (c, Some(q)) if c == q => quote = None,
+            ('"' | '\'', None) => {
+                quote = Some(c);
+                in_argument = true;
+            }
+            (c, None) if c.is_whitespace() => {
+                if in_argument {
+                    arguments.push(std::mem::take(&mut current));
+                    in_argument = false;
+                }
+            }
+            (c, _) => {
+                current.push(c);
+                in_argument = true;
+            }
+        }
+    }
+    if in_argument {
+        arguments.push(current);
+    }
+    arguments
+}
+
+pub struct AppIndex {
+    apps: RwLock<Vec<AppEntry>>,
+    cache: Option<PathBuf>,
+}
+
+impl AppIndex {
+    // Starts from the cached index so apps can be found right away, then rescans and watches the
+    // app directories on a background thread
+    pub fn start() -> Arc<Self> {
+        let cache = cache_path();
+        let apps: Vec<AppEntry> = std::fs::read_to_string(&cache)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        let index = Arc::new(Self::new(apps, Some(cache)));
+        let weak = Arc::downgrade(&index);
+        std::thread::spawn(move || watch(weak));
+        index
+    }
+
+    fn new(apps: Vec<AppEntry>, cache: Option<PathBuf>) -> Self {
+        Self { apps: RwLock::new(apps), cache }
+    }
+
+    // Scans every app directory again, returning how many apps were found
+    pub fn rebuild(&self) -> usize {
+        let apps = app_index::scan();
+        let count = apps.len();
+        *self.apps.write().unwrap() = apps;
+        self.save();
+        log::info!("Indexed {} apps", count);
+        count
+    }
+
+    // Applies changed files one by one; false when one of them needs a full rescan, like a
+    // removed folder of shortcuts
+    fn update(&self, paths: &[PathBuf]) -> bool {
+        let mut changes = Vec::new();
+        for path in paths.iter().collect::<HashSet<_>>() {
+            match app_index::load(path) {
+                Some(change) => changes.push(change),
+                None if path.extension().is_none() => return false,
+                None => {}
+            }
+        }
+        if changes.is_empty() {
+            return true;
+        }
+        let mut apps = self.apps.write().unwrap();
+        for (id, app) in changes {
+            apps.retain(|a| a.id != id);
+            match &app {
+                Some(app) => log::info!("App index: updated {}", app.name),
+                None => log::info!("App index: removed {}", id),
+            }
+            apps.extend(app);
+        }
+        drop(apps);
+        self.save();
+        true
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.cache else {
+            return;
+        };
+        let apps = self.apps.read().unwrap();
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .map_err(anyhow::Error::from)
+            .and_then(|_| Ok(serde_json::to_string(&*apps)?))
+            .and_then(|content| Ok(std::fs::write(path, content)?));
+        if let Err(e) = result {
+            log::warn!("Failed to save the app index: {}", e);
+        }
+    }
+
+    // Matching apps with their score, best first; `aliases` maps app names to extra names for them
+    pub fn search(&self, query: &str, aliases: &HashMap<String, Vec<String>>) -> Vec<(i64, AppEntry)> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let matcher = SkimMatcherV2::default().ignore_case();
+        let apps = self.apps.read().unwrap();
+        let mut matches: Vec<(i64, &AppEntry)> = apps
+            .iter()
+            .filter_map(|app| {
+                let aliases: Vec<&String> = aliases
+                    .iter()
+                    .filter(|(name, _)| name.eq_ignore_ascii_case(&app.name))
+                    .flat_map(|(_, aliases)| aliases)
+                    .collect();
+                Some((score(app, &aliases, query, &matcher)?, app))
+            })
+            .collect();
+        matches.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+        // shortcuts in different folders often stand for the same app
+        let mut names = HashSet::new();
+        matches
+            .into_iter()
+            .filter(|(_, app)| names.insert(app.name.as_str()))
+            .map(|(score, app)| (score, app.clone()))
+            .collect()
+    }
+}
+
+// Rescans once, then applies what changes in the app directories until the index is dropped
+fn watch(index: Weak<AppIndex>) {
+    match index.upgrade() {
+        Some(index) => index.rebuild(),
+        None => return,
+    };
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(tx) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            log::warn!("Can't watch the app directories, new apps need a reload: {}", e);
+            return;
+        }
+    };
+    for dir in app_index::application_dirs() {
+        if let Err(e) = watcher.watch(&dir, RecursiveMode::Recursive) {
+            log::debug!("Not watching {}: {}", dir.display(), e);
+        }
+    }
+    while let Ok(first) = rx.recv() {
+        let mut events = vec![first];
+        while let Ok(event) = rx.recv_timeout(SETTLE) {
+            events.push(event);
+        }
+        let Some(index) = index.upgrade() else {
+            return;
+        };
+        let mut paths = Vec::new();
+        let mut rescan = false;
+        for event in events {
+            match event {
+                Ok(event) if !matches!(event.kind, EventKind::Access(_)) => paths.extend(event.paths),
+                Ok(_) => {}
+                Err(e) => {
+                    log::warn!("Watching the app directories failed: {}", e);
+                    rescan = true;
+                }
+            }
+        }
+        if rescan || !index.update(&paths) {
+            index.rebuild();
+        }
+    }
+}
+
+// The best of the fields' fuzzy scores, a field counting for less the further it is from the
+// app's name; descriptions are long enough to fuzzy match almost anything, so they need the query
+// as a whole
+fn score(app: &AppEntry, aliases: &[&String], query: &str, matcher: &SkimMatcherV2) -> Option<i64> {
+    let fuzzy = |text: &str, weight: i64| matcher.fuzzy_match(text, query).map(|score| score * weight / 4);
+    let description = app
+        .description
+        .as_deref()
+        .filter(|d| d.to_lowercase().contains(&query.to_lowercase()))
+        .and_then(|d| fuzzy(d, 1));
+    let best = std::iter::once(fuzzy(&app.name, 4))
+        .chain(aliases.iter().map(|alias| fuzzy(alias, 4)))
+        .chain(std::iter::once(fuzzy(&app.exec, 3)))
+        .chain(app.keywords.iter().map(|keyword| fuzzy(keyword, 2)))
+        .chain(std::iter::once(description))
+        .flatten()
+        .max()?;
+    let exact_alias = aliases.iter().any(|alias| alias.eq_ignore_ascii_case(query));
+    Some(if exact_alias { best + EXACT_ALIAS_BONUS } else { best })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(name: &str, exec: &str, keywords: &[&str]) -> AppEntry {
+        AppEntry {
+            id: format!("{}.desktop", exec),
+            name: name.to_string(),
+            path: PathBuf::from(exec),
+            exec: exec.to_string(),
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_search() {
+        let index = AppIndex::new(vec![
+            app("Visual Studio Code", "code", &["editor", "ide"]),
+            app("Calculator", "gnome-calculator", &["math"]),
+            app("Firefox", "firefox", &["browser", "web"]),
+            app("Firefox", "firefox-esr", &[]),
+        ], None);
+        let names = |query: &str, aliases: &HashMap<String, Vec<String>>| -> Vec<String> {
+            index.search(query, aliases).into_iter().map(|(_, app)| app.name).collect()
+        };
+        let none = HashMap::new();
+        assert_eq!(names("vsc", &none), ["Visual Studio Code"]);
+        assert_eq!(names("code", &none), ["Visual Studio Code"]);
+        assert_eq!(names("browser", &none), ["Firefox"]);
+        assert_eq!(names("fire", &none), ["Firefox"]);
+        assert!(names("zzz", &none).is_empty());
+
+        let aliases = HashMap::from([("calculator".to_string(), vec!["calc".to_string()])]);
+        assert_eq!(names("calc", &aliases)[0], "Calculator");
+        assert_eq!(
+            split_arguments(r#"--new-window "My Project" 'a b' c\ d"#),
+            ["--new-window", "My Project", "a b", "c d"]
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+
+use futures::channel::mpsc as futures_mpsc;
+use image::{imageops::FilterType, RgbaImage};
+use once_cell::sync::Lazy;
+
+#[derive(Debug, Clone, PartialEq)]
+enum IconEntry {
+    Loading,
+    Ready(PathBuf),
+    Missing,
+}
+
+struct Shared {
+    entries: Mutex<HashMap<(String, u32), IconEntry>>,
+    listeners: Mutex<Vec<futures_mpsc::UnboundedSender<()>>>,
+}
+
+// Resolves app and file icons to PNGs of a given size, rasterized on a worker thread
+// and kept in a disk cache so the next start doesn't decode them again
+pub struct IconService {
+    shared: Arc<Shared>,
+    jobs: Mutex<mpsc::Sender<(String, u32)>>,
+}
+
+static ICONS: Lazy<IconService> = Lazy::new(IconService::start);
+
+fn cache_dir() -> PathBuf {
+    dirs::cache_dir()
+        .map(|dir| dir.join("Catalyst").join("icons"))
+        .unwrap_or_else(|| std::env::temp_dir().join("catalyst-icons"))
+}
+
+impl IconService {
+    pub fn global() -> &'static IconService {
+        &ICONS
+    }
+
+    fn start() -> Self {
+        let shared = Arc::new(Shared {
+            entries: Mutex::new(HashMap::new()),
+            listeners: Mutex::new(Vec::new()),
+        });
+        let (tx, rx) = mpsc::channel::<(String, u32)>();
+        let worker_shared = shared.clone();
+        std::thread::spawn(move || {
+            let cache_dir = cache_dir();
+            for (source, size) in rx {
+                let entry = match load(&source, size, &cache_dir) {
+                    Some(path) => IconEntry::Ready(path),
+                    None => {
+                        log::debug!("No icon found for {}", source);
+                        IconEntry::Missing
+                    }
+                };
+                worker_shared.entries.lock().unwrap().insert((source, size), entry);
+                worker_shared.listeners.lock().unwrap().retain(|tx| tx.unbounded_send(()).is_ok());
+            }
+        });
+        Self {
+            shared,
+            jobs: Mutex::new(tx),
+        }
+    }
+
+    // Returns the cached PNG when it is ready, otherwise queues it and returns None
+    pub fn get(&self, source: &str, size: u32) -> Option<PathBuf> {
+        let key = (source.to_string(), size);
+        let mut entries = self.shared.entries.lock().unwrap();
+        match entries.get(&key) {
+            Some(IconEntry::Ready(path)) => Some(path.clone()),
+            Some(IconEntry::Loading) | Some(IconEntry::Missing) => None,
+            None => {
+                entries.insert(key.clone(), IconEntry::Loading);
+                let _ = self.jobs.lock().unwrap().send(key);
+                None
+            }
+        }
+    }
+
+    // Receives a message every time an icon finished loading
+    pub fn subscribe(&self) -> futures_mpsc::UnboundedReceiver<()> {
+        let (tx, rx) = futures_mpsc::unbounded();
+        self.shared.listeners.lock().unwrap().push(tx);
+        rx
+    }
+}
+
+// Expands %VAR% references used in Windows icon locations
+fn expand_env(source: &str) -> String {
+    let mut result = String::new();
+    let mut rest = source;
+    while let Some(start) = rest.find('%') {
+        let Some(len) = rest[start + 1..].find('%') else {
+            break;
+        };
+        let name = &rest[start + 1..start + 1 + len];
+        result.push_str(&rest[..start]);
+        match std::env::var(name) {
+            Ok(value) => result.push_str(&value),
+            Err(_) => result.push_str(&rest[start..start + len + 2]),
+        }
+        rest = &rest[start + len + 2..];
+    }
+    result.push_str(rest);
+    result
+}
+
+// "shell32.dll,-3" -> ("shell32.dll", -3)
+fn split_icon_index(source: &str) -> (&str, i32) {
+    match source.rsplit_once(',') {
+        Some((path, index)) => match index.trim().parse::<i32>() {
+            Ok(index) => (path, index),
+            Err(_) => (source, 0),
+        },
+        None => (source, 0),
+    }
+}
+
+// Icon names are looked up in the icon theme, which only Linux has
+#[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+fn resolve(source: &str, size: u32) -> Option<(PathBuf, i32)> {
+    let expanded = expand_env(source);
+    let (path, index) = split_icon_index(&expanded);
+    if path.contains('/') || path.contains('\\') {
+        let path = PathBuf::from(path);
+        return path.exists().then_some((path, index));
+    }
+    #[cfg(target_os = "linux")]
+    {
+        return crate::platform::linux::lookup_icon(path, size).map(|path| (path, 0));
+    }
+    #[allow(unreachable_code)]
+    None
+}
+
+// `index` picks an icon inside .exe and .dll files, which only Windows reads
+#[cfg_attr(not(target_os = "windows"), allow(unused_variables))]
+fn rasterize(path: &Path, index: i32, size: u32) -> Option<RgbaImage> {
+    let extension = path.extension()?.to_string_lossy().to_lowercase();
+    let image = match extension.as_str() {
+        "svg" => return render_svg(path, size),
+        #[cfg(target_os = "windows")]
+        "exe" | "dll" => crate::platform::windows::icons::extract_icon(path, index)?,
+        _ => image::open(path).ok()?.to_rgba8(),
+    };
+    if image.width() == size && image.height() == size {
+        return Some(image);
+    }
+    Some(image::imageops::resize(&image, size, size, FilterType::Lanczos3))
+}
+
+fn render_svg(path: &Path, size: u32) -> Option<RgbaImage> {
+    use resvg::{tiny_skia, usvg};
+
+    let data = std::fs::read(path).ok()?;
+    let tree = usvg::Tree::from_data(&data, &usvg::Options::default()).ok()?;
+    let scale = size as f32 / tree.size().width().max(tree.size().height());
+    let mut pixmap = tiny_skia::Pixmap::new(size, size)?;
+    resvg::render(&tree, tiny_skia::Transform::from_scale(scale, scale), &mut pixmap.as_mut());
+    let pixels = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    RgbaImage::from_raw(size, size, pixels)
+}
+
+fn load(source: &str, size: u32, cache_dir: &Path) -> Option<PathBuf> {
+    let (path, index) = resolve(source, size)?;
+    let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    (&path, index, modified).hash(&mut hasher);
+    let cached = cache_dir.join(format!("{:016x}-{}.png", hasher.finish(), size));
+    if cached.exists() {
+        return Some(cached);
+    }
+    let image = rasterize(&path, index, size)?;
+    std::fs::create_dir_all(cache_dir).ok()?;
+    image.save_with_format(&cached, image::ImageFormat::Png).ok()?;
+    Some(cached)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_env() {
+        std::env::set_var("CATALYST_ICON_TEST", "C:\\Program Files");
+        assert_eq!(expand_env("%CATALYST_ICON_TEST%\\app.exe"), "C:\\Program Files\\app.exe");
+        assert_eq!(expand_env("%CATALYST_ICON_UNSET%\\app.exe"), "%CATALYST_ICON_UNSET%\\app.exe");
+        assert_eq!(expand_env("100% sure"), "100% sure");
+        assert_eq!(expand_env("plain"), "plain");
+    }
+
+    #[test]
+    fn test_split_icon_index() {
+        assert_eq!(split_icon_index("shell32.dll,-3"), ("shell32.dll", -3));
+        assert_eq!(split_icon_index("app.exe, 2"), ("app.exe", 2));
+        assert_eq!(split_icon_index("app.exe"), ("app.exe", 0));
+        assert_eq!(split_icon_index("Hello, World.png"), ("Hello, World.png", 0));
+    }
+
+    #[test]
+    fn test_resolve_paths() {
+        let dir = std::env::temp_dir().join(format!("catalyst-icons-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let icon = dir.join("app.png");
+        std::fs::write(&icon, "").unwrap();
+        let source = icon.to_string_lossy().to_string();
+        assert_eq!(resolve(&source, 32), Some((icon.clone(), 0)));
+        assert_eq!(resolve(&format!("{},4", source), 32), Some((icon.clone(), 4)));
+        assert_eq!(resolve(&dir.join("missing.png").to_string_lossy(), 32), None);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
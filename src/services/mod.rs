@@ -0,0 +1,3 @@
+pub mod app_index;
+pub mod icons;
+pub mod suggestions;
\ No newline at end of file
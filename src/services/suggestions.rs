@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::core::config::data_dir;
+
+const WORDS: &str = include_str!("../../assets/suggestions/words.txt");
+
+// Source of search completions, e.g. local history or a remote suggestion API
+#[async_trait]
+pub trait SuggestionProvider: Send + Sync {
+    async fn suggest(&self, query: &str, limit: usize) -> Vec<String>;
+    // Called with the terms of a search the user actually ran
+    fn record(&self, _query: &str) {}
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct HistoryEntry {
+    count: u32,
+    last_used: u64,
+}
+
+// Suggestions from past searches plus a bundled word-frequency list, no network needed
+pub struct LocalSuggestions {
+    history: Mutex<HashMap<String, HistoryEntry>>,
+    words: Vec<&'static str>,
+    path: Option<PathBuf>,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl LocalSuggestions {
+    pub fn load() -> Self {
+        let path = data_dir().join("search_history.json");
+        let history = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self::new(history, Some(path))
+    }
+    fn new(history: HashMap<String, HistoryEntry>, path: Option<PathBuf>) -> Self {
+        let words = WORDS
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .collect();
+        Self {
+            history: Mutex::new(history),
+            words,
+            path,
+        }
+    }
+    fn save(&self, history: &HashMap<String, HistoryEntry>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = std::fs::create_dir_all(data_dir())
+            .map_err(anyhow::Error::from)
+            .and_then(|_| Ok(serde_json::to_string(history)?))
+            .and_then(|content| Ok(std::fs::write(path, content)?));
+        if let Err(e) = result {
+            log::warn!("Failed to save search history: {}", e);
+        }
+    }
+    // Past searches starting with the query, most used and then most recent first
+    fn history_matches(&self, query: &str) -> Vec<String> {
+        let history = self.history.lock().unwrap();
+        let mut matches: Vec<(&String, &HistoryEntry)> = history
+            .iter()
+            .filter(|(past, _)| past.starts_with(query) && past.as_str() != query)
+            .collect();
+        matches.sort_by(|a, b| b.1.count.cmp(&a.1.count).then(b.1.last_used.cmp(&a.1.last_used)));
+        matches.into_iter().map(|(past, _)| past.clone()).collect()
+    }
+    // Completes the last word of the query, the list is ordered by frequency
+    fn word_completions(&self, query: &str) -> Vec<String> {
+        let (prefix, last) = match query.rsplit_once(' ') {
+            Some((prefix, last)) => (format!("{} ", prefix), last),
+            None => (String::new(), query),
+        };
+        if last.is_empty() {
+            return Vec::new();
+        }
+        self.words
+            .iter()
+            .filter(|word| word.starts_with(last) && **word != last)
+            .map(|word| format!("{}{}", prefix, word))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl SuggestionProvider for LocalSuggestions {
+    async fn suggest(&self, query: &str, limit: usize) -> Vec<String> {
+        let query = query.trim_start().to_lowercase();
+        if query.is_empty() || limit == 0 {
+            return Vec::new();
+        }
+        let mut suggestions = self.history_matches(&query);
+        for word in self.word_completions(&query) {
+            if !suggestions.contains(&word) {
+                suggestions.push(word);
+            }
+        }
+        suggestions.truncate(limit);
+        suggestions
+    }
+    fn record(&self, query: &str) {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return;
+        }
+        let mut history = self.history.lock().unwrap();
+        let entry = history.entry(query).or_default();
+        entry.count += 1;
+        entry.last_used = now();
+        self.save(&history);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_comes_before_words() {
+        let provider = LocalSuggestions::new(HashMap::new(), None);
+        provider.record("weather berlin");
+        provider.record("weather berlin");
+        provider.record("weather paris");
+        let suggestions = futures::executor::block_on(provider.suggest("weat", 3));
+        assert_eq!(suggestions, vec!["weather berlin", "weather paris", "weather"]);
+    }
+
+    #[test]
+    fn test_completes_last_word() {
+        let provider = LocalSuggestions::new(HashMap::new(), None);
+        let suggestions = futures::executor::block_on(provider.suggest("Rust tut", 5));
+        assert_eq!(suggestions, vec!["rust tutorial"]);
+        assert!(futures::executor::block_on(provider.suggest("rust ", 5)).is_empty());
+    }
+}
\ No newline at end of file
@@ -1,7 +1,9 @@
 pub mod result_list;
 pub mod result_row;
 pub mod search_bar;
+pub mod plugin_header;
 
 pub use result_list::*;
 pub use result_row::*;
-pub use search_bar::*;
\ No newline at end of file
+pub use search_bar::*;
+pub use plugin_header::*;
\ No newline at end of file
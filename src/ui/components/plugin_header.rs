@@ -0,0 +1,41 @@
+use gpui:: {
+    App, SharedString, Window, div, prelude::*, px
+};
+
+use crate::ui::theme::theme;
+
+#[derive(IntoElement)]
+pub struct PluginHeader {
+    title: SharedString,
+}
+
+impl PluginHeader {
+    pub fn new(title: impl Into<SharedString>) -> Self {
+        Self { title: title.into() }
+    }
+}
+
+impl RenderOnce for PluginHeader {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = theme(cx).clone();
+        div()
+            .flex()
+            .w(px(600.0))
+            .h(px(30.0))
+            .px_4()
+            .items_center()
+            .justify_between()
+            .child(
+                div()
+                    .font_weight(gpui::FontWeight::MEDIUM)
+                    .text_color(theme.text)
+                    .truncate()
+                    .child(self.title)
+            )
+            .child(
+                div()
+                    .text_color(theme.text_muted)
+                    .child("Esc to go back")
+            )
+    }
+}
@@ -2,6 +2,7 @@ use gpui:: {
     App, Application, AssetSource, AsyncApp, Bounds, Context, Entity, FocusHandle, Focusable, KeyBinding, KeyDownEvent, Point, ScrollHandle, SharedString, Size, Subscription, Window, WindowBounds, WindowOptions, actions, div, prelude::*, px, rgb, size
 };
 use crate::core::model::ResultItem;
+use crate::ui::theme::theme;
 use super::result_row::ResultRow;
 
 #[derive(IntoElement)]
@@ -20,6 +21,7 @@ impl ResultList {
 
 impl RenderOnce for ResultList {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = theme(cx).clone();
         if self.results.is_empty() {
             return div()
                 .id("empty");
@@ -31,7 +33,7 @@ impl RenderOnce for ResultList {
                 // .w_full()
                 .w(px(600.0))
                 .max_h(px(400.0))
-                .bg(gpui::black())
+                .bg(theme.list_background)
                 .border_0()
                 .overflow_y_scroll()
                 .track_scroll(&self.scroll_handle);
@@ -41,7 +43,7 @@ impl RenderOnce for ResultList {
                     .p_4()
                     .flex()
                     .justify_center()
-                    .text_color(gpui::rgb(0x666666))
+                    .text_color(theme.text_muted)
                     .child("Searching...")
             );
         } else {
@@ -1,9 +1,11 @@
 use gpui:: {
-    App, Application, AssetSource, AsyncApp, Bounds, Context, Entity, FocusHandle, Focusable, KeyBinding, KeyDownEvent, Point, ScrollHandle, SharedString, Size, Subscription, Window, WindowBounds, WindowOptions, actions, div, prelude::*, px, rgb, size
+    App, Application, AssetSource, AsyncApp, Bounds, Context, Entity, FocusHandle, Focusable, KeyBinding, KeyDownEvent, Point, ScrollHandle, SharedString, Size, Subscription, Window, WindowBounds, WindowOptions, actions, div, img, svg, prelude::*, px, rgb, size
 };
 use crate::core::model::ResultItem;
 use crate::core::model::ResultIcon;
 use crate::core::model::BuiltInIcon;
+use crate::services::icons::IconService;
+use crate::ui::theme::theme;
 
 #[derive(IntoElement)]
 pub struct ResultRow {
@@ -27,23 +29,36 @@ impl RenderOnce for ResultRow {
         let icon = self.result.icon.clone();
         let title = self.result.title.clone();
         let subtitle = self.result.subtitle.clone();
+        let theme = theme(cx).clone();
 
         let result_icon = |icon: &ResultIcon| -> gpui::AnyElement {
             match icon {
-                ResultIcon::Path(_path) => {
-                    div()
-                        .size(px(40.0))
-                        .flex()
-                        .items_center()
-                        .justify_center()
-                        .child(
-                            title
-                                .chars()
-                                .next()
-                                .unwrap_or('?')
-                                .to_string()
-                        )
-                        .into_any_element()
+                ResultIcon::Path(path) | ResultIcon::AppIcon(path) => {
+                    match IconService::global().get(path, 64) {
+                        Some(png) => div()
+                            .size(px(40.0))
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .child(img(png).size(px(32.0)))
+                            .into_any_element(),
+                        // placeholder until the icon service has loaded the image
+                        None => div()
+                            .size(px(40.0))
+                            .rounded_md()
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .text_color(theme.text)
+                            .child(
+                                title
+                                    .chars()
+                                    .next()
+                                    .unwrap_or('?')
+                                    .to_string()
+                            )
+                            .into_any_element(),
+                    }
                 }
                 ResultIcon::Emoji(emoji) => {
                     div()
@@ -54,45 +69,20 @@ impl RenderOnce for ResultRow {
                         .child(emoji.clone())
                         .into_any_element()
                 }
-                ResultIcon::AppIcon(_app_path) => {
+                ResultIcon::BuiltIn(builtin_icon) => {
                     div()
                         .size(px(40.0))
-                        .rounded_md()
                         .flex()
                         .items_center()
                         .justify_center()
                         .child(
-                            title
-                                .chars()
-                                .next()
-                                .unwrap_or('?')
-                                .to_string()
+                            svg()
+                                .path(builtin_icon.asset_path())
+                                .size(px(24.0))
+                                .text_color(theme.text)
                         )
                         .into_any_element()
                 }
-                ResultIcon::BuiltIn(builtin_icon) => {
-                    let icon = match builtin_icon {
-                        BuiltInIcon::Calculator => "🔢",
-                        BuiltInIcon::Search => "🔍",
-                        BuiltInIcon::File => "📄",
-                        BuiltInIcon::Folder => "📁",
-                        BuiltInIcon::Terminal => "💻",
-                        BuiltInIcon::Settings => "⚙️",
-                        BuiltInIcon::AI => "🤖",
-                        BuiltInIcon::Web => "🌐",
-                        BuiltInIcon::App => "📱",
-                        BuiltInIcon::Dictionary => "📖",
-                        BuiltInIcon::IP => "🌐",
-                        BuiltInIcon::Exit => "❌",
-                    };
-                    div()
-                        .size(px(40.0))
-                        .flex()
-                        .items_center()
-                        .justify_center()
-                        .child(icon)
-                        .into_any_element()
-                }
             }
         };
 
@@ -106,14 +96,14 @@ impl RenderOnce for ResultRow {
             .gap_3()
             .cursor_pointer()
             .rounded(px(5.0))
-            .bg(rgb(0x07006C))
+            .bg(theme.surface)
             .border_0()
             .when(is_selected, |this| {
-                this.bg(gpui::rgb(0x1000A9))
+                this.bg(theme.surface_selected)
             })
             .when(!is_selected, |this| {
                 this.hover(|style| {
-                    style.bg(gpui::rgb(0x1000A9))
+                    style.bg(theme.surface_selected)
                 })
             })
             .child(result_icon(&icon))
@@ -127,17 +117,17 @@ impl RenderOnce for ResultRow {
                     .child(
                         div()
                         .font_weight(gpui::FontWeight::MEDIUM)
-                            .text_color(gpui::white())
+                            .text_color(theme.text)
                             .truncate()
                             .child(title)
                             .when(is_selected, |this| {
-                                this.text_color(gpui::white())
+                                this.text_color(theme.text)
                             })
                     )
                     .when_some(subtitle, |this, subtitle| {
                         this.child(
                             div()
-                                .text_color(gpui::rgb(0x666666))
+                                .text_color(theme.text_muted)
                                 .truncate()
                                 .child(subtitle)
                         )
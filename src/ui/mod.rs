@@ -1,5 +1,8 @@
 pub mod components;
 pub mod views;
+pub mod navigation;
+pub mod theme;
 
 pub use components::*;
-pub use views::*;
\ No newline at end of file
+pub use views::*;
+pub use navigation::*;
\ No newline at end of file
@@ -0,0 +1,3 @@
+mod router;
+
+pub use router::*;
\ No newline at end of file
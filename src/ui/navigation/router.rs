@@ -0,0 +1,115 @@
+use crate::core::model::PluginView;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Route {
+    Launcher,
+    Plugin {
+        plugin_id: String,
+        view: PluginView,
+    },
+    Settings,
+    AiChat,
+}
+
+// What the view below looked like when a route was pushed on top of it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SavedState {
+    pub query: String,
+    pub selected_index: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    route: Route,
+    saved: SavedState,
+}
+
+pub struct Router {
+    stack: Vec<Entry>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self {
+            stack: vec![Entry { route: Route::Launcher, saved: SavedState::default() }],
+        }
+    }
+    pub fn current(&self) -> &Route {
+        // the launcher is never popped, so the stack is never empty
+        &self.stack.last().expect("Router stack is empty.").route
+    }
+    pub fn push(&mut self, route: Route, saved: SavedState) {
+        self.stack.push(Entry { route, saved });
+    }
+    // Returns the state to restore in the view that becomes visible
+    pub fn pop(&mut self) -> Option<SavedState> {
+        if !self.can_pop() {
+            return None;
+        }
+        self.stack.pop().map(|entry| entry.saved)
+    }
+    pub fn replace(&mut self, route: Route) {
+        if let Some(entry) = self.stack.last_mut() {
+            entry.route = route;
+        }
+    }
+    pub fn reset(&mut self) {
+        self.stack.truncate(1);
+    }
+    pub fn can_pop(&self) -> bool {
+        self.stack.len() > 1
+    }
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+}
+
+impl Default for Router {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin_route(title: &str) -> Route {
+        Route::Plugin {
+            plugin_id: "test".to_string(),
+            view: PluginView::list(title.to_string(), Vec::new()),
+        }
+    }
+
+    #[test]
+    fn test_launcher_is_never_popped() {
+        let mut router = Router::new();
+        assert_eq!(router.current(), &Route::Launcher);
+        assert!(!router.can_pop());
+        assert_eq!(router.pop(), None);
+        assert_eq!(router.depth(), 1);
+    }
+
+    #[test]
+    fn test_pop_restores_saved_state() {
+        let mut router = Router::new();
+        router.push(plugin_route("first"), SavedState { query: "def".to_string(), selected_index: 2 });
+        router.push(Route::Settings, SavedState { query: "".to_string(), selected_index: 0 });
+        assert_eq!(router.current(), &Route::Settings);
+
+        assert_eq!(router.pop(), Some(SavedState::default()));
+        assert_eq!(router.current(), &plugin_route("first"));
+        assert_eq!(router.pop(), Some(SavedState { query: "def".to_string(), selected_index: 2 }));
+        assert_eq!(router.current(), &Route::Launcher);
+    }
+
+    #[test]
+    fn test_reset_returns_to_launcher() {
+        let mut router = Router::new();
+        router.push(plugin_route("first"), SavedState::default());
+        router.push(plugin_route("second"), SavedState::default());
+        router.reset();
+        assert_eq!(router.current(), &Route::Launcher);
+        assert!(!router.can_pop());
+    }
+}
@@ -0,0 +1,245 @@
+use gpui::{App, Global, Rgba, rgb, rgba};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::core::config::config_dir;
+
+// Colors used by the launcher components
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThemeTokens {
+    // window and search bar
+    pub background: Rgba,
+    // area behind the result rows
+    pub list_background: Rgba,
+    // result rows, detail bodies and settings rows
+    pub surface: Rgba,
+    // selected and hovered rows, buttons
+    pub surface_selected: Rgba,
+    pub text: Rgba,
+    pub text_muted: Rgba,
+    pub error: Rgba,
+    // picks the matching adabraka theme for inputs and toggles
+    pub is_dark: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    pub tokens: ThemeTokens,
+}
+
+impl ThemeTokens {
+    pub fn dark() -> Self {
+        Self {
+            background: rgb(0x13144A),
+            list_background: rgb(0x000000),
+            surface: rgb(0x07006C),
+            surface_selected: rgb(0x1000A9),
+            text: rgb(0xFFFFFF),
+            text_muted: rgb(0x666666),
+            error: rgb(0xE5484D),
+            is_dark: true,
+        }
+    }
+    pub fn light() -> Self {
+        Self {
+            background: rgb(0xF4F4F7),
+            list_background: rgb(0xFFFFFF),
+            surface: rgb(0xFFFFFF),
+            surface_selected: rgb(0xDCE3FF),
+            text: rgb(0x1C1C28),
+            text_muted: rgb(0x70707F),
+            error: rgb(0xC62828),
+            is_dark: false,
+        }
+    }
+    pub fn high_contrast() -> Self {
+        Self {
+            background: rgb(0x000000),
+            list_background: rgb(0x000000),
+            surface: rgb(0x000000),
+            surface_selected: rgb(0x0000FF),
+            text: rgb(0xFFFFFF),
+            text_muted: rgb(0xFFFF00),
+            error: rgb(0xFF4040),
+            is_dark: true,
+        }
+    }
+    fn set(&mut self, key: &str, color: Rgba) -> bool {
+        let slot = match key {
+            "background" => &mut self.background,
+            "list_background" => &mut self.list_background,
+            "surface" => &mut self.surface,
+            "surface_selected" => &mut self.surface_selected,
+            "text" => &mut self.text,
+            "text_muted" => &mut self.text_muted,
+            "error" => &mut self.error,
+            _ => return false,
+        };
+        *slot = color;
+        true
+    }
+}
+
+fn builtin_themes() -> Vec<Theme> {
+    vec![
+        Theme { name: "dark".to_string(), tokens: ThemeTokens::dark() },
+        Theme { name: "light".to_string(), tokens: ThemeTokens::light() },
+        Theme { name: "high-contrast".to_string(), tokens: ThemeTokens::high_contrast() },
+    ]
+}
+
+// A user theme, e.g. `<config dir>/themes/nord.toml`:
+//
+// name = "Nord"
+// base = "dark"
+// is_dark = true
+// [colors]
+// background = "#2E3440"
+#[derive(Debug, Deserialize)]
+struct ThemeFile {
+    name: Option<String>,
+    base: Option<String>,
+    is_dark: Option<bool>,
+    #[serde(default)]
+    colors: HashMap<String, String>,
+}
+
+// "#RGB", "#RRGGBB" or "#RRGGBBAA", the leading '#' is optional
+fn parse_color(value: &str) -> Option<Rgba> {
+    let hex = value.trim().trim_start_matches('#');
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let hex = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect(),
+        _ => hex.to_string(),
+    };
+    let parsed = u32::from_str_radix(&hex, 16).ok()?;
+    match hex.len() {
+        6 => Some(rgb(parsed)),
+        8 => Some(rgba(parsed)),
+        _ => None,
+    }
+}
+
+fn load_theme_file(path: &Path) -> anyhow::Result<Theme> {
+    let content = std::fs::read_to_string(path)?;
+    let file: ThemeFile = toml::from_str(&content)?;
+    let name = file.name.unwrap_or_else(|| {
+        path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default()
+    });
+    let base = file.base.as_deref().unwrap_or("dark");
+    let mut tokens = builtin_themes()
+        .into_iter()
+        .find(|t| t.name.eq_ignore_ascii_case(base))
+        .map(|t| t.tokens)
+        .ok_or_else(|| anyhow::anyhow!("Unknown base theme {}", base))?;
+    if let Some(is_dark) = file.is_dark {
+        tokens.is_dark = is_dark;
+    }
+    for (key, value) in &file.colors {
+        let color = parse_color(value).ok_or_else(|| anyhow::anyhow!("Invalid color {} for {}", value, key))?;
+        if !tokens.set(key, color) {
+            log::warn!("Unknown theme color {} in {}", key, path.display());
+        }
+    }
+    Ok(Theme { name, tokens })
+}
+
+fn user_themes() -> Vec<Theme> {
+    let mut themes = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(config_dir().join("themes")) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == "toml") {
+                match load_theme_file(&path) {
+                    Ok(theme) => themes.push(theme),
+                    Err(e) => log::error!("Failed to load theme {}: {}", path.display(), e),
+                }
+            }
+        }
+    }
+    themes.sort_by(|a, b| a.name.cmp(&b.name));
+    themes
+}
+
+// Built-in themes followed by the ones in the config dir
+pub fn available_themes() -> Vec<Theme> {
+    let mut themes = builtin_themes();
+    themes.extend(user_themes());
+    themes
+}
+
+pub struct ActiveTheme(pub ThemeTokens);
+
+impl Global for ActiveTheme {}
+
+pub fn theme(cx: &App) -> &ThemeTokens {
+    &cx.global::<ActiveTheme>().0
+}
+
+// Switches every window to the named theme, falling back to dark
+pub fn apply_theme(name: &str, cx: &mut App) {
+    let tokens = available_themes()
+        .into_iter()
+        .find(|t| t.name.eq_ignore_ascii_case(name))
+        .map(|t| t.tokens)
+        .unwrap_or_else(|| {
+            log::warn!("Theme {} not found, using dark.", name);
+            ThemeTokens::dark()
+        });
+    let mut base = if tokens.is_dark {
+        adabraka_ui::prelude::Theme::dark()
+    } else {
+        adabraka_ui::prelude::Theme::light()
+    };
+    base.tokens.ring = gpui::transparent_black();
+    base.tokens.foreground = tokens.text.into();
+    base.tokens.muted_foreground = tokens.text_muted.into();
+    base.tokens.background = tokens.background.into();
+    adabraka_ui::prelude::install_theme(cx, base);
+    cx.set_global(ActiveTheme(tokens));
+    cx.refresh_windows();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("#fff"), Some(rgb(0xFFFFFF)));
+        assert_eq!(parse_color("#1a2"), Some(rgb(0x11AA22)));
+        assert_eq!(parse_color("#2E3440"), Some(rgb(0x2E3440)));
+        assert_eq!(parse_color(" 2e3440 "), Some(rgb(0x2E3440)));
+        assert_eq!(parse_color("#2E344080"), Some(rgba(0x2E344080)));
+        for invalid in ["", "#", "#12", "#12345", "#GGGGGG", "#+12345", "red", "#2E3440801"] {
+            assert_eq!(parse_color(invalid), None, "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_partial_theme_file() {
+        let dir = std::env::temp_dir().join(format!("catalyst-themes-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("paper.toml");
+        std::fs::write(&path, "base = \"light\"\n[colors]\nbackground = \"#fafafa\"\naccent = \"#ff0000\"\n").unwrap();
+        let theme = load_theme_file(&path).unwrap();
+        assert_eq!(theme.name, "paper");
+        // colors the file leaves out come from the base theme
+        assert_eq!(theme.tokens, ThemeTokens { background: rgb(0xFAFAFA), ..ThemeTokens::light() });
+
+        std::fs::write(&path, "name = \"Dim\"\nis_dark = false\n").unwrap();
+        let theme = load_theme_file(&path).unwrap();
+        assert_eq!(theme.name, "Dim");
+        assert_eq!(theme.tokens, ThemeTokens { is_dark: false, ..ThemeTokens::dark() });
+
+        std::fs::write(&path, "[colors]\ntext = \"white\"\n").unwrap();
+        assert!(load_theme_file(&path).is_err());
+        std::fs::write(&path, "base = \"solarized\"\n").unwrap();
+        assert!(load_theme_file(&path).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,33 @@
+use gpui:: {
+    App, Window, div, prelude::*
+};
+
+use crate::ui::components::PluginHeader;
+use crate::ui::theme::theme;
+
+#[derive(IntoElement)]
+pub struct AiChatView {}
+
+impl AiChatView {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl RenderOnce for AiChatView {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = theme(cx).clone();
+        div()
+            .flex()
+            .p_2()
+            .flex_col()
+            .gap_1()
+            .child(PluginHeader::new("AI Chat"))
+            .child(
+                div()
+                    .p_4()
+                    .text_color(theme.text_muted)
+                    .child("No AI provider is configured")
+            )
+    }
+}
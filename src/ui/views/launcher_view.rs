@@ -0,0 +1,33 @@
+use gpui:: {
+    App, ScrollHandle, Window, div, prelude::*
+};
+
+use crate::core::model::ResultItem;
+use crate::ui::components::ResultList;
+
+#[derive(IntoElement)]
+pub struct LauncherView {
+    results: Vec<ResultItem>,
+    selected_index: usize,
+    is_searching: bool,
+    scroll_handle: ScrollHandle,
+}
+
+impl LauncherView {
+    pub fn new(results: Vec<ResultItem>, selected_index: usize, is_searching: bool, scroll_handle: ScrollHandle) -> Self {
+        Self { results, selected_index, is_searching, scroll_handle }
+    }
+}
+
+impl RenderOnce for LauncherView {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        div()
+            .flex()
+            .p_2()
+            .flex_col()
+            .gap_1()
+            .child(
+                ResultList::new(self.results, self.selected_index, self.is_searching, self.scroll_handle)
+            )
+    }
+}
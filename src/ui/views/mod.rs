@@ -1,3 +1,9 @@
 mod launcher_view;
+mod plugin_view;
+mod settings_view;
+mod ai_chat_view;
 
-pub use launcher_view::*;
\ No newline at end of file
+pub use launcher_view::*;
+pub use plugin_view::*;
+pub use settings_view::*;
+pub use ai_chat_view::*;
\ No newline at end of file
@@ -0,0 +1,69 @@
+use gpui:: {
+    App, ScrollHandle, Window, div, prelude::*, px
+};
+
+use crate::core::model::{PluginView, ResultItem};
+use crate::ui::components::{PluginHeader, ResultList};
+use crate::ui::theme::theme;
+
+#[derive(IntoElement)]
+pub struct PluginViewPanel {
+    view: PluginView,
+    // rows left after filtering by the search bar, for list views
+    visible_items: Vec<ResultItem>,
+    selected_index: usize,
+    scroll_handle: ScrollHandle,
+}
+
+impl PluginViewPanel {
+    pub fn new(view: PluginView, visible_items: Vec<ResultItem>, selected_index: usize, scroll_handle: ScrollHandle) -> Self {
+        Self { view, visible_items, selected_index, scroll_handle }
+    }
+}
+
+impl RenderOnce for PluginViewPanel {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = theme(cx).clone();
+        let container = div()
+            .flex()
+            .p_2()
+            .flex_col()
+            .gap_1()
+            .child(PluginHeader::new(self.view.title().clone()));
+        match self.view {
+            PluginView::List { .. } => {
+                if self.visible_items.is_empty() {
+                    return container.child(
+                        div()
+                            .p_4()
+                            .flex()
+                            .justify_center()
+                            .text_color(theme.text_muted)
+                            .child("No matching items")
+                    );
+                }
+                container.child(
+                    ResultList::new(self.visible_items, self.selected_index, false, self.scroll_handle)
+                )
+            }
+            PluginView::Detail { body, .. } => {
+                container
+                    .child(
+                        div()
+                            .id("plugin_detail")
+                            .w(px(600.0))
+                            .max_h(px(240.0))
+                            .p_4()
+                            .rounded(px(5.0))
+                            .bg(theme.surface)
+                            .text_color(theme.text)
+                            .overflow_y_scroll()
+                            .child(body)
+                    )
+                    .child(
+                        ResultList::new(self.visible_items, self.selected_index, false, self.scroll_handle)
+                    )
+            }
+        }
+    }
+}
@@ -0,0 +1,378 @@
+use gpui:: {
+    App, Context, Entity, EventEmitter, Modifiers, ScrollHandle, SharedString, Window, actions, div, prelude::*, px
+};
+use serde_json::Value;
+
+use adabraka_ui::components::hotkey_input::{HotkeyInput, HotkeyInputState, HotkeyValue};
+use adabraka_ui::components::input::{Input, InputVariant};
+use adabraka_ui::components::input_state::InputState;
+use adabraka_ui::components::toggle::Toggle;
+
+use crate::core::config::Config;
+use crate::core::plugin::{PluginSettings, SettingField, SettingKind};
+use crate::ui::components::PluginHeader;
+use crate::ui::theme::{available_themes, theme};
+
+actions!(Settings, [SaveSettings]);
+
+pub enum SettingsEvent {
+    Saved(Config),
+}
+
+enum Editor {
+    Toggle(bool),
+    Input(Entity<InputState>),
+    Choice {
+        options: Vec<String>,
+        selected: usize,
+    },
+    Hotkey(Entity<HotkeyInputState>),
+}
+
+struct SettingRow {
+    field: SettingField,
+    editor: Editor,
+}
+
+struct SettingsSection {
+    title: String,
+    // where the section lives in the config, e.g. ["plugins", "app_search"]
+    path: Vec<String>,
+    rows: Vec<SettingRow>,
+}
+
+pub struct SettingsView {
+    config: Config,
+    sections: Vec<SettingsSection>,
+    status: Option<(SharedString, bool)>,
+    scroll_handle: ScrollHandle,
+}
+
+fn core_sections() -> Vec<(&'static str, &'static str, Vec<SettingField>)> {
+    vec![
+        ("general", "General", vec![
+            SettingField::number("max_results", "Maximum results", 10),
+            SettingField::toggle("telemetry", "Send telemetry", false),
+        ]),
+        ("appearance", "Appearance", vec![
+            SettingField::choice("theme", "Theme", available_themes().into_iter().map(|t| t.name).collect(), "dark"),
+            // the launcher sizes itself to its results for now
+            SettingField::number("window_width", "Window width", 600).with_note("Not applied yet, the launcher keeps its own size"),
+            SettingField::number("window_height", "Window height", 500).with_note("Not applied yet, the launcher keeps its own size"),
+        ]),
+        ("hotkeys", "Hotkeys", vec![
+            SettingField::hotkey("show_launcher", "Show launcher", "Alt+Space"),
+        ]),
+    ]
+}
+
+// "Ctrl+Alt+Space" -> HotkeyValue, the format used by the config and global-hotkey
+fn parse_hotkey(shortcut: &str) -> Option<HotkeyValue> {
+    let mut modifiers = Modifiers::default();
+    let mut key = None;
+    for token in shortcut.split('+').map(|t| t.trim()) {
+        match token.to_lowercase().as_str() {
+            "ctrl" | "control" => modifiers.control = true,
+            "alt" | "option" => modifiers.alt = true,
+            "shift" => modifiers.shift = true,
+            "super" | "win" | "cmd" | "command" => modifiers.platform = true,
+            "" => return None,
+            other => key = Some(other.to_string()),
+        }
+    }
+    key.map(|key| HotkeyValue::new(key, modifiers))
+}
+
+fn format_hotkey(hotkey: &HotkeyValue) -> String {
+    let mut parts = Vec::new();
+    if hotkey.modifiers.control {
+        parts.push("Ctrl".to_string());
+    }
+    if hotkey.modifiers.alt {
+        parts.push("Alt".to_string());
+    }
+    if hotkey.modifiers.shift {
+        parts.push("Shift".to_string());
+    }
+    if hotkey.modifiers.platform {
+        parts.push("Super".to_string());
+    }
+    let mut chars = hotkey.key.chars();
+    let key = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    };
+    parts.push(key);
+    parts.join("+")
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+impl SettingsView {
+    pub fn new(config: Config, plugin_settings: Vec<PluginSettings>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let values = serde_json::to_value(&config).unwrap_or(Value::Null);
+        let mut sections = Vec::new();
+        for (key, title, fields) in core_sections() {
+            let path = vec![key.to_string()];
+            let rows = Self::build_rows(&values, &path, fields, window, cx);
+            sections.push(SettingsSection { title: title.to_string(), path, rows });
+        }
+        for plugin in plugin_settings {
+            let path = vec!["plugins".to_string(), plugin.plugin_id.clone()];
+            let rows = Self::build_rows(&values, &path, plugin.fields, window, cx);
+            sections.push(SettingsSection { title: plugin.name, path, rows });
+        }
+        Self {
+            config,
+            sections,
+            status: None,
+            scroll_handle: ScrollHandle::new(),
+        }
+    }
+
+    fn build_rows(values: &Value, path: &[String], fields: Vec<SettingField>, window: &mut Window, cx: &mut Context<Self>) -> Vec<SettingRow> {
+        let section = path.iter().try_fold(values, |value, key| value.get(key));
+        fields.into_iter().map(|field| {
+            let current = section
+                .and_then(|s| s.get(&field.key))
+                .cloned()
+                .unwrap_or_else(|| field.default.clone());
+            let editor = match &field.kind {
+                SettingKind::Toggle => Editor::Toggle(current.as_bool().unwrap_or(false)),
+                SettingKind::Number | SettingKind::Text => {
+                    let input_state = cx.new(InputState::new);
+                    let text = value_text(&current);
+                    input_state.update(cx, |state, cx| state.set_value(text, window, cx));
+                    Editor::Input(input_state)
+                }
+                SettingKind::Choice(options) => {
+                    let current = value_text(&current);
+                    let selected = options.iter().position(|o| *o == current).unwrap_or(0);
+                    Editor::Choice { options: options.clone(), selected }
+                }
+                SettingKind::Hotkey => {
+                    let state = match parse_hotkey(&value_text(&current)) {
+                        Some(hotkey) => cx.new(|cx| HotkeyInputState::with_hotkey(cx, hotkey)),
+                        None => cx.new(HotkeyInputState::new),
+                    };
+                    Editor::Hotkey(state)
+                }
+            };
+            SettingRow { field, editor }
+        }).collect()
+    }
+
+    fn toggle(&mut self, section: usize, row: usize, checked: bool, cx: &mut Context<Self>) {
+        if let Some(Editor::Toggle(value)) = self.sections.get_mut(section).and_then(|s| s.rows.get_mut(row)).map(|r| &mut r.editor) {
+            *value = checked;
+            cx.notify();
+        }
+    }
+
+    fn cycle_choice(&mut self, section: usize, row: usize, cx: &mut Context<Self>) {
+        if let Some(Editor::Choice { options, selected }) = self.sections.get_mut(section).and_then(|s| s.rows.get_mut(row)).map(|r| &mut r.editor) {
+            if !options.is_empty() {
+                *selected = (*selected + 1) % options.len();
+                cx.notify();
+            }
+        }
+    }
+
+    // Reads every editor back into a copy of the config
+    fn collect(&self, cx: &App) -> anyhow::Result<Config> {
+        let mut values = serde_json::to_value(&self.config)?;
+        for section in &self.sections {
+            for row in &section.rows {
+                let value = match &row.editor {
+                    Editor::Toggle(checked) => Value::from(*checked),
+                    Editor::Input(state) => {
+                        let text = state.read(cx).content().trim().to_string();
+                        match row.field.kind {
+                            SettingKind::Number => {
+                                let number: i64 = text.parse().map_err(|_| {
+                                    anyhow::anyhow!("{} must be a whole number", row.field.label)
+                                })?;
+                                Value::from(number)
+                            }
+                            _ => Value::from(text),
+                        }
+                    }
+                    Editor::Choice { options, selected } => {
+                        Value::from(options.get(*selected).cloned().unwrap_or_default())
+                    }
+                    Editor::Hotkey(state) => {
+                        let hotkey = state.read(cx).hotkey().ok_or_else(|| {
+                            anyhow::anyhow!("{} needs a shortcut", row.field.label)
+                        })?;
+                        Value::from(format_hotkey(hotkey))
+                    }
+                };
+                let mut target = &mut values;
+                for key in &section.path {
+                    target = &mut target[key.as_str()];
+                }
+                target[row.field.key.as_str()] = value;
+            }
+        }
+        Ok(serde_json::from_value(values)?)
+    }
+
+    fn save(&mut self, _: &SaveSettings, _window: &mut Window, cx: &mut Context<Self>) {
+        let result = self.collect(cx).and_then(|config| config.save().map(|path| (config, path)));
+        match result {
+            Ok((config, path)) => {
+                self.config = config.clone();
+                self.status = Some((format!("Saved to {}", path.display()).into(), false));
+                cx.emit(SettingsEvent::Saved(config));
+            }
+            Err(e) => {
+                log::error!("Failed to save settings: {}", e);
+                self.status = Some((e.to_string().into(), true));
+            }
+        }
+        cx.notify();
+    }
+}
+
+impl EventEmitter<SettingsEvent> for SettingsView {}
+
+impl Render for SettingsView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = theme(cx).clone();
+        let entity = cx.entity();
+        let mut list = div()
+            .id("settings_list")
+            .w(px(600.0))
+            .max_h(px(400.0))
+            .flex()
+            .flex_col()
+            .gap_1()
+            .overflow_y_scroll()
+            .track_scroll(&self.scroll_handle);
+
+        for (section_index, section) in self.sections.iter().enumerate() {
+            list = list.child(
+                div()
+                    .px_4()
+                    .pt_2()
+                    .font_weight(gpui::FontWeight::BOLD)
+                    .text_color(theme.text)
+                    .child(section.title.clone())
+            );
+            for (row_index, row) in section.rows.iter().enumerate() {
+                let id = SharedString::from(format!("{}.{}", section.path.join("."), row.field.key));
+                let editor = match &row.editor {
+                    Editor::Toggle(checked) => {
+                        let entity = entity.clone();
+                        Toggle::new(id.clone())
+                            .checked(*checked)
+                            .on_click(move |checked, _window, cx| {
+                                entity.update(cx, |this, cx| this.toggle(section_index, row_index, *checked, cx));
+                            })
+                            .into_any_element()
+                    }
+                    Editor::Input(state) => {
+                        Input::new(state)
+                            .w(px(220.0))
+                            .variant(InputVariant::Ghost)
+                            .bg(theme.background)
+                            .into_any_element()
+                    }
+                    Editor::Choice { options, selected } => {
+                        let entity = entity.clone();
+                        div()
+                            .id(id.clone())
+                            .px_3()
+                            .py_1()
+                            .rounded(px(5.0))
+                            .bg(theme.surface_selected)
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .child(options.get(*selected).cloned().unwrap_or_default())
+                            .on_click(move |_, _window, cx| {
+                                entity.update(cx, |this, cx| this.cycle_choice(section_index, row_index, cx));
+                            })
+                            .into_any_element()
+                    }
+                    Editor::Hotkey(state) => {
+                        HotkeyInput::new(state.clone())
+                            .w(px(220.0))
+                            .into_any_element()
+                    }
+                };
+                list = list.child(
+                    div()
+                        .flex()
+                        .w_full()
+                        .h(px(48.0))
+                        .px_4()
+                        .items_center()
+                        .justify_between()
+                        .rounded(px(5.0))
+                        .bg(theme.surface)
+                        .child(
+                            div()
+                                .flex()
+                                .flex_col()
+                                .child(
+                                    div()
+                                        .text_color(theme.text)
+                                        .child(row.field.label.clone())
+                                )
+                                .children(row.field.note.clone().map(|note| {
+                                    div()
+                                        .text_xs()
+                                        .text_color(theme.text_muted)
+                                        .child(note)
+                                }))
+                        )
+                        .child(editor)
+                );
+            }
+        }
+
+        let (status, is_error) = self.status.clone().unwrap_or_else(|| ("Ctrl+S to save".into(), false));
+        div()
+            .flex()
+            .p_2()
+            .flex_col()
+            .gap_1()
+            .key_context("Settings")
+            .on_action(cx.listener(Self::save))
+            .child(PluginHeader::new("Settings"))
+            .child(list)
+            .child(
+                div()
+                    .flex()
+                    .w(px(600.0))
+                    .h(px(30.0))
+                    .px_4()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .truncate()
+                            .text_color(if is_error { theme.error } else { theme.text_muted })
+                            .child(status)
+                    )
+                    .child(
+                        div()
+                            .id("settings_save")
+                            .px_3()
+                            .py_1()
+                            .rounded(px(5.0))
+                            .bg(theme.surface_selected)
+                            .text_color(theme.text)
+                            .cursor_pointer()
+                            .child("Save")
+                            .on_click(cx.listener(|this, _, window, cx| this.save(&SaveSettings, window, cx)))
+                    )
+            )
+    }
+}
\ No newline at end of file